@@ -6,11 +6,16 @@
 #![allow(deprecated)] // Builder still uses Source/FileSource internally during transition
 
 use crate::config::Config;
-use crate::error::Result;
-use crate::source::{EnvSource, FileSource, LayeredSource, MemorySource, Source};
-use crate::value::ConfigValue;
+use crate::discovery;
+use crate::error::{Error, Result};
+use crate::source::{
+    insert_nested, merge_values, ConflictPolicy, EnvSource, FileSource, LayeredSource,
+    MemorySource, Provenance, Source,
+};
+use crate::value::{ConfigValue, FromValue};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
 
 /// A builder for creating `Config` instances from multiple sources.
 ///
@@ -36,14 +41,40 @@ use std::path::Path;
 /// }
 /// ```
 pub struct ConfigBuilder {
-    sources: Vec<Box<dyn Source>>,
+    sources: Vec<Layer>,
+    cli_args: HashMap<String, ConfigValue>,
+    profile: Option<Profile>,
+    validators: Vec<Validator>,
 }
 
+/// A source added to the builder.
+enum Layer {
+    Source(Box<dyn Source>),
+    /// Added with `add_optional_env()`; skipped at build time if no
+    /// variable matches its prefix.
+    OptionalEnv(EnvSource),
+}
+
+/// A check registered with `ConfigBuilder::validate()`.
+type Validator = Box<dyn Fn(&ConfigValue) -> Result<()> + Send + Sync>;
+
+/// The profile section selected with `with_profile()`.
+struct Profile {
+    name: String,
+    required: bool,
+}
+
+/// The section every profile is merged over.
+const DEFAULT_PROFILE: &str = "default";
+
 impl ConfigBuilder {
     /// Create a new empty builder.
     pub fn new() -> Self {
         Self {
             sources: Vec::new(),
+            cli_args: HashMap::new(),
+            profile: None,
+            validators: Vec::new(),
         }
     }
 
@@ -51,7 +82,7 @@ impl ConfigBuilder {
     ///
     /// Sources added later override values from sources added earlier.
     pub fn add_source<S: Source + 'static>(mut self, source: S) -> Self {
-        self.sources.push(Box::new(source));
+        self.sources.push(Layer::Source(Box::new(source)));
         self
     }
 
@@ -62,15 +93,45 @@ impl ConfigBuilder {
         self.add_source(FileSource::new(path))
     }
 
+    /// Add a configuration file found by name, honoring `{NAME}_CONFIG`.
+    ///
+    /// If the environment variable from `discovery::config_env_var(name)`
+    /// (e.g. `MYAPP_CONFIG` for `"myapp"`) is set, the file at that path is
+    /// loaded, and building fails if it doesn't exist. Otherwise the
+    /// standard search paths are searched as with `Config::load()`.
+    pub fn add_named(mut self, name: &str) -> Self {
+        self.sources.push(Layer::Source(Box::new(NamedFileSource {
+            name: name.to_string(),
+            env_var: discovery::config_env_var(name),
+        })));
+        self
+    }
+
     /// Add a file source that may or may not exist.
     ///
     /// If the file doesn't exist, it will be skipped without error.
     pub fn add_optional_file(mut self, path: impl AsRef<Path>) -> Self {
         let path = path.as_ref();
-        self.sources.push(Box::new(OptionalFileSource {
-            name: path.to_string_lossy().into_owned(),
-            path: path.to_path_buf(),
-        }));
+        self.sources
+            .push(Layer::Source(Box::new(OptionalFileSource {
+                name: path.to_string_lossy().into_owned(),
+                path: path.to_path_buf(),
+            })));
+        self
+    }
+
+    /// Add every file matching a glob pattern, in lexicographic order.
+    ///
+    /// This supports `conf.d`-style drop-in directories: files that sort
+    /// later override earlier ones. A pattern that matches nothing adds
+    /// no layers.
+    ///
+    /// The pattern is expanded when the configuration is built.
+    #[cfg(feature = "glob")]
+    pub fn add_glob(mut self, pattern: &str) -> Self {
+        self.sources.push(Layer::Source(Box::new(GlobSource {
+            pattern: pattern.to_string(),
+        })));
         self
     }
 
@@ -82,6 +143,17 @@ impl ConfigBuilder {
         self.add_source(EnvSource::new(prefix))
     }
 
+    /// Add environment variables with the given prefix, but only if any are set.
+    ///
+    /// Like `add_env()`, except that the layer is dropped entirely when no
+    /// variable matches `prefix` at build time, so it never shows up in
+    /// merge diagnostics.
+    pub fn add_optional_env(mut self, prefix: impl Into<String>) -> Self {
+        self.sources
+            .push(Layer::OptionalEnv(EnvSource::new(prefix)));
+        self
+    }
+
     /// Add environment variables with a custom separator.
     pub fn add_env_with_separator(
         self,
@@ -91,19 +163,135 @@ impl ConfigBuilder {
         self.add_source(EnvSource::with_separator(prefix, separator))
     }
 
+    /// Add command-line overrides as `(key, value)` pairs.
+    ///
+    /// Keys are dotted paths such as `server.port`; a leading `--` is
+    /// ignored, so `("--server.port", "9000")` works too. Values are typed
+    /// with [`infer_scalar()`](crate::value::infer_scalar), the same as
+    /// environment variables, so lossy numbers such as `007` or integers
+    /// that overflow `i64` stay strings.
+    ///
+    /// CLI arguments always form the top layer, overriding every other
+    /// source regardless of the order methods are called in.
+    pub fn add_cli_args(mut self, args: &[(String, String)]) -> Self {
+        for (key, value) in args {
+            let key = key.trim_start_matches("--");
+            let parts: Vec<&str> = key.split('.').collect();
+            insert_nested(&mut self.cli_args, &parts, value.clone());
+        }
+        self
+    }
+
+    /// Select a profile section from the loaded configuration.
+    ///
+    /// The merged configuration is expected to contain a top-level `default`
+    /// section and one section per profile:
+    ///
+    /// ```toml
+    /// [default]
+    /// host = "localhost"
+    ///
+    /// [production]
+    /// host = "prod.example.com"
+    /// ```
+    ///
+    /// At build time the named section is deep-merged over `default`, and
+    /// the result becomes the whole configuration: section wrappers and
+    /// other profiles are dropped. CLI arguments still apply on top.
+    ///
+    /// Building fails with `KeyNotFound` if the profile section is absent;
+    /// use `with_optional_profile()` to fall back to `default` instead.
+    pub fn with_profile(mut self, name: impl Into<String>) -> Self {
+        self.profile = Some(Profile {
+            name: name.into(),
+            required: true,
+        });
+        self
+    }
+
+    /// Select a profile section, falling back to `default` if it is absent.
+    ///
+    /// See `with_profile()`.
+    pub fn with_optional_profile(mut self, name: impl Into<String>) -> Self {
+        self.profile = Some(Profile {
+            name: name.into(),
+            required: false,
+        });
+        self
+    }
+
     /// Add in-memory default values.
     pub fn add_defaults(self, defaults: ConfigValue) -> Self {
         self.add_source(MemorySource::with_name(defaults, "defaults"))
     }
 
+    /// Add a check that runs on the fully merged configuration.
+    ///
+    /// Validators run at the end of `build()`, after profile selection and
+    /// CLI arguments, in the order they were added. The first error is
+    /// returned from `build()` unchanged. Useful for rules that span
+    /// several keys or layers, such as mutually exclusive settings.
+    pub fn validate<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&ConfigValue) -> Result<()> + Send + Sync + 'static,
+    {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
     /// Build the configuration by loading and merging all sources.
+    ///
+    /// The result remembers which source provided each value; see
+    /// `Config::source_of()`. CLI arguments are reported as `"cli"`.
     pub async fn build(self) -> Result<Config> {
+        let sources = self
+            .sources
+            .into_iter()
+            .filter_map(|layer| match layer {
+                Layer::Source(source) => Some(source),
+                Layer::OptionalEnv(env) if env.has_vars() => Some(Box::new(env) as Box<dyn Source>),
+                Layer::OptionalEnv(_) => None,
+            })
+            .collect();
         let layered = LayeredSource {
-            sources: self.sources,
+            sources,
+            conflict_policy: ConflictPolicy::default(),
+            timeout: None,
         };
 
-        let data = layered.load().await?;
-        Ok(Config::new(data))
+        let (mut data, mut provenance) = layered.load_with_provenance().await?;
+        if let Some(profile) = &self.profile {
+            data = select_profile(data, profile)?;
+            provenance = select_profile_provenance(provenance, profile);
+        }
+        if !self.cli_args.is_empty() {
+            let cli_args = ConfigValue::Object(self.cli_args);
+            provenance.record(&cli_args, "cli");
+            merge_values(&mut data, cli_args);
+        }
+        for validator in &self.validators {
+            validator(&data)?;
+        }
+
+        Ok(Config::new(data).with_provenance(provenance.into_map()))
+    }
+
+    /// Build the configuration and convert its root into `T`.
+    ///
+    /// Shorthand for `build()` followed by `T::from_value()`, typically with
+    /// a struct that derives `FromValue`. Conversion errors carry the path
+    /// of the offending field.
+    pub async fn build_typed<T: FromValue>(self) -> Result<T> {
+        let config = self.build().await?;
+        T::from_value(config.data())
+    }
+
+    /// Build the configuration and wrap it for shared, read-only use.
+    ///
+    /// Convert the result into a `FrozenConfig` to prevent accidental
+    /// mutation through the handle.
+    pub async fn freeze(self) -> Result<Arc<Config>> {
+        self.build().await.map(Arc::new)
     }
 }
 
@@ -113,6 +301,43 @@ impl Default for ConfigBuilder {
     }
 }
 
+/// Merge the selected profile section over the `default` section.
+fn select_profile(data: ConfigValue, profile: &Profile) -> Result<ConfigValue> {
+    let mut sections = match data {
+        ConfigValue::Object(sections) => sections,
+        _ => HashMap::new(),
+    };
+
+    let mut merged = sections
+        .remove(DEFAULT_PROFILE)
+        .unwrap_or_else(|| ConfigValue::Object(HashMap::new()));
+
+    match sections.remove(&profile.name) {
+        Some(overlay) => merge_values(&mut merged, overlay),
+        None if profile.required => return Err(Error::KeyNotFound(profile.name.clone())),
+        None => {}
+    }
+
+    Ok(merged)
+}
+
+/// Re-key provenance the way `select_profile()` re-keys the data.
+fn select_profile_provenance(provenance: Provenance, profile: &Profile) -> Provenance {
+    let mut entries: Vec<_> = provenance.into_map().into_iter().collect();
+    entries.sort();
+
+    let mut selected = Provenance::default();
+    for section in [DEFAULT_PROFILE, profile.name.as_str()] {
+        let prefix = format!("{}.", section);
+        for (key, source_name) in &entries {
+            if let Some(key) = key.strip_prefix(&prefix) {
+                selected.insert(key.to_string(), source_name.clone());
+            }
+        }
+    }
+    selected
+}
+
 /// A file source that silently ignores missing files.
 struct OptionalFileSource {
     path: std::path::PathBuf,
@@ -133,11 +358,161 @@ impl Source for OptionalFileSource {
     }
 }
 
+/// A file found by name, or at the path in its `{NAME}_CONFIG` variable.
+struct NamedFileSource {
+    name: String,
+    env_var: String,
+}
+
+#[async_trait::async_trait]
+impl Source for NamedFileSource {
+    async fn load(&self) -> Result<ConfigValue> {
+        let path = discovery::find_config_file_env(&self.name, &self.env_var).await?;
+        FileSource::new(path).load().await
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A source that loads and merges every file matching a glob pattern.
+#[cfg(feature = "glob")]
+struct GlobSource {
+    pattern: String,
+}
+
+#[cfg(feature = "glob")]
+#[async_trait::async_trait]
+impl Source for GlobSource {
+    async fn load(&self) -> Result<ConfigValue> {
+        let source_error = |e: Box<dyn std::error::Error + Send + Sync>| Error::SourceError {
+            source_name: self.pattern.clone(),
+            source: e,
+        };
+
+        let mut paths = glob::glob(&self.pattern)
+            .map_err(|e| source_error(Box::new(e)))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| source_error(Box::new(e)))?;
+        paths.sort();
+
+        // Sniff the content of matches whose extension no formatter claims
+        let formatters = crate::registry::collect_formatters();
+        let mut merged = ConfigValue::Object(HashMap::new());
+        for path in paths {
+            let data = crate::loader::file::parse_file(&path, &formatters).await?;
+            merge_values(&mut merged, data);
+        }
+        Ok(merged)
+    }
+
+    fn name(&self) -> &str {
+        &self.pattern
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::value::test_helpers::obj;
 
+    #[tokio::test]
+    async fn test_builder_freeze() {
+        let config = ConfigBuilder::new()
+            .add_defaults(obj(vec![("port", ConfigValue::Integer(8080))]))
+            .freeze()
+            .await
+            .unwrap();
+
+        let frozen = crate::config::FrozenConfig::from(config.clone());
+        assert_eq!(frozen.get::<u16>("port").unwrap(), 8080);
+        assert!(Arc::ptr_eq(frozen.as_arc(), &config));
+    }
+
+    #[tokio::test]
+    async fn test_builder_validate_rejects_merged_config() {
+        let exclusive = |value: &ConfigValue| {
+            let has = |key| value.get(key).is_some();
+            if has("tls_cert") && has("insecure") {
+                return Err(Error::SourceError {
+                    source_name: "validate".to_string(),
+                    source: "tls_cert and insecure are mutually exclusive".into(),
+                });
+            }
+            Ok(())
+        };
+
+        let result = ConfigBuilder::new()
+            .add_defaults(obj(vec![("insecure", ConfigValue::Bool(true))]))
+            .add_source(MemorySource::new(obj(vec![(
+                "tls_cert",
+                ConfigValue::String("cert.pem".into()),
+            )])))
+            .validate(exclusive)
+            .build()
+            .await;
+        let Err(Error::SourceError { source, .. }) = result else {
+            panic!("expected the validator's error");
+        };
+        assert!(source.to_string().contains("mutually exclusive"));
+
+        let config = ConfigBuilder::new()
+            .add_defaults(obj(vec![("insecure", ConfigValue::Bool(true))]))
+            .validate(exclusive)
+            .build()
+            .await
+            .unwrap();
+        assert!(config.get::<bool>("insecure").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_builder_validators_run_in_order() {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let record = |name: &'static str, fail: bool| {
+            let calls = Arc::clone(&calls);
+            move |value: &ConfigValue| {
+                calls.lock().unwrap().push(name);
+                assert_eq!(value.get("port"), Some(&ConfigValue::Integer(9000)));
+                if fail {
+                    Err(Error::KeyNotFound(name.to_string()))
+                } else {
+                    Ok(())
+                }
+            }
+        };
+
+        let result = ConfigBuilder::new()
+            .add_defaults(obj(vec![("port", ConfigValue::Integer(8080))]))
+            .add_cli_args(&[("port".to_string(), "9000".to_string())])
+            .validate(record("first", false))
+            .validate(record("second", true))
+            .validate(record("third", false))
+            .build()
+            .await;
+
+        assert!(matches!(result, Err(Error::KeyNotFound(name)) if name == "second"));
+        assert_eq!(*calls.lock().unwrap(), vec!["first", "second"]);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_builder_add_named_honors_env_var() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let pinned = temp_dir.path().join("pinned.toml");
+        std::fs::write(&pinned, "port = 9000").unwrap();
+
+        std::env::set_var("PREFER_BUILDER_NAMED_CONFIG", &pinned);
+        let config = ConfigBuilder::new()
+            .add_defaults(obj(vec![("port", ConfigValue::Integer(8080))]))
+            .add_named("prefer-builder-named")
+            .build()
+            .await;
+        std::env::remove_var("PREFER_BUILDER_NAMED_CONFIG");
+
+        assert_eq!(config.unwrap().get::<u16>("port").unwrap(), 9000);
+    }
+
     #[tokio::test]
     async fn test_builder_with_defaults() {
         let config = ConfigBuilder::new()
@@ -188,4 +563,260 @@ mod tests {
         let port: u16 = config.get("database.port").unwrap();
         assert_eq!(port, 5432);
     }
+
+    fn profiles() -> ConfigValue {
+        obj(vec![
+            (
+                "default",
+                obj(vec![
+                    ("host", ConfigValue::String("localhost".to_string())),
+                    (
+                        "db",
+                        obj(vec![
+                            ("pool", ConfigValue::Integer(5)),
+                            ("name", ConfigValue::String("app".to_string())),
+                        ]),
+                    ),
+                ]),
+            ),
+            (
+                "production",
+                obj(vec![
+                    ("host", ConfigValue::String("prod.example.com".to_string())),
+                    ("db", obj(vec![("pool", ConfigValue::Integer(50))])),
+                ]),
+            ),
+            (
+                "staging",
+                obj(vec![(
+                    "host",
+                    ConfigValue::String("staging.example.com".to_string()),
+                )]),
+            ),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_builder_profile_merges_over_default() {
+        let config = ConfigBuilder::new()
+            .add_defaults(profiles())
+            .with_profile("production")
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(config.get::<String>("host").unwrap(), "prod.example.com");
+        assert_eq!(config.get::<i64>("db.pool").unwrap(), 50);
+        assert_eq!(config.get::<String>("db.name").unwrap(), "app");
+
+        // Section wrappers and other profiles are removed
+        assert!(!config.has_key("default"));
+        assert!(!config.has_key("production"));
+        assert!(!config.has_key("staging"));
+    }
+
+    #[tokio::test]
+    async fn test_builder_missing_profile() {
+        let result = ConfigBuilder::new()
+            .add_defaults(profiles())
+            .with_profile("qa")
+            .build()
+            .await;
+        assert!(matches!(result, Err(Error::KeyNotFound(name)) if name == "qa"));
+
+        let config = ConfigBuilder::new()
+            .add_defaults(profiles())
+            .with_optional_profile("qa")
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(config.get::<String>("host").unwrap(), "localhost");
+    }
+
+    #[tokio::test]
+    async fn test_builder_cli_args_override_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml");
+        std::fs::write(&path, "[server]\nhost = \"localhost\"\nport = 8080\n").unwrap();
+
+        let args = vec![
+            ("--server.port".to_string(), "9000".to_string()),
+            ("debug".to_string(), "TRUE".to_string()),
+        ];
+        let config = ConfigBuilder::new()
+            .add_cli_args(&args)
+            // Added after the CLI args, but still overridden by them
+            .add_file(&path)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(config.get::<u16>("server.port").unwrap(), 9000);
+        assert_eq!(config.get::<String>("server.host").unwrap(), "localhost");
+        assert!(config.get::<bool>("debug").unwrap());
+    }
+
+    #[cfg(feature = "glob")]
+    #[tokio::test]
+    async fn test_builder_add_glob_sorted_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let conf_d = dir.path().join("conf.d");
+        std::fs::create_dir(&conf_d).unwrap();
+
+        // Written out of order to ensure precedence comes from sorting
+        std::fs::write(conf_d.join("20-db.yaml"), "db:\n  host: db.local\n").unwrap();
+        std::fs::write(conf_d.join("30-override.yaml"), "db:\n  port: 6543\n").unwrap();
+        std::fs::write(
+            conf_d.join("10-base.yaml"),
+            "name: app\ndb:\n  host: localhost\n  port: 5432\n",
+        )
+        .unwrap();
+
+        let pattern = conf_d.join("*.yaml");
+        let config = ConfigBuilder::new()
+            .add_glob(pattern.to_str().unwrap())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(config.get::<String>("name").unwrap(), "app");
+        assert_eq!(config.get::<String>("db.host").unwrap(), "db.local");
+        assert_eq!(config.get::<u16>("db.port").unwrap(), 6543);
+    }
+
+    #[cfg(feature = "glob")]
+    #[tokio::test]
+    async fn test_builder_add_glob_sniffs_unknown_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("10-base.conf"),
+            "[db]\nhost = \"localhost\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("20-port.conf"),
+            "{\"db\": {\"port\": 6543}}",
+        )
+        .unwrap();
+
+        let pattern = dir.path().join("*.conf");
+        let config = ConfigBuilder::new()
+            .add_glob(pattern.to_str().unwrap())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(config.get::<String>("db.host").unwrap(), "localhost");
+        assert_eq!(config.get::<u16>("db.port").unwrap(), 6543);
+    }
+
+    #[cfg(feature = "glob")]
+    #[tokio::test]
+    async fn test_builder_add_glob_no_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let pattern = dir.path().join("*.yaml");
+
+        let config = ConfigBuilder::new()
+            .add_defaults(obj(vec![("name", ConfigValue::String("app".to_string()))]))
+            .add_glob(pattern.to_str().unwrap())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(config.get::<String>("name").unwrap(), "app");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_builder_add_optional_env() {
+        let defaults = obj(vec![("port", ConfigValue::Integer(8080))]);
+
+        std::env::remove_var("OPTENVTEST__PORT");
+        let builder = ConfigBuilder::new()
+            .add_defaults(defaults.clone())
+            .add_optional_env("OPTENVTEST");
+        assert!(matches!(builder.sources[1], Layer::OptionalEnv(ref env) if !env.has_vars()));
+        let config = builder.build().await.unwrap();
+        assert_eq!(config.data(), &defaults);
+        assert_eq!(config.source_of("port"), Some("defaults"));
+
+        std::env::set_var("OPTENVTEST__PORT", "9000");
+        let config = ConfigBuilder::new()
+            .add_defaults(defaults)
+            .add_optional_env("OPTENVTEST")
+            .build()
+            .await;
+        std::env::remove_var("OPTENVTEST__PORT");
+        let config = config.unwrap();
+
+        assert_eq!(config.get::<u16>("port").unwrap(), 9000);
+        assert_eq!(config.source_of("port"), Some("OPTENVTEST"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_builder_source_of_env_override() {
+        std::env::set_var("PROVTEST__SERVER__PORT", "9000");
+        let config = ConfigBuilder::new()
+            .add_defaults(obj(vec![(
+                "server",
+                obj(vec![
+                    ("host", ConfigValue::String("localhost".to_string())),
+                    ("port", ConfigValue::Integer(8080)),
+                ]),
+            )]))
+            .add_env("PROVTEST")
+            .build()
+            .await;
+        std::env::remove_var("PROVTEST__SERVER__PORT");
+        let mut config = config.unwrap();
+
+        assert_eq!(config.get::<u16>("server.port").unwrap(), 9000);
+        assert_eq!(config.source_of("server.port"), Some("PROVTEST"));
+        assert_eq!(config.source_of("server.host"), Some("defaults"));
+        assert_eq!(config.source_of("server"), None);
+        assert_eq!(config.source_of("missing"), None);
+        assert_eq!(config.effective_sources().unwrap().len(), 2);
+
+        config.set("server.port", ConfigValue::Integer(1));
+        assert_eq!(config.source_of("server.port"), None);
+    }
+
+    #[tokio::test]
+    async fn test_builder_source_of_profile_and_cli() {
+        let defaults = obj(vec![
+            (
+                "default",
+                obj(vec![
+                    ("host", ConfigValue::String("localhost".to_string())),
+                    ("ports", ConfigValue::Array(vec![ConfigValue::Integer(1)])),
+                ]),
+            ),
+            (
+                "production",
+                obj(vec![("host", ConfigValue::String("prod".to_string()))]),
+            ),
+        ]);
+        let overrides = obj(vec![(
+            "default",
+            obj(vec![(
+                "ports",
+                ConfigValue::Array(vec![ConfigValue::Integer(2)]),
+            )]),
+        )]);
+        let args = vec![("debug".to_string(), "true".to_string())];
+
+        let config = ConfigBuilder::new()
+            .add_defaults(defaults)
+            .add_source(MemorySource::with_name(overrides, "overrides"))
+            .with_profile("production")
+            .add_cli_args(&args)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(config.source_of("host"), Some("defaults"));
+        assert_eq!(config.source_of("ports[0]"), Some("overrides"));
+        assert_eq!(config.source_of("debug"), Some("cli"));
+    }
 }
@@ -2,12 +2,17 @@
 
 use crate::discovery;
 use crate::error::{Error, Result};
-use crate::events::Emitter;
+use crate::events::{key_under_prefix, Emitter};
 use crate::registry;
-use crate::value::{ConfigValue, FromValue};
+use crate::value::{Change, ConfigValue, FromValue};
 use crate::visitor::{visit, ValueVisitor};
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Converted values memoized by `Config::get_cached()`, keyed by key and type.
+type ConversionCache = RwLock<HashMap<(String, TypeId), Box<dyn Any + Send + Sync>>>;
 
 /// The main configuration struct that holds parsed configuration data.
 ///
@@ -20,7 +25,10 @@ pub struct Config {
     source_path: Option<PathBuf>,
     source: Option<String>,
     loader_name: Option<String>,
+    format: Option<String>,
     emitter: Option<Emitter>,
+    cache: Option<ConversionCache>,
+    provenance: Option<HashMap<String, String>>,
 }
 
 impl std::fmt::Debug for Config {
@@ -30,6 +38,7 @@ impl std::fmt::Debug for Config {
             .field("source_path", &self.source_path)
             .field("source", &self.source)
             .field("loader_name", &self.loader_name)
+            .field("format", &self.format)
             .finish()
     }
 }
@@ -41,11 +50,29 @@ impl Clone for Config {
             source_path: self.source_path.clone(),
             source: self.source.clone(),
             loader_name: self.loader_name.clone(),
+            format: self.format.clone(),
             emitter: None,
+            cache: self.cache.as_ref().map(|_| ConversionCache::default()),
+            provenance: self.provenance.clone(),
         }
     }
 }
 
+/// Configs are equal when their data is equal; where they were loaded
+/// from is ignored. Float values compare as `ConfigValue` does, so a config
+/// holding `NaN` is not equal to itself.
+impl PartialEq for Config {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl AsRef<ConfigValue> for Config {
+    fn as_ref(&self) -> &ConfigValue {
+        &self.data
+    }
+}
+
 impl Config {
     /// Create a new Config from a ConfigValue.
     pub fn new(data: ConfigValue) -> Self {
@@ -54,10 +81,19 @@ impl Config {
             source_path: None,
             source: None,
             loader_name: None,
+            format: None,
             emitter: None,
+            cache: None,
+            provenance: None,
         }
     }
 
+    /// Attach the source name of each leaf key, as recorded by the builder.
+    pub(crate) fn with_provenance(mut self, provenance: HashMap<String, String>) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
     /// Create a new ConfigBuilder for constructing a Config from multiple sources.
     ///
     /// # Examples
@@ -86,25 +122,39 @@ impl Config {
             source_path: Some(path),
             source: None,
             loader_name: None,
+            format: None,
             emitter: None,
+            cache: None,
+            provenance: None,
         }
     }
 
-    /// Create a Config with full metadata from the registry loading path.
-    pub(crate) fn with_metadata(data: ConfigValue, source: String, loader_name: String) -> Self {
-        let source_path = PathBuf::from(&source);
-        let source_path = if source_path.exists() {
-            Some(source_path)
-        } else {
-            None
-        };
+    /// Create a Config with full metadata.
+    ///
+    /// This is what the registry loading path uses, and lets custom
+    /// `Loader` implementations and tests build a fully-populated Config.
+    /// If `source` names an existing file, it also becomes the
+    /// `source_path()`.
+    pub fn with_metadata(
+        data: ConfigValue,
+        source: Option<String>,
+        loader_name: Option<String>,
+        format: Option<String>,
+    ) -> Self {
+        let source_path = source
+            .as_ref()
+            .map(PathBuf::from)
+            .filter(|path| path.exists());
 
         Self {
             data,
             source_path,
-            source: Some(source),
-            loader_name: Some(loader_name),
+            source,
+            loader_name,
+            format,
             emitter: None,
+            cache: None,
+            provenance: None,
         }
     }
 
@@ -118,19 +168,70 @@ impl Config {
     }
 
     /// Load a configuration from a specific file path.
+    ///
+    /// The format is chosen by extension. Files with no extension or an
+    /// unrecognized one are parsed with `formatter::parse_sniff()`.
+    // Takes `&PathBuf` to keep the public signature stable.
+    #[allow(clippy::ptr_arg)]
     pub async fn load_from_path(path: &PathBuf) -> Result<Self> {
-        let contents = tokio::fs::read_to_string(path).await?;
-        let source = path.to_string_lossy().to_string();
-        let formatters = registry::collect_formatters();
-        let fmt = formatters
-            .iter()
-            .find(|f| f.provides(&source))
-            .ok_or_else(|| Error::UnsupportedFormat(path.clone()))?;
-        let data = fmt.deserialize(&contents)?;
-
+        let data = parse_file(path).await?;
         Ok(Self::with_source(data, path.clone()))
     }
 
+    /// Parse a configuration from a string.
+    ///
+    /// The format is chosen by `format_hint`, a file extension such as
+    /// `"yaml"` or `"json"`. The resulting Config has no source path.
+    /// Returns `NoFormatterFound` if no formatter handles the hint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prefer::Config;
+    /// # fn main() -> prefer::Result<()> {
+    /// let config = Config::parse_str("port: 8080", "yaml")?;
+    /// let port: u16 = config.get("port")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_str(content: &str, format_hint: &str) -> Result<Self> {
+        let fmt = registry::find_formatter_by_hint(format_hint)
+            .ok_or_else(|| Error::NoFormatterFound(format_hint.to_string()))?;
+        Ok(Self::new(fmt.deserialize(content)?))
+    }
+
+    /// Read and parse a configuration from an async reader.
+    ///
+    /// Like `parse_str()`, but reads the content from `reader` first.
+    pub async fn from_reader<R>(mut reader: R, format_hint: &str) -> Result<Self>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        use tokio::io::AsyncReadExt;
+
+        if registry::find_formatter_by_hint(format_hint).is_none() {
+            return Err(Error::NoFormatterFound(format_hint.to_string()));
+        }
+
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents).await?;
+        let contents = contents.strip_prefix('\u{FEFF}').unwrap_or(&contents);
+        Self::parse_str(contents, format_hint)
+    }
+
+    /// Turn this configuration into a live-reloading handle.
+    ///
+    /// Watches the file this configuration was loaded from and swaps in
+    /// new snapshots as it changes. Returns `WatchNotSupported` if the
+    /// configuration has no source path.
+    pub async fn watch_self(self) -> Result<crate::watch::WatchedConfig> {
+        let path = self
+            .source_path
+            .clone()
+            .ok_or_else(|| Error::WatchNotSupported(self.source.clone().unwrap_or_default()))?;
+        crate::watch::WatchedConfig::watch_path(self, path).await
+    }
+
     /// Get the source path of this configuration, if available.
     pub fn source_path(&self) -> Option<&PathBuf> {
         self.source_path.as_ref()
@@ -146,6 +247,54 @@ impl Config {
         self.loader_name.as_deref()
     }
 
+    /// Get the name of the format this config was parsed from, if known.
+    pub fn format(&self) -> Option<&str> {
+        self.format.as_deref()
+    }
+
+    /// Set the source identifier reported by `source()`.
+    ///
+    /// Unlike `with_metadata()`, this leaves `source_path()` unchanged.
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        self.source = Some(source.into());
+    }
+
+    /// Set the loader name reported by `loader_name()`.
+    pub fn set_loader_name(&mut self, loader_name: impl Into<String>) {
+        self.loader_name = Some(loader_name.into());
+    }
+
+    /// Set the format name reported by `format()`.
+    pub fn set_format(&mut self, format: impl Into<String>) {
+        self.format = Some(format.into());
+    }
+
+    /// Get the name of the source that provided the value at `key`.
+    ///
+    /// Only configs built with `ConfigBuilder` track this; others return
+    /// `None`. A key inside an array or other leaf value reports the source
+    /// of that leaf. Objects are not leaves, so a key naming a whole
+    /// section returns `None`, as do keys changed with `set()`. Replacing
+    /// the data with `replace_data()` or `data_mut()` clears every entry.
+    pub fn source_of(&self, key: &str) -> Option<&str> {
+        let provenance = self.provenance.as_ref()?;
+        let mut key = key;
+        loop {
+            if let Some(source_name) = provenance.get(key) {
+                return Some(source_name);
+            }
+            key = &key[..key.rfind(['.', '['])?];
+        }
+    }
+
+    /// Get the source name of every leaf key, for configs built with
+    /// `ConfigBuilder`.
+    ///
+    /// See `source_of()`.
+    pub fn effective_sources(&self) -> Option<&HashMap<String, String>> {
+        self.provenance.as_ref()
+    }
+
     /// Get a configuration value by key using dot notation.
     ///
     /// # Examples
@@ -164,6 +313,216 @@ impl Config {
         T::from_value(value).map_err(|e| e.with_key(key))
     }
 
+    /// Get raw bytes using dot notation.
+    ///
+    /// Accepts a `Bytes` value (such as a binary column from a database), an
+    /// array of integers 0–255, or with the `base64` feature, a base64
+    /// string as written by the text formatters.
+    pub fn get_bytes(&self, key: &str) -> Result<Vec<u8>> {
+        self.get(key)
+    }
+
+    /// Get a duration using dot notation.
+    ///
+    /// Accepts a number of seconds or a human-readable string such as
+    /// `"30s"`, `"1500ms"` or `"1h30m"`.
+    pub fn get_duration(&self, key: &str) -> Result<std::time::Duration> {
+        self.get(key)
+    }
+
+    /// Get the length of the array at `key`.
+    ///
+    /// Returns `KeyNotFound` if the key is missing and `ConversionError` if
+    /// the value is not an array.
+    pub fn get_array_len(&self, key: &str) -> Result<usize> {
+        self.get_array(key).map(Vec::len)
+    }
+
+    /// Convert a single element of the array at `key`.
+    ///
+    /// Together with `get_array_len()`, this allows paging through a large
+    /// array without converting all of it into a `Vec`. Returns
+    /// `KeyNotFound` (for `key[index]`) if the index is out of range, and
+    /// `ConversionError` if the value is not an array.
+    pub fn get_array_item<T: FromValue>(&self, key: &str, index: usize) -> Result<T> {
+        let item_key = format!("{key}[{index}]");
+        let item = self
+            .get_array(key)?
+            .get(index)
+            .ok_or_else(|| Error::KeyNotFound(item_key.clone()))?;
+        T::from_value(item).map_err(|e| e.with_key(item_key))
+    }
+
+    fn get_array(&self, key: &str) -> Result<&Vec<ConfigValue>> {
+        let value = self.get_value(key)?;
+        value.as_array().ok_or_else(|| Error::ConversionError {
+            key: key.to_string(),
+            type_name: "array".into(),
+            source: format!("expected array, found {}", value.type_name()).into(),
+        })
+    }
+
+    /// Enable memoization of converted values for `get_cached()`.
+    ///
+    /// Useful for read-heavy code that converts the same key on every
+    /// request. The cache is cleared whenever the data is modified through
+    /// `set()` or `data_mut()`.
+    pub fn with_cache(mut self) -> Self {
+        self.cache = Some(ConversionCache::default());
+        self
+    }
+
+    /// Get a configuration value, reusing a previous conversion if cached.
+    ///
+    /// Behaves like `get()`, but when caching is enabled with `with_cache()`
+    /// the converted value is stored per key and type, and later calls
+    /// return a clone of it. Errors are not cached. Without a cache this is
+    /// equivalent to `get()`.
+    pub fn get_cached<T>(&self, key: &str) -> Result<T>
+    where
+        T: FromValue + Clone + Send + Sync + 'static,
+    {
+        let Some(cache) = &self.cache else {
+            return self.get(key);
+        };
+
+        let cache_key = (key.to_string(), TypeId::of::<T>());
+        if let Some(cached) = cache
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&cache_key)
+            .and_then(|v| v.downcast_ref::<T>())
+        {
+            return Ok(cached.clone());
+        }
+
+        let value: T = self.get(key)?;
+        cache
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(cache_key, Box::new(value.clone()));
+        Ok(value)
+    }
+
+    /// Drop all memoized conversions after the data changes.
+    fn invalidate_cache(&mut self) {
+        if let Some(cache) = &mut self.cache {
+            cache.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+        }
+    }
+
+    /// Get a configuration value, falling back to `default` if the key is missing.
+    ///
+    /// Only a missing key uses the default; a value that is present but
+    /// cannot be converted still returns a `ConversionError`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use prefer::Config;
+    /// # fn example(config: &Config) -> prefer::Result<()> {
+    /// let workers: u32 = config.get_or("server.workers", 4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_or<T: FromValue>(&self, key: &str, default: T) -> Result<T> {
+        self.get_or_else(key, || default)
+    }
+
+    /// Get a configuration value, computing a fallback if the key is missing.
+    ///
+    /// Like `get_or()`, but the default is only constructed when needed.
+    pub fn get_or_else<T: FromValue>(&self, key: &str, default: impl FnOnce() -> T) -> Result<T> {
+        // Only absence of `key` itself falls back; a `KeyNotFound` from
+        // inside the conversion (a missing nested field) still fails.
+        match self.get_value(key) {
+            Ok(value) => T::from_value(value).map_err(|e| e.with_key(key)),
+            Err(Error::KeyNotFound(_)) => Ok(default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get several values of the same type at once.
+    ///
+    /// Values are returned in the order of `keys`. Fails on the first key
+    /// that is missing or does not convert, with that key in the error. For
+    /// keys of different types, use the `get_tuple!` macro.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prefer::Config;
+    /// # fn main() -> prefer::Result<()> {
+    /// let config = Config::parse_str("http: 80\nhttps: 443", "yaml")?;
+    /// let ports: Vec<u16> = config.get_many(&["http", "https"])?;
+    /// assert_eq!(ports, vec![80, 443]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_many<T: FromValue>(&self, keys: &[&str]) -> Result<Vec<T>> {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
+
+    /// Render a text template, filling in `{{ dotted.key }}` placeholders.
+    ///
+    /// Whitespace around the key is optional. Strings are inserted as-is,
+    /// arrays and objects as compact JSON (see
+    /// `ConfigValue::to_json_string()`), and other values as displayed.
+    /// Write `\{{` for a literal `{{`; the backslash is dropped.
+    ///
+    /// Fails with `KeyNotFound` naming the placeholder's key if it is
+    /// missing, and with a `ParseError` for an empty or unterminated
+    /// placeholder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prefer::Config;
+    /// # fn main() -> prefer::Result<()> {
+    /// let config = Config::parse_str("server:\n  host: example.com\n  port: 443", "yaml")?;
+    /// let line = config.render("listen {{ server.host }}:{{server.port}};")?;
+    /// assert_eq!(line, "listen example.com:443;");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn render(&self, template: &str) -> Result<String> {
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(start) = rest.find("{{") {
+            if let Some(literal) = rest[..start].strip_suffix('\\') {
+                out.push_str(literal);
+                out.push_str("{{");
+                rest = &rest[start + 2..];
+                continue;
+            }
+            out.push_str(&rest[..start]);
+
+            let offset = template.len() - rest.len() + start;
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| template_error(offset, "unterminated placeholder"))?;
+            let key = after[..end].trim();
+            if key.is_empty() {
+                return Err(template_error(offset, "empty placeholder"));
+            }
+
+            match self.get_value(key)? {
+                ConfigValue::String(s) => out.push_str(s),
+                value
+                @ (ConfigValue::Array(_) | ConfigValue::Object(_) | ConfigValue::Bytes(_)) => {
+                    out.push_str(&value.to_json_string())
+                }
+                value => out.push_str(&value.to_string()),
+            }
+            rest = &after[end + 2..];
+        }
+
+        out.push_str(rest);
+        Ok(out)
+    }
+
     /// Get a raw configuration value by key using dot notation.
     ///
     /// Returns a reference to the `ConfigValue` at the specified key path.
@@ -185,6 +544,15 @@ impl Config {
         Ok(current)
     }
 
+    /// Get an owned copy of the value at `key`, using dot notation.
+    ///
+    /// Like `get_value()`, but the result doesn't borrow the config, so it
+    /// can outlive it or be moved into a spawned task. Changes to the copy
+    /// don't affect the config.
+    pub fn get_owned(&self, key: &str) -> Result<ConfigValue> {
+        self.get_value(key).cloned()
+    }
+
     /// Set a configuration value by key using dot notation.
     ///
     /// Creates intermediate objects as needed. Emits a "changed" event
@@ -193,12 +561,38 @@ impl Config {
         let previous = self.get_value(key).ok().cloned();
         let parts: Vec<&str> = key.split('.').collect();
         set_nested(&mut self.data, &parts, value.clone());
+        self.invalidate_cache();
+
+        if let Some(provenance) = &mut self.provenance {
+            provenance.retain(|existing, _| {
+                !key_under_prefix(existing, key) && !key_under_prefix(key, existing)
+            });
+        }
 
         if let Some(emitter) = &self.emitter {
             emitter.emit("changed", key, &value, previous.as_ref());
         }
     }
 
+    /// Set a value only if `key` is not already present.
+    ///
+    /// Returns whether the value was inserted. A key holding `Null` counts
+    /// as present. When inserting, this behaves exactly like `set()`,
+    /// including the "changed" event; otherwise nothing happens.
+    pub fn set_if_absent(&mut self, key: &str, value: impl Into<ConfigValue>) -> bool {
+        if self.has_key(key) {
+            return false;
+        }
+        self.set(key, value.into());
+        true
+    }
+
+    /// Alias for `set_if_absent()`, for layering programmatic defaults
+    /// after loading.
+    pub fn set_default(&mut self, key: &str, value: impl Into<ConfigValue>) -> bool {
+        self.set_if_absent(key, value)
+    }
+
     /// Register a handler for configuration change events.
     ///
     /// The handler is called whenever `set()` is used to modify a value.
@@ -207,21 +601,171 @@ impl Config {
         emitter.bind("changed", handler);
     }
 
+    /// Register a handler for changes to one subtree.
+    ///
+    /// Like `on_change()`, but the handler is only called when the changed
+    /// key is `key_prefix` or nested under it, so observing `"server"` sees
+    /// `server.port` but not `database.host`.
+    ///
+    /// Replacing an ancestor also counts: after observing `"server.port"`,
+    /// `set("server", ...)` calls the handler with `server.port` and its new
+    /// and previous values, if they differ. A value that is gone is reported
+    /// as `Null`.
+    pub fn observe(&mut self, key_prefix: &str, handler: crate::events::EventHandler) {
+        let prefix = key_prefix.to_string();
+        self.on_change(Box::new(move |key, value, previous| {
+            if key_under_prefix(key, &prefix) {
+                handler(key, value, previous);
+            } else if key_under_prefix(&prefix, key) {
+                let rest = prefix[key.len()..].trim_start_matches('.');
+                let current = value_at(value, rest);
+                let previous = previous.and_then(|p| value_at(p, rest));
+                if current != previous {
+                    handler(&prefix, current.unwrap_or(&ConfigValue::Null), previous);
+                }
+            }
+        }));
+    }
+
+    /// Replace the configuration data, emitting a "changed" event for every
+    /// leaf that differs.
+    ///
+    /// Used when a watched source is reloaded. Leaf keys are compared with
+    /// `ConfigValue::flatten_keys()` and reported in sorted order; a key
+    /// that was removed is reported with a `Null` value.
+    pub fn replace_data(&mut self, data: ConfigValue) {
+        let previous = std::mem::replace(&mut self.data, data);
+        self.invalidate_cache();
+        self.provenance = None;
+
+        let Some(emitter) = &self.emitter else {
+            return;
+        };
+
+        let old = previous.flatten_keys(".");
+        let new = self.data.flatten_keys(".");
+        let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        for key in keys {
+            let before = old.get(key);
+            let after = new.get(key);
+            if before != after {
+                emitter.emit("changed", key, after.unwrap_or(&ConfigValue::Null), before);
+            }
+        }
+    }
+
+    /// Compare the file at `path` against this configuration.
+    ///
+    /// The file is parsed the same way as `load_from_path()` and treated as
+    /// the older side, so `Change::Added` means a key is set here but not
+    /// on disk and `Change::Removed` means the opposite. An empty result
+    /// means no drift. A missing file is an explicit `FileNotFound` error
+    /// rather than an empty baseline; to report every key as added, diff
+    /// against an empty object with `ConfigValue::diff()` instead.
+    pub async fn diff_with_path(&self, path: &Path) -> Result<Vec<Change>> {
+        let on_disk = parse_file(path).await?;
+        Ok(on_disk.diff(&self.data))
+    }
+
     /// Get the entire configuration data as a reference.
     pub fn data(&self) -> &ConfigValue {
         &self.data
     }
 
     /// Get the entire configuration data as a mutable reference.
+    ///
+    /// Source tracking is cleared, since the changes can't be followed.
     pub fn data_mut(&mut self) -> &mut ConfigValue {
+        self.invalidate_cache();
+        self.provenance = None;
         &mut self.data
     }
 
+    /// Consume the configuration, returning its data without cloning.
+    pub fn into_value(self) -> ConfigValue {
+        self.data
+    }
+
+    /// Consume the configuration, returning the value at `key` without
+    /// cloning it.
+    ///
+    /// The owning counterpart of `get_owned()`.
+    pub fn into_section(mut self, key: &str) -> Result<ConfigValue> {
+        let mut current = &mut self.data;
+        for part in key.split('.') {
+            current = match current {
+                ConfigValue::Object(map) => map.get_mut(part),
+                _ => None,
+            }
+            .ok_or_else(|| Error::KeyNotFound(key.to_string()))?;
+        }
+        Ok(std::mem::take(current))
+    }
+
+    /// Convert the section at `key` into a typed struct.
+    ///
+    /// Like `get()`, but also prefixes `key` onto the `KeyNotFound` errors
+    /// raised inside the section (such as a missing field in a derived
+    /// struct), so every error names the full path.
+    ///
+    /// # Examples
+    ///
+    /// With a `#[derive(FromValue)]` struct, each subsystem can read its own
+    /// section:
+    ///
+    /// ```no_run
+    /// # use prefer::Config;
+    /// # use std::collections::HashMap;
+    /// # type DbConfig = HashMap<String, String>;
+    /// # fn example(config: &Config) -> prefer::Result<()> {
+    /// let db: DbConfig = config.as_typed_section("database")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_typed_section<T: FromValue>(&self, key: &str) -> Result<T> {
+        let section = self.get_value(key)?;
+        T::from_value(section).map_err(|e| match e {
+            Error::KeyNotFound(inner) => Error::KeyNotFound(format!("{}.{}", key, inner)),
+            e => e.with_key(key),
+        })
+    }
+
     /// Check if a key exists in the configuration.
     pub fn has_key(&self, key: &str) -> bool {
         self.get_value(key).is_ok()
     }
 
+    /// Check that every key in `keys` is present.
+    ///
+    /// Unlike `get()`, this does not stop at the first missing key: the
+    /// returned `MissingKeys` error lists all of them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use prefer::Config;
+    /// # fn example(config: &Config) -> prefer::Result<()> {
+    /// config.require(&["database.host", "database.port", "auth.secret"])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn require(&self, keys: &[&str]) -> Result<()> {
+        let missing: Vec<String> = keys
+            .iter()
+            .filter(|key| !self.has_key(key))
+            .map(|key| key.to_string())
+            .collect();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::MissingKeys(missing))
+        }
+    }
+
     /// Extract a value by key using the `FromValue` trait.
     ///
     /// This is an alias for `get()` for backwards compatibility.
@@ -276,6 +820,116 @@ impl Config {
     }
 }
 
+/// An immutable, cheaply-cloneable configuration handle.
+///
+/// Wraps an `Arc<Config>` and exposes only read access, so a configuration
+/// shared across tasks can't be modified by accident. Clones share the
+/// same underlying `Config`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use prefer::{ConfigBuilder, FrozenConfig};
+///
+/// #[tokio::main]
+/// async fn main() -> prefer::Result<()> {
+///     let config = FrozenConfig::from(ConfigBuilder::new().add_file("myapp").freeze().await?);
+///
+///     let shared = config.clone();
+///     tokio::spawn(async move {
+///         let port: u16 = shared.get("server.port").unwrap();
+///     });
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrozenConfig(Arc<Config>);
+
+/// Get several values of different types from a config as a tuple.
+///
+/// Each `key => Type` pair is read with `get()`, and the whole batch
+/// evaluates to a `Result` holding a tuple in the same order. Fails on the
+/// first key that is missing or does not convert. Works with anything that
+/// has a `get()` method, such as `Config` and `FrozenConfig`.
+///
+/// # Examples
+///
+/// ```
+/// # use prefer::{get_tuple, Config};
+/// # fn main() -> prefer::Result<()> {
+/// let config = Config::parse_str("host: localhost\nport: 8080", "yaml")?;
+/// let (host, port) = get_tuple!(config, "host" => String, "port" => u16)?;
+/// assert_eq!((host.as_str(), port), ("localhost", 8080));
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! get_tuple {
+    ($config:expr, $($key:expr => $ty:ty),+ $(,)?) => {{
+        let config = &$config;
+        (|| -> $crate::Result<_> { Ok(($(config.get::<$ty>($key)?,)+)) })()
+    }};
+}
+
+impl FrozenConfig {
+    /// Get a value using dot notation. See `Config::get()`.
+    pub fn get<T: FromValue>(&self, key: &str) -> Result<T> {
+        self.0.get(key)
+    }
+
+    /// Get a raw configuration value using dot notation.
+    pub fn get_value(&self, key: &str) -> Result<&ConfigValue> {
+        self.0.get_value(key)
+    }
+
+    /// Get an owned copy of a value. See `Config::get_owned()`.
+    pub fn get_owned(&self, key: &str) -> Result<ConfigValue> {
+        self.0.get_owned(key)
+    }
+
+    /// Check if a key exists in the configuration.
+    pub fn has_key(&self, key: &str) -> bool {
+        self.0.has_key(key)
+    }
+
+    /// Get the shared `Config`.
+    pub fn as_arc(&self) -> &Arc<Config> {
+        &self.0
+    }
+}
+
+impl From<Arc<Config>> for FrozenConfig {
+    fn from(config: Arc<Config>) -> Self {
+        Self(config)
+    }
+}
+
+impl From<Config> for FrozenConfig {
+    fn from(config: Config) -> Self {
+        Self(Arc::new(config))
+    }
+}
+
+/// Read and parse a file with the registered formatters.
+async fn parse_file(path: &Path) -> Result<ConfigValue> {
+    crate::loader::file::parse_file(path, &registry::collect_formatters()).await
+}
+
+/// An error for a malformed placeholder at byte `offset` of a template.
+fn template_error(offset: usize, message: &str) -> Error {
+    Error::ParseError {
+        format: "template".to_string(),
+        path: PathBuf::from("<template>"),
+        source: format!("{} at byte {}", message, offset).into(),
+    }
+}
+
+/// Look up a dotted key path below `value`.
+fn value_at<'a>(value: &'a ConfigValue, path: &str) -> Option<&'a ConfigValue> {
+    path.split('.')
+        .try_fold(value, |current, part| current.get(part))
+}
+
 /// Set a value at a nested key path, creating intermediate objects as needed.
 fn set_nested(current: &mut ConfigValue, parts: &[&str], value: ConfigValue) {
     debug_assert!(!parts.is_empty(), "key parts should never be empty");
@@ -309,7 +963,186 @@ fn set_nested(current: &mut ConfigValue, parts: &[&str], value: ConfigValue) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::value::test_helpers::obj;
+    use crate::value::test_helpers::{int, obj, string};
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_into_value_and_as_ref() {
+        let data = obj(vec![("port", ConfigValue::Integer(8080))]);
+        let config = Config::new(data.clone());
+
+        let borrowed: &ConfigValue = config.as_ref();
+        assert!(std::ptr::eq(borrowed, config.data()));
+
+        assert_eq!(config.into_value(), data);
+    }
+
+    #[test]
+    fn test_render() {
+        let config = Config::new(obj(vec![
+            (
+                "server",
+                obj(vec![("host", string("example.com")), ("port", int(443))]),
+            ),
+            ("tags", ConfigValue::Array(vec![string("a"), int(1)])),
+        ]));
+
+        let rendered = config
+            .render("server {{ server.host }}:{{server.port}} # {{ tags }}")
+            .unwrap();
+        assert_eq!(rendered, "server example.com:443 # [\"a\",1]");
+
+        assert_eq!(
+            config.render("\\{{ server.host }} {} }}").unwrap(),
+            "{{ server.host }} {} }}"
+        );
+        assert_eq!(config.render("no placeholders").unwrap(), "no placeholders");
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let config = Config::new(obj(vec![("name", string("app"))]));
+
+        match config.render("{{ name }} {{ server.port }}") {
+            Err(Error::KeyNotFound(key)) => assert_eq!(key, "server.port"),
+            other => panic!("expected KeyNotFound, got {:?}", other),
+        }
+
+        for template in ["{{ name", "a {{  }} b"] {
+            match config.render(template) {
+                Err(Error::ParseError { format, .. }) => assert_eq!(format, "template"),
+                other => panic!("expected ParseError, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_many() {
+        let config = Config::new(obj(vec![
+            ("http", ConfigValue::Integer(80)),
+            ("https", ConfigValue::Integer(443)),
+            ("name", ConfigValue::String("app".to_string())),
+        ]));
+
+        let ports: Vec<u16> = config.get_many(&["https", "http"]).unwrap();
+        assert_eq!(ports, vec![443, 80]);
+        assert!(config.get_many::<u16>(&[]).unwrap().is_empty());
+
+        let err = config.get_many::<u16>(&["http", "admin"]).unwrap_err();
+        assert!(matches!(err, Error::KeyNotFound(key) if key == "admin"));
+
+        let err = config.get_many::<u16>(&["http", "name"]).unwrap_err();
+        assert!(matches!(err, Error::ConversionError { key, .. } if key == "name"));
+    }
+
+    #[test]
+    fn test_get_tuple() {
+        let config = Config::new(obj(vec![
+            ("port", ConfigValue::Integer(8080)),
+            ("name", ConfigValue::String("app".to_string())),
+        ]));
+
+        let (port, name) = crate::get_tuple!(config, "port" => u16, "name" => String).unwrap();
+        assert_eq!(port, 8080);
+        assert_eq!(name, "app");
+
+        let frozen = FrozenConfig::from(config);
+        let (name,) = crate::get_tuple!(frozen, "name" => String).unwrap();
+        assert_eq!(name, "app");
+
+        let err = crate::get_tuple!(frozen, "name" => String, "missing" => u16).unwrap_err();
+        assert!(matches!(err, Error::KeyNotFound(key) if key == "missing"));
+    }
+
+    #[test]
+    fn test_get_owned_is_independent() {
+        let config = Config::new(obj(vec![(
+            "database",
+            obj(vec![(
+                "primary",
+                obj(vec![("host", ConfigValue::String("db1".to_string()))]),
+            )]),
+        )]));
+
+        let mut primary = config.get_owned("database.primary").unwrap();
+        primary
+            .as_object_mut()
+            .unwrap()
+            .insert("host".into(), ConfigValue::String("db2".to_string()));
+
+        assert_eq!(primary.get("host").unwrap().as_str(), Some("db2"));
+        assert_eq!(
+            config.get::<String>("database.primary.host").unwrap(),
+            "db1"
+        );
+        assert!(matches!(
+            config.get_owned("database.replica"),
+            Err(Error::KeyNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_into_section() {
+        let primary = obj(vec![("host", ConfigValue::String("db1".to_string()))]);
+        let config = Config::new(obj(vec![(
+            "database",
+            obj(vec![("primary", primary.clone())]),
+        )]));
+
+        assert_eq!(
+            config.clone().into_section("database.primary").unwrap(),
+            primary
+        );
+        assert!(matches!(
+            config.clone().into_section("database.primary.host.x"),
+            Err(Error::KeyNotFound(key)) if key == "database.primary.host.x"
+        ));
+        assert!(config.into_section("missing").is_err());
+    }
+
+    #[test]
+    fn test_config_eq_ignores_source_metadata() {
+        let data = obj(vec![("port", ConfigValue::Integer(8080))]);
+        let a = Config::with_source(data.clone(), PathBuf::from("a.toml"));
+        let b = Config::with_source(data.clone(), PathBuf::from("b.json"));
+
+        assert_eq!(a, b);
+        assert_eq!(a, Config::new(data));
+        assert_ne!(
+            a,
+            Config::new(obj(vec![("port", ConfigValue::Integer(80))]))
+        );
+
+        let nan = Config::new(obj(vec![("x", ConfigValue::Float(f64::NAN))]));
+        assert_ne!(nan, nan.clone());
+    }
+
+    #[test]
+    fn test_config_is_send_sync() {
+        assert_send_sync::<Config>();
+        assert_send_sync::<FrozenConfig>();
+    }
+
+    #[tokio::test]
+    async fn test_frozen_config_shared_across_tasks() {
+        let frozen =
+            FrozenConfig::from(Config::new(obj(vec![("port", ConfigValue::Integer(8080))])));
+
+        let shared = frozen.clone();
+        let port = tokio::spawn(async move { shared.get::<u16>("port").unwrap() })
+            .await
+            .unwrap();
+
+        assert_eq!(port, 8080);
+        assert_eq!(frozen.get::<u16>("port").unwrap(), 8080);
+        assert!(frozen.has_key("port"));
+        assert!(!frozen.has_key("host"));
+        assert_eq!(
+            frozen.get_value("port").unwrap(),
+            &ConfigValue::Integer(8080)
+        );
+    }
 
     #[test]
     fn test_get_simple_value() {
@@ -365,6 +1198,213 @@ mod tests {
         assert!(matches!(result.unwrap_err(), Error::KeyNotFound(_)));
     }
 
+    #[test]
+    fn test_get_or_missing_uses_default() {
+        let config = Config::new(obj(vec![]));
+        assert_eq!(config.get_or("server.port", 8080u16).unwrap(), 8080);
+        assert_eq!(
+            config
+                .get_or_else("server.host", || "localhost".to_string())
+                .unwrap(),
+            "localhost"
+        );
+    }
+
+    #[test]
+    fn test_get_or_present_valid() {
+        let config = Config::new(obj(vec![("port", ConfigValue::Integer(9090))]));
+        assert_eq!(config.get_or("port", 8080u16).unwrap(), 9090);
+        assert_eq!(
+            config
+                .get_or_else("port", || -> u16 { panic!("default should not be built") })
+                .unwrap(),
+            9090
+        );
+    }
+
+    #[test]
+    fn test_get_or_present_invalid_errors() {
+        let config = Config::new(obj(vec![(
+            "port",
+            ConfigValue::String("not a port".to_string()),
+        )]));
+
+        let err = config.get_or("port", 8080u16).unwrap_err();
+        assert!(matches!(err, Error::ConversionError { ref key, .. } if key == "port"));
+        assert!(config.get_or_else("port", || 8080u16).is_err());
+    }
+
+    #[test]
+    fn test_get_or_present_missing_field_errors() {
+        #[derive(Debug)]
+        struct Server {
+            host: String,
+        }
+
+        // Fails on a missing field the way a derived impl does
+        impl FromValue for Server {
+            fn from_value(value: &ConfigValue) -> Result<Self> {
+                Ok(Server {
+                    host: String::from_value(value.try_get("host")?)?,
+                })
+            }
+        }
+
+        let config = Config::new(obj(vec![(
+            "server",
+            obj(vec![("port", ConfigValue::Integer(80))]),
+        )]));
+        let default = || Server {
+            host: "localhost".to_string(),
+        };
+
+        let err = config.get_or("server", default()).unwrap_err();
+        assert!(matches!(err, Error::KeyNotFound(ref key) if key == "host"));
+        assert!(config.get_or_else("server", default).is_err());
+        assert_eq!(
+            config.get_or("missing", default()).unwrap().host,
+            "localhost"
+        );
+    }
+
+    fn servers() -> Config {
+        Config::new(obj(vec![
+            (
+                "servers",
+                ConfigValue::Array(vec![
+                    obj(vec![("port", ConfigValue::Integer(8080))]),
+                    obj(vec![("port", ConfigValue::Integer(8081))]),
+                    obj(vec![("port", ConfigValue::String("bad".to_string()))]),
+                ]),
+            ),
+            ("name", ConfigValue::String("app".to_string())),
+        ]))
+    }
+
+    #[test]
+    fn test_get_array_len() {
+        let config = servers();
+        assert_eq!(config.get_array_len("servers").unwrap(), 3);
+        assert!(matches!(
+            config.get_array_len("name"),
+            Err(Error::ConversionError { .. })
+        ));
+        assert!(matches!(
+            config.get_array_len("missing"),
+            Err(Error::KeyNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_duration() {
+        use std::time::Duration;
+
+        let config = Config::new(obj(vec![(
+            "http",
+            obj(vec![
+                ("timeout", ConfigValue::String("1500ms".to_string())),
+                ("idle", ConfigValue::String("2m".to_string())),
+                ("retry", ConfigValue::Integer(90)),
+                ("backoff", ConfigValue::String("soon".to_string())),
+            ]),
+        )]));
+
+        assert_eq!(
+            config.get_duration("http.timeout").unwrap(),
+            Duration::from_millis(1500)
+        );
+        assert_eq!(
+            config.get_duration("http.idle").unwrap(),
+            Duration::from_secs(120)
+        );
+        assert_eq!(
+            config.get_duration("http.retry").unwrap(),
+            Duration::from_secs(90)
+        );
+        match config.get_duration("http.backoff") {
+            Err(Error::ConversionError { key, .. }) => assert_eq!(key, "http.backoff"),
+            other => panic!("expected ConversionError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_get_bytes() {
+        let config = Config::new(obj(vec![
+            ("blob", ConfigValue::Bytes(vec![1, 2, 3])),
+            ("name", ConfigValue::String("x".to_string())),
+        ]));
+
+        assert_eq!(config.get_bytes("blob").unwrap(), vec![1, 2, 3]);
+        assert!(matches!(
+            config.get_bytes("missing"),
+            Err(Error::KeyNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_array_item() {
+        let config = servers();
+        let second: HashMap<String, u16> = config.get_array_item("servers", 1).unwrap();
+        assert_eq!(second["port"], 8081);
+
+        match config.get_array_item::<HashMap<String, u16>>("servers", 2) {
+            Err(Error::ConversionError { key, .. }) => assert_eq!(key, "servers[2].port"),
+            other => panic!("expected ConversionError, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_get_array_item_out_of_range() {
+        let config = servers();
+        let result = config.get_array_item::<ConfigValue>("servers", 3);
+        assert!(matches!(result, Err(Error::KeyNotFound(key)) if key == "servers[3]"));
+
+        let result = config.get_array_item::<ConfigValue>("name", 0);
+        assert!(matches!(result, Err(Error::ConversionError { .. })));
+    }
+
+    fn cache_len(config: &Config) -> usize {
+        config.cache.as_ref().unwrap().read().unwrap().len()
+    }
+
+    #[test]
+    fn test_get_cached_hit() {
+        let config = Config::new(obj(vec![("port", ConfigValue::Integer(8080))])).with_cache();
+
+        assert_eq!(config.get_cached::<u16>("port").unwrap(), 8080);
+        assert_eq!(cache_len(&config), 1);
+        assert_eq!(config.get_cached::<u16>("port").unwrap(), 8080);
+        assert_eq!(cache_len(&config), 1);
+
+        // Different types are cached separately
+        assert_eq!(config.get_cached::<i64>("port").unwrap(), 8080);
+        assert_eq!(cache_len(&config), 2);
+
+        // Errors are not cached
+        assert!(config.get_cached::<String>("port").is_err());
+        assert_eq!(cache_len(&config), 2);
+    }
+
+    #[test]
+    fn test_get_cached_invalidated_by_set() {
+        let mut config = Config::new(obj(vec![("port", ConfigValue::Integer(8080))])).with_cache();
+
+        assert_eq!(config.get_cached::<u16>("port").unwrap(), 8080);
+        config.set("port", ConfigValue::Integer(9090));
+        assert_eq!(cache_len(&config), 0);
+        assert_eq!(config.get_cached::<u16>("port").unwrap(), 9090);
+
+        config.data_mut();
+        assert_eq!(cache_len(&config), 0);
+    }
+
+    #[test]
+    fn test_get_cached_without_cache() {
+        let config = Config::new(obj(vec![("port", ConfigValue::Integer(8080))]));
+        assert_eq!(config.get_cached::<u16>("port").unwrap(), 8080);
+        assert!(config.cache.is_none());
+    }
+
     #[test]
     fn test_has_key() {
         let config = Config::new(obj(vec![(
@@ -377,6 +1417,31 @@ mod tests {
         assert!(!config.has_key("nonexistent"));
     }
 
+    #[test]
+    fn test_require_all_present() {
+        let config = Config::new(obj(vec![
+            ("name", ConfigValue::String("test".to_string())),
+            ("db", obj(vec![("host", ConfigValue::Null)])),
+        ]));
+        assert!(config.require(&["name", "db.host"]).is_ok());
+    }
+
+    #[test]
+    fn test_require_reports_all_missing() {
+        let config = Config::new(obj(vec![("name", ConfigValue::String("test".to_string()))]));
+
+        let err = config
+            .require(&["name", "db.host", "db.port", "auth.secret"])
+            .unwrap_err();
+        assert!(matches!(&err, Error::MissingKeys(keys) if keys.len() == 3));
+
+        let msg = err.to_string();
+        assert!(msg.contains("db.host"));
+        assert!(msg.contains("db.port"));
+        assert!(msg.contains("auth.secret"));
+        assert!(!msg.contains("name"));
+    }
+
     #[test]
     fn test_set_simple() {
         let mut config = Config::new(obj(vec![("port", ConfigValue::Integer(8080))]));
@@ -419,13 +1484,77 @@ mod tests {
         assert_eq!(entries[0].2, Some(ConfigValue::Integer(8080)));
     }
 
+    #[test]
+    fn test_set_if_absent() {
+        let mut config = Config::new(obj(vec![
+            ("port", ConfigValue::Integer(8080)),
+            ("proxy", ConfigValue::Null),
+        ]));
+
+        let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log_clone = log.clone();
+        config.on_change(Box::new(move |key, value, prev| {
+            log_clone
+                .lock()
+                .unwrap()
+                .push((key.to_string(), value.clone(), prev.cloned()));
+        }));
+
+        assert!(config.set_if_absent("server.host", "localhost"));
+        assert_eq!(config.get::<String>("server.host").unwrap(), "localhost");
+
+        assert!(!config.set_if_absent("port", 9090));
+        assert!(!config.set_default("proxy", "http://proxy"));
+        assert!(!config.set_default("server.host", "example.com"));
+        assert_eq!(config.get::<i64>("port").unwrap(), 8080);
+        assert_eq!(config.get::<String>("server.host").unwrap(), "localhost");
+        assert!(config.get_value("proxy").unwrap().is_null());
+
+        assert!(config.set_default("server.port", 443));
+        assert_eq!(config.get::<u16>("server.port").unwrap(), 443);
+
+        let entries = log.lock().unwrap();
+        assert_eq!(
+            *entries,
+            vec![
+                (
+                    "server.host".to_string(),
+                    ConfigValue::String("localhost".into()),
+                    None
+                ),
+                ("server.port".to_string(), ConfigValue::Integer(443), None),
+            ]
+        );
+    }
+
     #[test]
     fn test_metadata_accessors() {
-        let config =
-            Config::with_metadata(ConfigValue::Null, "/etc/myapp.toml".into(), "file".into());
+        let config = Config::with_metadata(
+            ConfigValue::Null,
+            Some("/etc/myapp.toml".into()),
+            Some("file".into()),
+            Some("toml".into()),
+        );
 
         assert_eq!(config.source(), Some("/etc/myapp.toml"));
         assert_eq!(config.loader_name(), Some("file"));
+        assert_eq!(config.format(), Some("toml"));
+    }
+
+    #[test]
+    fn test_metadata_setters() {
+        let mut config = Config::new(ConfigValue::Null);
+        config.set_source("memory://app");
+        config.set_loader_name("custom");
+        config.set_format("json");
+
+        assert_eq!(config.source(), Some("memory://app"));
+        assert_eq!(config.loader_name(), Some("custom"));
+        assert_eq!(config.format(), Some("json"));
+        assert!(config.source_path().is_none());
+
+        let cloned = config.clone();
+        assert_eq!(cloned.format(), Some("json"));
     }
 
     #[test]
@@ -442,8 +1571,9 @@ mod tests {
     fn test_clone_preserves_metadata() {
         let config = Config::with_metadata(
             ConfigValue::Null,
-            "/nonexistent/path.toml".into(),
-            "file".into(),
+            Some("/nonexistent/path.toml".into()),
+            Some("file".into()),
+            None,
         );
         let cloned = config.clone();
         assert_eq!(cloned.source(), Some("/nonexistent/path.toml"));
@@ -452,8 +1582,12 @@ mod tests {
 
     #[test]
     fn test_debug_output() {
-        let config =
-            Config::with_metadata(ConfigValue::Integer(42), "test.json".into(), "file".into());
+        let config = Config::with_metadata(
+            ConfigValue::Integer(42),
+            Some("test.json".into()),
+            Some("file".into()),
+            Some("json".into()),
+        );
         let debug = format!("{:?}", config);
         assert!(debug.contains("Config"));
         assert!(debug.contains("loader_name"));
@@ -465,8 +1599,9 @@ mod tests {
     fn test_with_metadata_nonexistent_path() {
         let config = Config::with_metadata(
             ConfigValue::Null,
-            "/this/path/does/not/exist.toml".into(),
-            "file".into(),
+            Some("/this/path/does/not/exist.toml".into()),
+            Some("file".into()),
+            None,
         );
         // source_path should be None for non-existent paths
         assert!(config.source_path().is_none());
@@ -480,6 +1615,7 @@ mod tests {
         assert!(config.source_path().is_none());
         assert!(config.source().is_none());
         assert!(config.loader_name().is_none());
+        assert!(config.format().is_none());
     }
 
     #[test]
@@ -508,6 +1644,157 @@ mod tests {
         assert_eq!(val, "deep");
     }
 
+    #[test]
+    fn test_observe_filters_by_prefix() {
+        let mut config = Config::new(obj(vec![
+            ("server", obj(vec![("port", ConfigValue::Integer(8080))])),
+            ("database", obj(vec![("host", ConfigValue::from("db"))])),
+        ]));
+
+        let server_log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let database_log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let log = server_log.clone();
+        config.observe(
+            "server",
+            Box::new(move |key, _, _| log.lock().unwrap().push(key.to_string())),
+        );
+        let log = database_log.clone();
+        config.observe(
+            "database",
+            Box::new(move |key, _, _| log.lock().unwrap().push(key.to_string())),
+        );
+
+        config.set("server.port", ConfigValue::Integer(9090));
+        config.set("server.host", ConfigValue::from("0.0.0.0"));
+        config.set("database.host", ConfigValue::from("replica"));
+        config.set("servers", ConfigValue::Integer(3));
+
+        assert_eq!(
+            *server_log.lock().unwrap(),
+            vec!["server.port", "server.host"]
+        );
+        assert_eq!(*database_log.lock().unwrap(), vec!["database.host"]);
+    }
+
+    #[test]
+    fn test_replacing_data_clears_provenance() {
+        let provenance = || HashMap::from([("port".to_string(), "defaults".to_string())]);
+        let data = || obj(vec![("port", ConfigValue::Integer(8080))]);
+
+        let mut config = Config::new(data()).with_provenance(provenance());
+        assert_eq!(config.source_of("port"), Some("defaults"));
+        config.replace_data(obj(vec![("port", ConfigValue::Integer(9090))]));
+        assert_eq!(config.source_of("port"), None);
+
+        let mut config = Config::new(data()).with_provenance(provenance());
+        *config.data_mut() = obj(vec![("port", ConfigValue::Integer(9090))]);
+        assert_eq!(config.source_of("port"), None);
+        assert!(config.effective_sources().is_none());
+    }
+
+    #[test]
+    fn test_observe_sees_ancestor_replacement() {
+        let mut config = Config::new(obj(vec![(
+            "server",
+            obj(vec![("port", ConfigValue::Integer(8080))]),
+        )]));
+
+        let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log_clone = log.clone();
+        config.observe(
+            "server.port",
+            Box::new(move |key, value, prev| {
+                log_clone
+                    .lock()
+                    .unwrap()
+                    .push((key.to_string(), value.clone(), prev.cloned()))
+            }),
+        );
+
+        config.set("server", obj(vec![("port", ConfigValue::Integer(9090))]));
+        // The observed key is unchanged, so this is not reported
+        config.set(
+            "server",
+            obj(vec![
+                ("port", ConfigValue::Integer(9090)),
+                ("host", ConfigValue::from("0.0.0.0")),
+            ]),
+        );
+        config.set("server", ConfigValue::from("disabled"));
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                (
+                    "server.port".to_string(),
+                    ConfigValue::Integer(9090),
+                    Some(ConfigValue::Integer(8080))
+                ),
+                (
+                    "server.port".to_string(),
+                    ConfigValue::Null,
+                    Some(ConfigValue::Integer(9090))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_data_emits_changed_leaves() {
+        let mut config = Config::new(obj(vec![
+            (
+                "server",
+                obj(vec![
+                    ("port", ConfigValue::Integer(8080)),
+                    ("host", ConfigValue::from("localhost")),
+                ]),
+            ),
+            ("database", obj(vec![("host", ConfigValue::from("db"))])),
+        ]));
+
+        let server_log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let database_log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let log = server_log.clone();
+        config.observe(
+            "server",
+            Box::new(move |key, value, prev| {
+                log.lock()
+                    .unwrap()
+                    .push((key.to_string(), value.clone(), prev.cloned()))
+            }),
+        );
+        let log = database_log.clone();
+        config.observe(
+            "database",
+            Box::new(move |key, _, _| log.lock().unwrap().push(key.to_string())),
+        );
+
+        config.replace_data(obj(vec![
+            ("server", obj(vec![("port", ConfigValue::Integer(9090))])),
+            ("database", obj(vec![("host", ConfigValue::from("db"))])),
+        ]));
+
+        assert_eq!(config.get::<u16>("server.port").unwrap(), 9090);
+        assert_eq!(
+            *server_log.lock().unwrap(),
+            vec![
+                (
+                    "server.host".to_string(),
+                    ConfigValue::Null,
+                    Some(ConfigValue::from("localhost"))
+                ),
+                (
+                    "server.port".to_string(),
+                    ConfigValue::Integer(9090),
+                    Some(ConfigValue::Integer(8080))
+                ),
+            ]
+        );
+        assert!(database_log.lock().unwrap().is_empty());
+    }
+
     #[test]
     fn test_set_new_key_fires_with_none_previous() {
         let mut config = Config::new(ConfigValue::Object(HashMap::new()));
@@ -548,6 +1835,41 @@ mod tests {
         assert!(matches!(result, Err(Error::KeyNotFound(_))));
     }
 
+    #[tokio::test]
+    async fn test_watch_self_without_source_path() {
+        let config = Config::new(ConfigValue::Null);
+        let result = config.watch_self().await;
+        assert!(matches!(result, Err(Error::WatchNotSupported(_))));
+    }
+
+    #[test]
+    fn test_parse_str_yaml() {
+        let config =
+            Config::parse_str("server:\n  host: localhost\n  port: 8080\n", "yaml").unwrap();
+
+        assert_eq!(config.get::<String>("server.host").unwrap(), "localhost");
+        assert_eq!(config.get::<u16>("server.port").unwrap(), 8080);
+        assert!(config.source_path().is_none());
+    }
+
+    #[test]
+    fn test_parse_str_unknown_hint() {
+        let result = Config::parse_str("key = value", "bson");
+        assert!(matches!(result, Err(Error::NoFormatterFound(hint)) if hint == "bson"));
+    }
+
+    #[tokio::test]
+    async fn test_from_reader() {
+        let content: &[u8] = br#"{"name": "piped"}"#;
+        let config = Config::from_reader(content, "json").await.unwrap();
+
+        assert_eq!(config.get::<String>("name").unwrap(), "piped");
+        assert!(config.source_path().is_none());
+
+        let result = Config::from_reader(content, "bson").await;
+        assert!(matches!(result, Err(Error::NoFormatterFound(_))));
+    }
+
     #[test]
     fn test_with_source() {
         let config = Config::with_source(ConfigValue::Integer(42), PathBuf::from("/tmp/test.json"));
@@ -1,12 +1,21 @@
 //! Configuration file discovery across standard system paths.
 
 use crate::error::{Error, Result};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tokio::fs;
 
+/// How long `find_config_file_cached()` trusts a resolved path.
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Paths resolved by `find_config_file_cached()`, keyed by name.
+static CACHE: Mutex<BTreeMap<String, (PathBuf, Instant)>> = Mutex::new(BTreeMap::new());
+
 /// Supported configuration file extensions.
 const EXTENSIONS: &[&str] = &[
-    "json", "json5", "jsonc", "yaml", "yml", "toml", "ini", "xml",
+    "json", "json5", "jsonc", "hjson", "yaml", "yml", "toml", "ini", "xml",
 ];
 
 /// Check if a path has a supported configuration file extension.
@@ -34,6 +43,12 @@ pub fn get_search_paths() -> Vec<PathBuf> {
             paths.push(home.join(".config"));
         }
 
+        // ~/Library/Application Support
+        #[cfg(target_os = "macos")]
+        if let Some(support) = dirs::config_dir() {
+            paths.push(support);
+        }
+
         // XDG_CONFIG_DIRS
         if let Some(config_dirs) = std::env::var_os("XDG_CONFIG_DIRS") {
             for dir in std::env::split_paths(&config_dirs) {
@@ -59,8 +74,10 @@ pub fn get_search_paths() -> Vec<PathBuf> {
             paths.push(profile);
         }
 
-        // APPDATA
-        if let Some(appdata) = dirs::config_dir() {
+        // %APPDATA%, falling back to the known folder if it's unset
+        if let Some(appdata) = std::env::var_os("APPDATA") {
+            paths.push(PathBuf::from(appdata));
+        } else if let Some(appdata) = dirs::config_dir() {
             paths.push(appdata);
         }
 
@@ -81,7 +98,9 @@ pub fn get_search_paths() -> Vec<PathBuf> {
 /// Find a configuration file by name in standard search paths.
 ///
 /// If the name already has a supported extension and exists, it will be used directly.
-/// Otherwise, searches for files with supported extensions appended.
+/// Otherwise, searches for files with supported extensions appended. Explicit
+/// paths (absolute, or starting with `./` or `../`) are accepted with any
+/// extension, so their format can be detected from content.
 ///
 /// Returns the path to the first matching configuration file found.
 pub async fn find_config_file(name: &str) -> Result<PathBuf> {
@@ -106,13 +125,85 @@ pub async fn find_config_file(name: &str) -> Result<PathBuf> {
     // Also check if it's an absolute or explicitly relative path
     let path = Path::new(name);
     let is_explicit_path = path.is_absolute() || name.starts_with("./") || name.starts_with("../");
-    if is_explicit_path && has_supported_extension(path) && fs::metadata(path).await.is_ok() {
-        return Ok(path.to_path_buf());
+    if is_explicit_path {
+        match fs::metadata(path).await {
+            Ok(meta) if meta.is_file() => return Ok(path.to_path_buf()),
+            Ok(meta) if meta.is_dir() => return Err(Error::IsADirectory(path.to_path_buf())),
+            _ => {}
+        }
     }
 
     Err(Error::FileNotFound(name.to_string()))
 }
 
+/// Find a configuration file by name, reusing a recent result.
+///
+/// Behaves like `find_config_file(name)`, but remembers each resolved path
+/// for a short time (30 seconds) so repeated lookups skip the filesystem.
+/// Entries are keyed by `name` alone, so a cached path may be stale if the
+/// working directory or the files change; call `clear_cache()` to force a
+/// fresh search. Failed lookups are not cached.
+pub async fn find_config_file_cached(name: &str) -> Result<PathBuf> {
+    if let Some((path, resolved_at)) = lock_cache().get(name) {
+        if resolved_at.elapsed() < CACHE_TTL {
+            return Ok(path.clone());
+        }
+    }
+
+    let path = find_config_file(name).await?;
+    lock_cache().insert(name.to_string(), (path.clone(), Instant::now()));
+    Ok(path)
+}
+
+/// Forget every path remembered by `find_config_file_cached()`.
+pub fn clear_cache() {
+    lock_cache().clear();
+}
+
+fn lock_cache() -> std::sync::MutexGuard<'static, BTreeMap<String, (PathBuf, Instant)>> {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Find a configuration file, letting an environment variable pin its path.
+///
+/// If `env_var` is set to a non-empty value, that path is used directly and
+/// must be an existing file; it is not searched for, and a missing file is
+/// an error rather than a fallback. Otherwise, behaves like
+/// `find_config_file(name)`.
+pub async fn find_config_file_env(name: &str, env_var: &str) -> Result<PathBuf> {
+    match std::env::var_os(env_var).filter(|v| !v.is_empty()) {
+        Some(value) => {
+            let path = PathBuf::from(value);
+            if fs::metadata(&path).await.is_ok_and(|m| m.is_file()) {
+                Ok(path)
+            } else {
+                Err(Error::FileNotFound(path.display().to_string()))
+            }
+        }
+        None => find_config_file(name).await,
+    }
+}
+
+/// The conventional environment variable that pins a config file's path.
+///
+/// The name is upper-cased, other characters than ASCII letters and digits
+/// become underscores, and `_CONFIG` is appended, so `"my-app"` maps to
+/// `MY_APP_CONFIG`.
+pub fn config_env_var(name: &str) -> String {
+    let mut var: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    var.push_str("_CONFIG");
+    var
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +241,72 @@ mod tests {
         }));
     }
 
+    #[cfg(target_family = "unix")]
+    #[tokio::test]
+    #[serial]
+    async fn test_find_in_xdg_config_home() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app_dir = temp_dir.path().join("prefer-xdg-test");
+        std::fs::create_dir(&app_dir).unwrap();
+        std::fs::write(app_dir.join("config.toml"), "key = 1").unwrap();
+
+        let original = std::env::var_os("XDG_CONFIG_HOME");
+        std::env::set_var("XDG_CONFIG_HOME", temp_dir.path());
+        let result = find_config_file("prefer-xdg-test/config").await;
+        match original {
+            Some(value) => std::env::set_var("XDG_CONFIG_HOME", value),
+            None => std::env::remove_var("XDG_CONFIG_HOME"),
+        }
+
+        assert_eq!(result.unwrap(), app_dir.join("config.toml"));
+    }
+
+    #[cfg(target_os = "macos")]
+    #[tokio::test]
+    #[serial]
+    async fn test_find_in_application_support() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app_dir = temp_dir
+            .path()
+            .join("Library/Application Support/prefer-macos-test");
+        std::fs::create_dir_all(&app_dir).unwrap();
+        std::fs::write(app_dir.join("config.toml"), "key = 1").unwrap();
+
+        let original = std::env::var_os("HOME");
+        std::env::set_var("HOME", temp_dir.path());
+        let paths = get_search_paths();
+        let result = find_config_file("prefer-macos-test/config").await;
+        match original {
+            Some(value) => std::env::set_var("HOME", value),
+            None => std::env::remove_var("HOME"),
+        }
+
+        assert!(paths.contains(&temp_dir.path().join("Library/Application Support")));
+        assert_eq!(result.unwrap(), app_dir.join("config.toml"));
+    }
+
+    #[cfg(target_family = "windows")]
+    #[tokio::test]
+    #[serial]
+    async fn test_find_in_appdata() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app_dir = temp_dir.path().join("prefer-windows-test");
+        std::fs::create_dir(&app_dir).unwrap();
+        std::fs::write(app_dir.join("config.toml"), "key = 1").unwrap();
+
+        let original = std::env::var_os("APPDATA");
+        std::env::set_var("APPDATA", temp_dir.path());
+        let paths = get_search_paths();
+        let result = find_config_file("prefer-windows-test/config").await;
+        match original {
+            Some(value) => std::env::set_var("APPDATA", value),
+            None => std::env::remove_var("APPDATA"),
+        }
+
+        assert!(paths.contains(&temp_dir.path().to_path_buf()));
+        assert_eq!(result.unwrap(), app_dir.join("config.toml"));
+    }
+
     #[test]
     fn test_has_supported_extension() {
         assert!(has_supported_extension(Path::new("config.toml")));
@@ -186,6 +343,60 @@ mod tests {
         std::env::set_current_dir(original_dir).unwrap();
     }
 
+    #[test]
+    fn test_config_env_var() {
+        assert_eq!(config_env_var("myapp"), "MYAPP_CONFIG");
+        assert_eq!(config_env_var("my-app.v2"), "MY_APP_V2_CONFIG");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_find_config_file_env_uses_variable() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let pinned = temp_dir.path().join("prod.conf");
+        std::fs::write(&pinned, "key = 1").unwrap();
+
+        std::env::set_var("PREFER_DISCOVERY_TEST_CONFIG", &pinned);
+        let result = find_config_file_env("missing-app", "PREFER_DISCOVERY_TEST_CONFIG").await;
+        std::env::remove_var("PREFER_DISCOVERY_TEST_CONFIG");
+
+        assert_eq!(result.unwrap(), pinned);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_find_config_file_env_missing_file_errors() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let fallback = temp_dir.path().join("app.toml");
+        std::fs::write(&fallback, "key = 1").unwrap();
+        let pinned = temp_dir.path().join("missing.toml");
+
+        // A pinned path that doesn't exist is an error, not a fallback
+        std::env::set_var("PREFER_DISCOVERY_TEST_CONFIG", &pinned);
+        let result =
+            find_config_file_env(fallback.to_str().unwrap(), "PREFER_DISCOVERY_TEST_CONFIG").await;
+        std::env::remove_var("PREFER_DISCOVERY_TEST_CONFIG");
+
+        match result {
+            Err(Error::FileNotFound(path)) => assert!(path.contains("missing.toml")),
+            other => panic!("expected FileNotFound, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_find_config_file_env_unset_falls_back() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let fallback = temp_dir.path().join("app.toml");
+        std::fs::write(&fallback, "key = 1").unwrap();
+
+        std::env::remove_var("PREFER_DISCOVERY_TEST_CONFIG");
+        let result =
+            find_config_file_env(fallback.to_str().unwrap(), "PREFER_DISCOVERY_TEST_CONFIG").await;
+
+        assert_eq!(result.unwrap(), fallback);
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_find_file_appends_extension() {
@@ -208,4 +419,30 @@ mod tests {
 
         std::env::set_current_dir(original_dir).unwrap();
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_find_config_file_cached_until_cleared() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("cachedconfig.toml");
+        std::fs::write(&file_path, "key = 1").unwrap();
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(temp_dir.path()).unwrap();
+
+        clear_cache();
+        let first = find_config_file_cached("cachedconfig").await.unwrap();
+        assert!(first.ends_with("cachedconfig.toml"));
+
+        // The cached path is returned even though the file is gone
+        std::fs::remove_file(&file_path).unwrap();
+        let second = find_config_file_cached("cachedconfig").await.unwrap();
+        assert_eq!(first, second);
+
+        clear_cache();
+        let result = find_config_file_cached("cachedconfig").await;
+
+        std::env::set_current_dir(original_dir).unwrap();
+        assert!(matches!(result, Err(Error::FileNotFound(_))));
+    }
 }
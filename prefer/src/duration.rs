@@ -0,0 +1,100 @@
+//! Parsing of human-readable durations such as `"30s"` or `"1h30m"`.
+
+use core::time::Duration;
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Parse a duration written as one or more `<integer><unit>` pairs.
+///
+/// Units follow the humantime conventions: `ns`, `us`, `ms`, `s`, `m`, `h`,
+/// `d` and `w`, plus long forms like `sec`, `min` and `hours`. Whitespace
+/// between pairs is allowed, and a bare integer is a number of seconds.
+/// Returns `None` for malformed or overflowing input.
+pub(crate) fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+    if s.bytes().all(|b| b.is_ascii_digit()) {
+        return s.parse().ok().map(Duration::from_secs);
+    }
+
+    let mut total: u128 = 0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let count: u64 = rest[..digits].parse().ok()?;
+        rest = rest[digits..].trim_start();
+
+        let letters = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = unit_nanos(&rest[..letters])?;
+        rest = rest[letters..].trim_start();
+
+        total = total.checked_add(u128::from(count) * u128::from(unit))?;
+    }
+
+    let secs = u64::try_from(total / NANOS_PER_SEC).ok()?;
+    Some(Duration::new(secs, (total % NANOS_PER_SEC) as u32))
+}
+
+/// Nanoseconds in one of the given unit, or `None` if it isn't a unit.
+fn unit_nanos(unit: &str) -> Option<u64> {
+    let nanos = match unit {
+        "ns" | "nsec" | "nanos" => 1,
+        "us" | "µs" | "usec" | "micros" => 1_000,
+        "ms" | "msec" | "millis" => 1_000_000,
+        "s" | "sec" | "secs" | "second" | "seconds" => 1_000_000_000,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60 * 1_000_000_000,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 3_600 * 1_000_000_000,
+        "d" | "day" | "days" => 86_400 * 1_000_000_000,
+        "w" | "week" | "weeks" => 604_800 * 1_000_000_000,
+        _ => return None,
+    };
+    Some(nanos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_units() {
+        assert_eq!(parse_duration("1500ms"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration("2m"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1h"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("2d"), Some(Duration::from_secs(172_800)));
+        assert_eq!(parse_duration("250us"), Some(Duration::from_micros(250)));
+        assert_eq!(parse_duration("5 minutes"), Some(Duration::from_secs(300)));
+    }
+
+    #[test]
+    fn test_parse_compound() {
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(
+            parse_duration("1m 30s 500ms"),
+            Some(Duration::from_millis(90_500))
+        );
+    }
+
+    #[test]
+    fn test_parse_bare_seconds() {
+        assert_eq!(parse_duration("90"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration(" 0 "), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("soon"), None);
+        assert_eq!(parse_duration("1h30"), None);
+        assert_eq!(parse_duration("-5s"), None);
+        assert_eq!(parse_duration("1.5s"), None);
+        assert_eq!(parse_duration("10 parsecs"), None);
+        assert_eq!(parse_duration("99999999999999999999s"), None);
+    }
+}
@@ -22,6 +22,22 @@ pub enum Error {
     )]
     FileNotFound(String),
 
+    /// A configuration file exists but this process may not read it.
+    #[cfg(feature = "std")]
+    #[cfg_attr(
+        feature = "std",
+        error("Permission denied reading configuration file '{}'", .0.display())
+    )]
+    PermissionDenied(PathBuf),
+
+    /// A directory was given where a configuration file was expected.
+    #[cfg(feature = "std")]
+    #[cfg_attr(
+        feature = "std",
+        error("Expected a configuration file but '{}' is a directory", .0.display())
+    )]
+    IsADirectory(PathBuf),
+
     /// Failed to read configuration file.
     #[cfg(feature = "std")]
     #[cfg_attr(feature = "std", error("Failed to read configuration file: {0}"))]
@@ -43,6 +59,14 @@ pub enum Error {
     #[cfg_attr(feature = "std", error("Configuration key '{0}' not found"))]
     KeyNotFound(String),
 
+    /// One or more required configuration keys were not found.
+    #[cfg(feature = "std")]
+    #[cfg_attr(
+        feature = "std",
+        error("Missing required configuration keys: {}", .0.join(", "))
+    )]
+    MissingKeys(Vec<String>),
+
     /// Failed to convert configuration value to requested type.
     #[cfg_attr(
         feature = "std",
@@ -91,6 +115,26 @@ pub enum Error {
     #[cfg_attr(feature = "std", error("No formatter found for source: {0}"))]
     NoFormatterFound(String),
 
+    /// A chain of includes or references leads back to where it started.
+    ///
+    /// Holds the chain in order, ending with the entry that closes the
+    /// cycle, e.g. `["a.url", "b.host", "a.url"]`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(
+        feature = "std",
+        error("Cyclic reference detected: {}", .0.join(" -> "))
+    )]
+    CyclicReference(Vec<String>),
+
+    /// Two layers set the same key to different values under
+    /// `ConflictPolicy::Error`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(
+        feature = "std",
+        error("Configuration key '{key}' from '{source_name}' conflicts with an earlier layer")
+    )]
+    MergeConflict { key: String, source_name: String },
+
     /// The loader does not support watching for changes.
     #[cfg(feature = "std")]
     #[cfg_attr(feature = "std", error("Watching is not supported for: {0}"))]
@@ -122,15 +166,56 @@ impl Error {
     /// Add key context to a ConversionError.
     ///
     /// If this is a ConversionError, returns a new ConversionError with the
-    /// specified key. Otherwise returns self unchanged.
+    /// specified key prepended to any key it already has, so nested
+    /// conversions report the full path (e.g. `database.primary.host` or
+    /// `servers[0].port`). Otherwise returns self unchanged.
     #[rustfmt::skip] // Keep single-line for consistent LLVM coverage instrumentation
     pub fn with_key(self, key: impl Into<String>) -> Self {
-        if let Error::ConversionError { type_name, source, .. } = self {
-            Error::ConversionError { key: key.into(), type_name, source }
+        if let Error::ConversionError { key: inner, type_name, source } = self {
+            Error::ConversionError { key: join_key_path(key.into(), &inner), type_name, source }
         } else {
             self
         }
     }
+
+    /// Returns true if something was simply absent: a file, key, loader,
+    /// or formatter.
+    ///
+    /// Useful for falling back to defaults while still surfacing real
+    /// failures such as parse errors.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Error::KeyNotFound(_) => true,
+            #[cfg(feature = "std")]
+            Error::FileNotFound(_) | Error::NoLoaderFound(_) | Error::NoFormatterFound(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if a source was found but its content could not be
+    /// parsed.
+    pub fn is_parse_error(&self) -> bool {
+        match self {
+            #[cfg(feature = "std")]
+            Error::ParseError { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if a value was present but could not be converted to
+    /// the requested type.
+    pub fn is_conversion_error(&self) -> bool {
+        matches!(self, Error::ConversionError { .. })
+    }
+}
+
+/// Join an outer key onto an inner key path, without a dot before indices.
+fn join_key_path(mut outer: String, inner: &str) -> String {
+    if !inner.is_empty() && !outer.is_empty() && !inner.starts_with('[') {
+        outer.push('.');
+    }
+    outer.push_str(inner);
+    outer
 }
 
 #[cfg(test)]
@@ -151,6 +236,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_with_key_prepends_to_existing_key() {
+        let err = Error::ConversionError {
+            key: String::new(),
+            type_name: "u16".into(),
+            source: "test".into(),
+        };
+        let result = err.with_key("port").with_key("[0]").with_key("servers");
+        match result {
+            Error::ConversionError { key, .. } => assert_eq!(key, "servers[0].port"),
+            _ => panic!("expected ConversionError"),
+        }
+    }
+
     #[test]
     fn test_with_key_other_error() {
         let err = Error::FileNotFound("test.json".into());
@@ -158,6 +257,54 @@ mod tests {
         assert!(matches!(result, Error::FileNotFound(s) if s == "test.json"));
     }
 
+    #[test]
+    fn test_classification_helpers() {
+        let parse_error = || Error::ParseError {
+            format: "JSON".into(),
+            path: PathBuf::from("app.json"),
+            source: "unexpected end of input".into(),
+        };
+        let conversion_error = || Error::ConversionError {
+            key: "port".into(),
+            type_name: "u16".into(),
+            source: "expected integer".into(),
+        };
+
+        let not_found = [
+            Error::FileNotFound("app".into()),
+            Error::KeyNotFound("server.port".into()),
+            Error::NoLoaderFound("redis://localhost".into()),
+            Error::NoFormatterFound("bson".into()),
+        ];
+        for err in &not_found {
+            assert!(err.is_not_found(), "{:?}", err);
+            assert!(!err.is_parse_error(), "{:?}", err);
+            assert!(!err.is_conversion_error(), "{:?}", err);
+        }
+
+        assert!(parse_error().is_parse_error());
+        assert!(!parse_error().is_not_found());
+        assert!(!parse_error().is_conversion_error());
+
+        assert!(conversion_error().is_conversion_error());
+        assert!(conversion_error().with_key("server").is_conversion_error());
+        assert!(!conversion_error().is_not_found());
+        assert!(!conversion_error().is_parse_error());
+
+        let others = [
+            Error::MissingKeys(vec!["a".into()]),
+            Error::IsADirectory(PathBuf::from("conf.d")),
+            Error::PermissionDenied(PathBuf::from("secret.toml")),
+            Error::WatchNotSupported("env".into()),
+            Error::IoError(std::io::Error::other("disk on fire")),
+        ];
+        for err in &others {
+            assert!(!err.is_not_found(), "{:?}", err);
+            assert!(!err.is_parse_error(), "{:?}", err);
+            assert!(!err.is_conversion_error(), "{:?}", err);
+        }
+    }
+
     #[test]
     fn test_display_no_loader_found() {
         let err = Error::NoLoaderFound("postgres://localhost".into());
@@ -194,6 +341,15 @@ mod tests {
         assert!(err.to_string().contains("server.port"));
     }
 
+    #[test]
+    fn test_display_missing_keys() {
+        let err = Error::MissingKeys(vec!["db.host".into(), "db.port".into()]);
+        assert_eq!(
+            err.to_string(),
+            "Missing required configuration keys: db.host, db.port"
+        );
+    }
+
     #[test]
     fn test_display_conversion_error() {
         let err = Error::ConversionError {
@@ -206,6 +362,25 @@ mod tests {
         assert!(msg.contains("u16"));
     }
 
+    #[test]
+    fn test_display_cyclic_reference() {
+        let err = Error::CyclicReference(vec!["a.url".into(), "b.host".into(), "a.url".into()]);
+        assert_eq!(
+            err.to_string(),
+            "Cyclic reference detected: a.url -> b.host -> a.url"
+        );
+    }
+
+    #[test]
+    fn test_display_merge_conflict() {
+        let err = Error::MergeConflict {
+            key: "security.tls".into(),
+            source_name: "user.toml".into(),
+        };
+        assert!(err.to_string().contains("security.tls"));
+        assert!(err.to_string().contains("user.toml"));
+    }
+
     #[test]
     fn test_display_unsupported_format() {
         let err = Error::UnsupportedFormat(PathBuf::from("config.bson"));
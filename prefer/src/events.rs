@@ -57,6 +57,17 @@ impl Emitter {
     }
 }
 
+/// Whether `key` is `prefix` itself or nested under it.
+///
+/// `server.port` and `server[0]` are under `server`, but `servers` is not.
+/// An empty prefix matches every key.
+pub(crate) fn key_under_prefix(key: &str, prefix: &str) -> bool {
+    prefix.is_empty()
+        || key
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+}
+
 impl Default for Emitter {
     fn default() -> Self {
         Self::new()
@@ -120,6 +131,17 @@ mod tests {
         emitter.emit("changed", "key", &ConfigValue::Null, None);
     }
 
+    #[test]
+    fn test_key_under_prefix() {
+        assert!(key_under_prefix("server", "server"));
+        assert!(key_under_prefix("server.port", "server"));
+        assert!(key_under_prefix("server[0].host", "server"));
+        assert!(key_under_prefix("anything", ""));
+        assert!(!key_under_prefix("servers", "server"));
+        assert!(!key_under_prefix("server", "server.port"));
+        assert!(!key_under_prefix("database.host", "server"));
+    }
+
     #[test]
     fn test_multiple_handlers() {
         let mut emitter = Emitter::new();
@@ -0,0 +1,659 @@
+//! HJSON (Human JSON) format support.
+
+use crate::error::{Error, Result};
+use crate::formatter::{extension_matches, Formatter};
+use crate::registry::RegisteredFormatter;
+use crate::value::{wide_integer, ConfigValue};
+use std::collections::HashMap;
+
+inventory::submit! { RegisteredFormatter(&HjsonFormatter) }
+
+/// Formatter for HJSON files.
+///
+/// HJSON relaxes JSON for hand editing: `#`, `//` and `/* */` comments,
+/// optional commas, unquoted keys, quoteless strings that run to the end of
+/// the line, `'''` multiline strings, and optional braces around the root
+/// object. Parsed with a small built-in parser (no extra dependency).
+///
+/// Serialized output puts one member per line, leaves keys unquoted where
+/// possible, and always quotes strings.
+pub struct HjsonFormatter;
+
+impl Formatter for HjsonFormatter {
+    fn provides(&self, identifier: &str) -> bool {
+        extension_matches(identifier, self.extensions())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["hjson"]
+    }
+
+    fn deserialize(&self, content: &str) -> Result<ConfigValue> {
+        Parser::new(content)
+            .parse_root()
+            .map_err(|message| Error::ParseError {
+                format: "HJSON".to_string(),
+                path: std::path::PathBuf::from("<content>"),
+                source: message.into(),
+            })
+    }
+
+    fn serialize(&self, value: &ConfigValue) -> Result<String> {
+        let mut out = String::new();
+        write_value(value, 0, &mut out);
+        out.push('\n');
+        Ok(out)
+    }
+
+    fn name(&self) -> &str {
+        "hjson"
+    }
+}
+
+/// Parse errors are messages that already include the position.
+type ParseResult<T> = std::result::Result<T, String>;
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, message: &str) -> String {
+        let before = &self.src[..self.pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        format!("{} at line {}, column {}", message, line, column)
+    }
+
+    /// Skip whitespace, including line breaks, and comments.
+    fn skip_trivia(&mut self) -> ParseResult<()> {
+        loop {
+            let rest = self.rest();
+            if rest.starts_with('#') || rest.starts_with("//") {
+                self.pos += rest.find('\n').unwrap_or(rest.len());
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                let end = comment
+                    .find("*/")
+                    .ok_or_else(|| self.error("unterminated comment"))?;
+                self.pos += end + 4;
+            } else if let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+                self.pos += c.len_utf8();
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Skip trivia and at most one comma after a member or element.
+    fn skip_separator(&mut self) -> ParseResult<()> {
+        self.skip_trivia()?;
+        if self.peek() == Some(',') {
+            self.bump();
+        }
+        Ok(())
+    }
+
+    /// Parse a whole document, whose root object may omit its braces.
+    fn parse_root(&mut self) -> ParseResult<ConfigValue> {
+        self.skip_trivia()?;
+        let value = match self.peek() {
+            None => return Ok(ConfigValue::Object(HashMap::new())),
+            Some('{' | '[') => self.parse_value()?,
+            Some(_) => {
+                let start = self.pos;
+                match self.parse_members(None) {
+                    Ok(members) => return Ok(ConfigValue::Object(members)),
+                    // Not a braceless object, so try a single value instead
+                    Err(e) => {
+                        self.pos = start;
+                        self.parse_value().map_err(|_| e)?
+                    }
+                }
+            }
+        };
+
+        self.skip_trivia()?;
+        match self.peek() {
+            None => Ok(value),
+            Some(_) => Err(self.error("unexpected content after the root value")),
+        }
+    }
+
+    /// Parse object members up to `close`, or to the end of input if `None`.
+    ///
+    /// The opening brace, if any, has already been consumed.
+    fn parse_members(&mut self, close: Option<char>) -> ParseResult<HashMap<String, ConfigValue>> {
+        let mut members = HashMap::new();
+        loop {
+            self.skip_trivia()?;
+            match (self.peek(), close) {
+                (None, None) => return Ok(members),
+                (None, Some(_)) => return Err(self.error("unterminated object")),
+                (Some(c), Some(end)) if c == end => {
+                    self.bump();
+                    return Ok(members);
+                }
+                _ => {}
+            }
+
+            let key = self.parse_key()?;
+            self.skip_trivia()?;
+            if self.peek() != Some(':') {
+                return Err(self.error(&format!("expected ':' after key '{}'", key)));
+            }
+            self.bump();
+
+            let value = self.parse_value()?;
+            members.insert(key, value);
+            self.skip_separator()?;
+        }
+    }
+
+    fn parse_key(&mut self) -> ParseResult<String> {
+        if let Some(quote @ ('"' | '\'')) = self.peek() {
+            self.bump();
+            return self.parse_quoted(quote);
+        }
+
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || ",:[]{}".contains(c))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a key"));
+        }
+        self.pos += len;
+        Ok(rest[..len].to_string())
+    }
+
+    fn parse_value(&mut self) -> ParseResult<ConfigValue> {
+        self.skip_trivia()?;
+        match self.peek() {
+            None => Err(self.error("expected a value")),
+            Some('{') => {
+                self.bump();
+                self.parse_members(Some('}')).map(ConfigValue::Object)
+            }
+            Some('[') => {
+                self.bump();
+                self.parse_array()
+            }
+            Some('\'') if self.rest().starts_with("'''") => {
+                self.parse_multiline().map(ConfigValue::String)
+            }
+            Some(quote @ ('"' | '\'')) => {
+                self.bump();
+                self.parse_quoted(quote).map(ConfigValue::String)
+            }
+            Some(c) if ",:]}".contains(c) => Err(self.error(&format!("unexpected '{}'", c))),
+            Some(_) => Ok(self.parse_unquoted()),
+        }
+    }
+
+    /// Parse array elements; the opening bracket has already been consumed.
+    fn parse_array(&mut self) -> ParseResult<ConfigValue> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            match self.peek() {
+                None => return Err(self.error("unterminated array")),
+                Some(']') => {
+                    self.bump();
+                    return Ok(ConfigValue::Array(items));
+                }
+                _ => {}
+            }
+
+            items.push(self.parse_value()?);
+            self.skip_separator()?;
+        }
+    }
+
+    /// Parse a literal (`true`, `false`, `null`, or a number) or a
+    /// quoteless string.
+    ///
+    /// A literal must be followed by the end of the line, a comma, a
+    /// closing bracket, or a comment. Anything else makes the rest of the
+    /// line a quoteless string, so `5 apples` is a string.
+    fn parse_unquoted(&mut self) -> ConfigValue {
+        let rest = self.rest();
+        let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+
+        let token_len = line
+            .find(|c: char| c.is_whitespace() || ",]}".contains(c))
+            .unwrap_or(line.len());
+        let after = line[token_len..].trim_start();
+        let ends_literal = after.is_empty()
+            || after.starts_with([',', ']', '}', '#'])
+            || after.starts_with("//")
+            || after.starts_with("/*");
+
+        if ends_literal {
+            if let Some(value) = parse_literal(&line[..token_len]) {
+                self.pos += token_len;
+                return value;
+            }
+        }
+
+        self.pos += line.len();
+        ConfigValue::String(line.trim_end().to_string())
+    }
+
+    /// Parse a quoted string; the opening quote has already been consumed.
+    fn parse_quoted(&mut self, quote: char) -> ParseResult<String> {
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                None | Some('\n') => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => return Ok(out),
+                Some('\\') => out.push(self.parse_escape()?),
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    fn parse_escape(&mut self) -> ParseResult<char> {
+        let c = match self.bump() {
+            Some('"') => '"',
+            Some('\'') => '\'',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+                let mut code = self.parse_hex4()?;
+                if (0xD800..0xDC00).contains(&code) && self.rest().starts_with("\\u") {
+                    self.pos += 2;
+                    let low = self.parse_hex4()?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(self.error("invalid surrogate pair"));
+                    }
+                    code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                }
+                char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))?
+            }
+            _ => return Err(self.error("invalid escape sequence")),
+        };
+        Ok(c)
+    }
+
+    fn parse_hex4(&mut self) -> ParseResult<u32> {
+        let hex = self
+            .rest()
+            .get(..4)
+            .filter(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(u32::from_str_radix(hex, 16).expect("validated hex digits"))
+    }
+
+    /// Parse a `'''` multiline string.
+    ///
+    /// As in the HJSON spec, the line break after the opening quotes and
+    /// the one before the closing quotes are dropped, and each line loses
+    /// up to as much leading whitespace as the opening quotes were indented.
+    fn parse_multiline(&mut self) -> ParseResult<String> {
+        let indent = self.src[..self.pos]
+            .rsplit('\n')
+            .next()
+            .unwrap_or("")
+            .chars()
+            .count();
+        self.pos += 3;
+
+        let rest = self.rest();
+        let end = rest
+            .find("'''")
+            .ok_or_else(|| self.error("unterminated multiline string"))?;
+        self.pos += end + 3;
+
+        let text = rest[..end].replace("\r\n", "\n");
+        let mut body = text.as_str();
+        if let Some(i) = body.find('\n').filter(|&i| body[..i].trim().is_empty()) {
+            body = &body[i + 1..];
+        }
+        if let Some(i) = body
+            .rfind('\n')
+            .filter(|&i| body[i + 1..].trim().is_empty())
+        {
+            body = &body[..i];
+        }
+
+        let lines: Vec<&str> = body
+            .split('\n')
+            .map(|line| strip_indent(line, indent))
+            .collect();
+        Ok(lines.join("\n"))
+    }
+}
+
+/// Remove up to `indent` leading whitespace characters from a line.
+fn strip_indent(line: &str, indent: usize) -> &str {
+    let skip = line
+        .char_indices()
+        .take(indent)
+        .take_while(|(_, c)| c.is_whitespace())
+        .last()
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    &line[skip..]
+}
+
+fn parse_literal(token: &str) -> Option<ConfigValue> {
+    match token {
+        "true" => Some(ConfigValue::Bool(true)),
+        "false" => Some(ConfigValue::Bool(false)),
+        "null" => Some(ConfigValue::Null),
+        _ if is_json_number(token) => Some(match token.parse::<i64>() {
+            Ok(n) => ConfigValue::Integer(n),
+            Err(_) => match wide_integer(token) {
+                Some(wide) => wide,
+                None => ConfigValue::Float(token.parse().ok()?),
+            },
+        }),
+        _ => None,
+    }
+}
+
+/// Whether a token follows JSON's number syntax.
+fn is_json_number(s: &str) -> bool {
+    fn digits(s: &str) -> (&str, &str) {
+        s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()))
+    }
+
+    let (int, rest) = digits(s.strip_prefix('-').unwrap_or(s));
+    if int.is_empty() || (int.len() > 1 && int.starts_with('0')) {
+        return false;
+    }
+
+    let rest = match rest.strip_prefix('.') {
+        Some(frac) => match digits(frac) {
+            ("", _) => return false,
+            (_, rest) => rest,
+        },
+        None => rest,
+    };
+
+    match rest.strip_prefix(['e', 'E']) {
+        Some(exp) => match digits(exp.strip_prefix(['+', '-']).unwrap_or(exp)) {
+            ("", _) => false,
+            (_, rest) => rest.is_empty(),
+        },
+        None => rest.is_empty(),
+    }
+}
+
+fn push_indent(out: &mut String, level: usize) {
+    out.extend(std::iter::repeat_n("  ", level));
+}
+
+fn write_value(value: &ConfigValue, level: usize, out: &mut String) {
+    match value {
+        ConfigValue::Null => out.push_str("null"),
+        ConfigValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        ConfigValue::Integer(i) => out.push_str(&i.to_string()),
+        // Debug formatting keeps a decimal point, so floats stay floats
+        ConfigValue::Float(f) if f.is_finite() => out.push_str(&format!("{:?}", f)),
+        ConfigValue::Float(_) => out.push_str("null"),
+        ConfigValue::String(s) => write_quoted(s, out),
+        ConfigValue::Bytes(bytes) => write_quoted(&super::encode_bytes(bytes), out),
+        ConfigValue::Array(arr) if arr.is_empty() => out.push_str("[]"),
+        ConfigValue::Array(arr) => {
+            out.push_str("[\n");
+            for item in arr {
+                push_indent(out, level + 1);
+                write_value(item, level + 1, out);
+                out.push('\n');
+            }
+            push_indent(out, level);
+            out.push(']');
+        }
+        ConfigValue::Object(map) if map.is_empty() => out.push_str("{}"),
+        ConfigValue::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+
+            out.push_str("{\n");
+            for (key, value) in entries {
+                push_indent(out, level + 1);
+                write_key(key, out);
+                out.push_str(": ");
+                write_value(value, level + 1, out);
+                out.push('\n');
+            }
+            push_indent(out, level);
+            out.push('}');
+        }
+    }
+}
+
+fn write_key(key: &str, out: &mut String) {
+    let needs_quotes = key.is_empty()
+        || key
+            .chars()
+            .any(|c| c.is_whitespace() || ",:[]{}\"'#/".contains(c));
+    if needs_quotes {
+        write_quoted(key, out);
+    } else {
+        out.push_str(key);
+    }
+}
+
+fn write_quoted(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::test_helpers::{array, bool_val, float, int, obj, string};
+
+    #[test]
+    fn test_provides() {
+        let f = HjsonFormatter;
+        assert!(f.provides("config.hjson"));
+        assert!(!f.provides("config.json"));
+        assert!(!f.provides("config"));
+    }
+
+    #[test]
+    fn test_deserialize_wide_integer() {
+        let f = HjsonFormatter;
+        let result = f
+            .deserialize("{\n  id: 170141183460469231731687303715884105727\n  ratio: 1e40\n}")
+            .unwrap();
+        assert_eq!(result.get("id").unwrap().as_i128(), Some(i128::MAX));
+        assert_eq!(result.get("ratio").unwrap().as_f64(), Some(1e40));
+    }
+
+    #[test]
+    fn test_deserialize_comments() {
+        let f = HjsonFormatter;
+        let result = f
+            .deserialize(
+                "{\n  # hash comment\n  a: 1 // line comment\n  /* block\n     comment */\n  b: true, # trailing\n}",
+            )
+            .unwrap();
+        assert_eq!(result, obj(vec![("a", int(1)), ("b", bool_val(true))]));
+    }
+
+    #[test]
+    fn test_deserialize_unquoted_keys_and_strings() {
+        let f = HjsonFormatter;
+        let result = f
+            .deserialize(
+                "{\n  name: my app\n  greeting: hello, world # not a comment\n  count: 5 apples\n  \"quoted key\": 'single'\n  ratio: -1.5e2\n  empty: null\n}",
+            )
+            .unwrap();
+
+        assert_eq!(result.get("name").unwrap().as_str(), Some("my app"));
+        assert_eq!(
+            result.get("greeting").unwrap().as_str(),
+            Some("hello, world # not a comment")
+        );
+        assert_eq!(result.get("count").unwrap().as_str(), Some("5 apples"));
+        assert_eq!(result.get("quoted key").unwrap().as_str(), Some("single"));
+        assert_eq!(result.get("ratio").unwrap().as_f64(), Some(-150.0));
+        assert_eq!(result.get("empty"), Some(&ConfigValue::Null));
+    }
+
+    #[test]
+    fn test_deserialize_multiline_string() {
+        let f = HjsonFormatter;
+        let result = f
+            .deserialize(
+                "{\n  text:\n    '''\n    first line\n      indented\n    last line\n    '''\n  inline: '''one line'''\n}",
+            )
+            .unwrap();
+        assert_eq!(
+            result.get("text").unwrap().as_str(),
+            Some("first line\n  indented\nlast line")
+        );
+        assert_eq!(result.get("inline").unwrap().as_str(), Some("one line"));
+    }
+
+    #[test]
+    fn test_deserialize_braceless_root() {
+        let f = HjsonFormatter;
+        let result = f
+            .deserialize("# app settings\nname: app\nserver: {\n  port: 8080\n}\n")
+            .unwrap();
+        assert_eq!(result.get("name").unwrap().as_str(), Some("app"));
+        assert_eq!(
+            result.get("server").unwrap().get("port").unwrap().as_i64(),
+            Some(8080)
+        );
+
+        assert_eq!(f.deserialize("").unwrap(), obj(vec![]));
+        assert_eq!(f.deserialize("\"just text\"").unwrap(), string("just text"));
+    }
+
+    #[test]
+    fn test_deserialize_arrays() {
+        let f = HjsonFormatter;
+        let result = f
+            .deserialize(
+                "{\n  ports: [80, 443,]\n  hosts: [\n    a.example.com\n    b.example.com\n  ]\n}",
+            )
+            .unwrap();
+        assert_eq!(result.get("ports"), Some(&array(vec![int(80), int(443)])));
+        assert_eq!(
+            result.get("hosts"),
+            Some(&array(vec![
+                string("a.example.com"),
+                string("b.example.com")
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_escapes() {
+        let f = HjsonFormatter;
+        let result = f
+            .deserialize(r#"{ s: "tab\there \"q\" \u00e9 \ud83d\ude00" }"#)
+            .unwrap();
+        assert_eq!(
+            result.get("s").unwrap().as_str(),
+            Some("tab\there \"q\" é 😀")
+        );
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        let f = HjsonFormatter;
+        assert!(f.deserialize("{ a: 1").is_err());
+        assert!(f.deserialize("{ a 1 }").is_err());
+        assert!(f.deserialize("{ a: \"open }").is_err());
+        assert!(f.deserialize("{ a: ''' open }").is_err());
+        assert!(f.deserialize("[1, 2").is_err());
+        assert!(f.deserialize("{ a: 1 } extra").is_err());
+
+        match f.deserialize("{\n  a 1\n}") {
+            Err(Error::ParseError { format, source, .. }) => {
+                assert_eq!(format, "HJSON");
+                assert!(source.to_string().contains("line 2"));
+            }
+            other => panic!("expected ParseError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_is_json_number() {
+        for s in ["0", "-1", "42", "1.5", "-0.25", "1e3", "2E-2", "1.5e+10"] {
+            assert!(is_json_number(s), "{}", s);
+        }
+        for s in [
+            "", "-", "007", "1.", ".5", "1e", "+1", "0x10", "inf", "1.2.3",
+        ] {
+            assert!(!is_json_number(s), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_serialize_roundtrip() {
+        let f = HjsonFormatter;
+        let original = obj(vec![
+            ("name", string("my app")),
+            ("port", int(8080)),
+            ("ratio", float(1.0)),
+            ("debug", bool_val(false)),
+            ("nothing", ConfigValue::Null),
+            ("text", string("line one\nline \"two\"\t# not a comment")),
+            ("odd key: yes", string("")),
+            ("tags", array(vec![string("a"), int(1), array(vec![])])),
+            (
+                "server",
+                obj(vec![("host", string("localhost")), ("tls", obj(vec![]))]),
+            ),
+        ]);
+
+        let serialized = f.serialize(&original).unwrap();
+        assert!(serialized.contains("\n  port: 8080\n"));
+        assert!(serialized.contains("\"odd key: yes\": \"\""));
+
+        let restored = f.deserialize(&serialized).unwrap();
+        assert_eq!(original, restored);
+    }
+
+    #[test]
+    fn test_serialize_scalars() {
+        let f = HjsonFormatter;
+        assert_eq!(f.serialize(&int(1)).unwrap(), "1\n");
+        assert_eq!(f.serialize(&float(2.5)).unwrap(), "2.5\n");
+        assert_eq!(f.serialize(&float(f64::NAN)).unwrap(), "null\n");
+        assert_eq!(f.serialize(&string("\u{1}")).unwrap(), "\"\\u0001\"\n");
+    }
+}
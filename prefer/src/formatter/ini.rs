@@ -1,18 +1,19 @@
 //! INI format support.
 
 use crate::error::{Error, Result};
-use crate::formatter::{extension_matches, Formatter};
+use crate::formatter::toml::TomlFormatter;
+use crate::formatter::{content_lines, extension_matches, is_section_header, Formatter};
 use crate::registry::RegisteredFormatter;
-use crate::value::ConfigValue;
+use crate::value::{infer_scalar, ConfigValue};
 use std::collections::HashMap;
 
 inventory::submit! { RegisteredFormatter(&IniFormatter) }
 
 /// Formatter for INI files.
 ///
-/// Uses the `rust-ini` crate. Auto-detects value types: booleans, integers,
-/// floats, and strings. Groups values by section name, with a "default"
-/// section for global keys.
+/// Uses the `rust-ini` crate. Auto-detects value types (booleans, integers,
+/// floats, and strings) with `infer_scalar()`. Groups values by section
+/// name, with a "default" section for global keys.
 pub struct IniFormatter;
 
 impl Formatter for IniFormatter {
@@ -20,6 +21,23 @@ impl Formatter for IniFormatter {
         extension_matches(identifier, self.extensions())
     }
 
+    /// Recognizes `[section]` headers and `key = value` lines. Content that
+    /// also looks like TOML is left to `TomlFormatter`, which parses it the
+    /// same way while keeping quoted strings intact.
+    fn provides_content(&self, content: &str) -> bool {
+        if TomlFormatter.provides_content(content) {
+            return false;
+        }
+        match content_lines(content, &["#", ";"]).next() {
+            Some(line) if line.starts_with("[[") => false,
+            Some(line) if is_section_header(line) => true,
+            Some(line) => line
+                .split_once('=')
+                .is_some_and(|(key, _)| !key.trim().is_empty()),
+            None => false,
+        }
+    }
+
     fn extensions(&self) -> &[&str] {
         &["ini"]
     }
@@ -40,17 +58,7 @@ impl Formatter for IniFormatter {
             let mut section_map: HashMap<String, ConfigValue> = HashMap::new();
 
             for (key, value) in properties.iter() {
-                let parsed_value = if let Ok(num) = value.parse::<i64>() {
-                    ConfigValue::Integer(num)
-                } else if let Ok(num) = value.parse::<f64>() {
-                    ConfigValue::Float(num)
-                } else if let Ok(b) = value.parse::<bool>() {
-                    ConfigValue::Bool(b)
-                } else {
-                    ConfigValue::String(value.to_string())
-                };
-
-                section_map.insert(key.to_string(), parsed_value);
+                section_map.insert(key.to_string(), infer_scalar(value));
             }
 
             root.insert(section_name.to_string(), ConfigValue::Object(section_map));
@@ -93,6 +101,7 @@ fn ini_value_str(value: &ConfigValue) -> String {
         ConfigValue::Integer(i) => i.to_string(),
         ConfigValue::Float(f) => f.to_string(),
         ConfigValue::String(s) => s.clone(),
+        ConfigValue::Bytes(bytes) => super::encode_bytes(bytes),
         _ => String::new(),
     }
 }
@@ -108,6 +117,18 @@ mod tests {
         assert!(!f.provides("config.toml"));
     }
 
+    #[test]
+    fn test_provides_content() {
+        let f = IniFormatter;
+        assert!(f.provides_content("[server]\nhost = localhost"));
+        assert!(f.provides_content("; comment\nname = app"));
+        assert!(!f.provides_content("[[servers]]\nport = 80"));
+        assert!(!f.provides_content("[server]\nport = 8080"));
+        assert!(!f.provides_content(r#"{"name": "app"}"#));
+        assert!(!f.provides_content("name: app"));
+        assert!(!f.provides_content(""));
+    }
+
     #[test]
     fn test_deserialize() {
         let f = IniFormatter;
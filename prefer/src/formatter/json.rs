@@ -1,9 +1,9 @@
 //! JSON and JSON5 format support.
 
 use crate::error::{Error, Result};
-use crate::formatter::{extension_matches, Formatter};
+use crate::formatter::{content_lines, extension_matches, is_section_header, Formatter};
 use crate::registry::RegisteredFormatter;
-use crate::value::ConfigValue;
+use crate::value::{wide_integer, ConfigValue};
 use std::collections::HashMap;
 
 inventory::submit! { RegisteredFormatter(&JsonFormatter) }
@@ -19,6 +19,14 @@ impl Formatter for JsonFormatter {
         extension_matches(identifier, self.extensions())
     }
 
+    fn provides_content(&self, content: &str) -> bool {
+        match content_lines(content, &["//"]).next() {
+            Some(line) if line.starts_with('{') => true,
+            Some(line) if line.starts_with('[') => !is_section_header(line),
+            _ => false,
+        }
+    }
+
     fn extensions(&self) -> &[&str] {
         &["json", "json5", "jsonc"]
     }
@@ -48,7 +56,10 @@ fn jzon_to_config_value(value: jzon::JsonValue) -> ConfigValue {
         JsonValue::Null => ConfigValue::Null,
         JsonValue::Boolean(b) => ConfigValue::Bool(*b),
         JsonValue::Number(_) => {
-            if let Some(i) = value.as_i64() {
+            // `as_i64` wraps mantissas above `i64::MAX`, so check those first.
+            if let Some(wide) = value.as_u64().and_then(|n| wide_integer(&n.to_string())) {
+                wide
+            } else if let Some(i) = value.as_i64() {
                 ConfigValue::Integer(i)
             } else if let Some(f) = value.as_f64() {
                 ConfigValue::Float(f)
@@ -86,6 +97,7 @@ fn config_value_to_json(value: &ConfigValue) -> String {
         ConfigValue::Integer(i) => i.to_string(),
         ConfigValue::Float(f) => f.to_string(),
         ConfigValue::String(s) => format!("\"{}\"", super::escape_quotes(s)),
+        ConfigValue::Bytes(bytes) => format!("\"{}\"", super::encode_bytes(bytes)),
         ConfigValue::Array(arr) => {
             let items: Vec<String> = arr.iter().map(config_value_to_json).collect();
             format!("[{}]", items.join(","))
@@ -111,6 +123,17 @@ mod tests {
         assert!(!f.provides("config"));
     }
 
+    #[test]
+    fn test_provides_content() {
+        let f = JsonFormatter;
+        assert!(f.provides_content(r#"{"name": "app"}"#));
+        assert!(f.provides_content("\n  [1, 2, 3]"));
+        assert!(f.provides_content("// settings\n{\n  \"a\": 1\n}"));
+        assert!(!f.provides_content("[server]\nport = 8080"));
+        assert!(!f.provides_content("name: app"));
+        assert!(!f.provides_content(""));
+    }
+
     #[test]
     fn test_deserialize_object() {
         let f = JsonFormatter;
@@ -119,6 +142,16 @@ mod tests {
         assert_eq!(result.get("port").unwrap().as_i64(), Some(8080));
     }
 
+    #[test]
+    fn test_deserialize_u64_range() {
+        let f = JsonFormatter;
+        let result = f
+            .deserialize(r#"{"id": 18446744073709551615, "ratio": 1.5}"#)
+            .unwrap();
+        assert_eq!(result.get("id").unwrap().as_u128(), Some(u64::MAX.into()));
+        assert_eq!(result.get("ratio").unwrap().as_f64(), Some(1.5));
+    }
+
     #[test]
     fn test_deserialize_array() {
         let f = JsonFormatter;
@@ -154,6 +187,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_serialize_bytes_as_base64() {
+        let f = JsonFormatter;
+        let value = ConfigValue::Object(
+            [("key".to_string(), ConfigValue::Bytes(b"hello".to_vec()))]
+                .into_iter()
+                .collect(),
+        );
+
+        let serialized = f.serialize(&value).unwrap();
+        assert_eq!(serialized, r#"{"key":"aGVsbG8="}"#);
+
+        // Bytes load back as a base64 string
+        let restored = f.deserialize(&serialized).unwrap();
+        assert_eq!(restored.get("key").unwrap().as_str(), Some("aGVsbG8="));
+    }
+
+    #[test]
+    fn test_serialize_bytes_matches_to_json_string() {
+        let f = JsonFormatter;
+        for len in 0..=5 {
+            let value = ConfigValue::Bytes((250..=255).take(len).collect());
+            assert_eq!(f.serialize(&value).unwrap(), value.to_json_string());
+        }
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_bytes_round_trip() {
+        use crate::value::FromValue;
+
+        let f = JsonFormatter;
+        let serialized = f
+            .serialize(&ConfigValue::Bytes(vec![0, 1, 254, 255]))
+            .unwrap();
+        let restored = f.deserialize(&serialized).unwrap();
+        assert_eq!(
+            Vec::<u8>::from_value(&restored).unwrap(),
+            vec![0, 1, 254, 255]
+        );
+    }
+
     #[test]
     fn test_serialize_string_escaping() {
         let f = JsonFormatter;
@@ -3,6 +3,8 @@
 //! The `Formatter` trait separates format parsing from source loading.
 //! Each formatter declares what file extensions it handles via `provides()`
 //! and `extensions()`, and is discovered automatically through the registry.
+//! Formatters may also override `provides_content()` to recognize their
+//! format from the content itself.
 //!
 //! Built-in formatters:
 //! - `JsonFormatter` — `.json`, `.json5`, `.jsonc`
@@ -10,6 +12,7 @@
 //! - `TomlFormatter` — `.toml`
 //! - `IniFormatter` — `.ini` (behind `ini` feature)
 //! - `XmlFormatter` — `.xml` (behind `xml` feature)
+//! - `HjsonFormatter` — `.hjson` (behind `hjson` feature)
 
 pub mod json;
 pub mod toml;
@@ -21,7 +24,10 @@ pub mod ini;
 #[cfg(feature = "xml")]
 pub mod xml;
 
-use crate::error::Result;
+#[cfg(feature = "hjson")]
+pub mod hjson;
+
+use crate::error::{Error, Result};
 use crate::value::ConfigValue;
 use std::path::Path;
 
@@ -68,6 +74,15 @@ pub trait Formatter: Send + Sync + 'static {
     /// Typically checks the file extension against `extensions()`.
     fn provides(&self, identifier: &str) -> bool;
 
+    /// Whether this formatter recognizes the given content.
+    ///
+    /// Used to pick a formatter for sources without a usable extension,
+    /// such as extensionless files or stdin. Implementations should be a
+    /// cheap look at the leading text, not a full parse. Defaults to `false`.
+    fn provides_content(&self, _content: &str) -> bool {
+        false
+    }
+
     /// File extensions this formatter handles (without the leading dot).
     ///
     /// For example: `["json", "json5", "jsonc"]`.
@@ -81,6 +96,16 @@ pub trait Formatter: Send + Sync + 'static {
 
     /// Human-readable name for error messages.
     fn name(&self) -> &str;
+
+    /// Priority used to choose between formatters that handle the same
+    /// extension.
+    ///
+    /// The registry picks the highest-priority formatter that matches.
+    /// Built-in formatters use the default of `0`, so a formatter with a
+    /// positive priority overrides a built-in format.
+    fn priority(&self) -> i32 {
+        0
+    }
 }
 
 /// Check whether an identifier's file extension matches any of the given extensions.
@@ -95,11 +120,93 @@ pub fn extension_matches(identifier: &str, extensions: &[&str]) -> bool {
     extensions.contains(&ext)
 }
 
+/// Trimmed, non-empty lines of `content` that don't start with any of the
+/// given comment prefixes.
+///
+/// A utility for `Formatter::provides_content()` implementations.
+pub(crate) fn content_lines<'a>(
+    content: &'a str,
+    comment_prefixes: &'a [&str],
+) -> impl Iterator<Item = &'a str> {
+    content.lines().map(str::trim).filter(move |line| {
+        !line.is_empty() && !comment_prefixes.iter().any(|p| line.starts_with(p))
+    })
+}
+
+/// Whether a line is a bare `[section]` or `[[section]]` header.
+///
+/// Only simple names (alphanumerics, `_`, `-`, `.` and spaces) count, so
+/// JSON arrays like `["a", "b"]` are not mistaken for headers.
+pub(crate) fn is_section_header(line: &str) -> bool {
+    let inner = match line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+        Some(inner) => inner,
+        None => return false,
+    };
+    let inner = inner
+        .strip_prefix('[')
+        .and_then(|l| l.strip_suffix(']'))
+        .unwrap_or(inner)
+        .trim();
+    !inner.is_empty()
+        && inner
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ' '))
+}
+
 /// Escape backslashes and double quotes in a string for serialization.
 pub(crate) fn escape_quotes(s: &str) -> String {
     s.replace('\\', "\\\\").replace('"', "\\\"")
 }
 
+/// Encode bytes as a standard base64 string for text formats.
+pub(crate) fn encode_bytes(bytes: &[u8]) -> String {
+    use base64::Engine;
+
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+/// Parse content whose format is unknown by trying formatters in turn.
+///
+/// Used as a fallback for files with no extension or an unrecognized one
+/// (such as `.conf`). A formatter that recognizes the content (see
+/// `registry::find_formatter_by_content()`) is tried first. Otherwise,
+/// formats are tried from strictest to most permissive: JSON, then TOML,
+/// then YAML. The first successful parse wins.
+///
+/// Sniffing is inherently ambiguous. YAML accepts almost any text, so a
+/// malformed JSON or TOML file may be "successfully" read as YAML with
+/// surprising results; a YAML result that is a bare scalar rather than a
+/// mapping or sequence is therefore rejected. Prefer an explicit extension
+/// whenever possible.
+pub fn parse_sniff(contents: &str) -> Result<ConfigValue> {
+    sniff(contents).map(|(_, value)| value)
+}
+
+/// Like `parse_sniff()`, also returning the formatter that parsed the content.
+pub(crate) fn sniff(contents: &str) -> Result<(&'static dyn Formatter, ConfigValue)> {
+    if let Some(formatter) = crate::registry::find_formatter_by_content(contents) {
+        if let Ok(value) = formatter.deserialize(contents) {
+            return Ok((formatter, value));
+        }
+    }
+    if let Ok(value) = json::JsonFormatter.deserialize(contents) {
+        return Ok((&json::JsonFormatter, value));
+    }
+    if let Ok(value) = toml::TomlFormatter.deserialize(contents) {
+        return Ok((&toml::TomlFormatter, value));
+    }
+    match yaml::YamlFormatter.deserialize(contents) {
+        Ok(value @ (ConfigValue::Object(_) | ConfigValue::Array(_))) => {
+            Ok((&yaml::YamlFormatter, value))
+        }
+        _ => Err(Error::ParseError {
+            format: "unknown".to_string(),
+            path: std::path::PathBuf::from("<content>"),
+            source: "content is not a JSON, TOML, or YAML document".into(),
+        }),
+    }
+}
+
 /// Check whether a format hint string matches any of the given extensions.
 ///
 /// Used when matching by format hint rather than file extension.
@@ -119,6 +226,61 @@ mod tests {
         assert!(!extension_matches("no_extension", &["json"]));
     }
 
+    #[test]
+    fn test_parse_sniff_json() {
+        let value = parse_sniff(r#"{"name": "app", "port": 8080}"#).unwrap();
+        assert_eq!(value.get("name").unwrap().as_str(), Some("app"));
+        assert_eq!(value.get("port").unwrap().as_i64(), Some(8080));
+    }
+
+    #[test]
+    fn test_parse_sniff_toml() {
+        let value = parse_sniff("name = \"app\"\n\n[server]\nport = 8080\n").unwrap();
+        assert_eq!(value.get("name").unwrap().as_str(), Some("app"));
+        let server = value.get("server").unwrap();
+        assert_eq!(server.get("port").unwrap().as_i64(), Some(8080));
+    }
+
+    #[test]
+    fn test_parse_sniff_yaml() {
+        let value = parse_sniff("name: app\nserver:\n  port: 8080\n").unwrap();
+        assert_eq!(value.get("name").unwrap().as_str(), Some("app"));
+        let server = value.get("server").unwrap();
+        assert_eq!(server.get("port").unwrap().as_i64(), Some(8080));
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn test_parse_sniff_uses_content_detection() {
+        // Neither JSON, TOML, nor YAML, but recognized as INI
+        let value = parse_sniff("[server]\nhost = localhost\n").unwrap();
+        let server = value.get("server").unwrap();
+        assert_eq!(server.get("host").unwrap().as_str(), Some("localhost"));
+    }
+
+    #[test]
+    fn test_parse_sniff_rejects_plain_text() {
+        assert!(parse_sniff("just some words").is_err());
+    }
+
+    #[test]
+    fn test_content_lines_skips_blanks_and_comments() {
+        let lines: Vec<&str> =
+            content_lines("\n# comment\n  a = 1  \n; other\n", &["#", ";"]).collect();
+        assert_eq!(lines, vec!["a = 1"]);
+    }
+
+    #[test]
+    fn test_is_section_header() {
+        assert!(is_section_header("[server]"));
+        assert!(is_section_header("[[servers]]"));
+        assert!(is_section_header("[server.tls]"));
+        assert!(!is_section_header("[]"));
+        assert!(!is_section_header("[\"a\", \"b\"]"));
+        assert!(!is_section_header("[1, 2]"));
+        assert!(!is_section_header("server"));
+    }
+
     #[test]
     fn test_hint_matches() {
         assert!(hint_matches("json", &["json", "json5", "jsonc"]));
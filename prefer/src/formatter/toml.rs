@@ -1,7 +1,7 @@
 //! TOML format support.
 
 use crate::error::{Error, Result};
-use crate::formatter::{extension_matches, Formatter};
+use crate::formatter::{content_lines, extension_matches, is_section_header, Formatter};
 use crate::registry::RegisteredFormatter;
 use crate::value::ConfigValue;
 use std::collections::HashMap;
@@ -11,7 +11,10 @@ inventory::submit! { RegisteredFormatter(&TomlFormatter) }
 /// Formatter for TOML files.
 ///
 /// Uses the `toml_edit` crate (no serde dependency). Handles datetimes
-/// by converting to strings, and supports inline tables and array-of-tables.
+/// by converting to RFC 3339 strings (see `ConfigValue::as_datetime()` with
+/// the `chrono` feature), and supports inline tables and array-of-tables.
+/// When serializing, strings holding a full RFC 3339 datetime are written
+/// as bare TOML datetimes so they survive a round trip.
 pub struct TomlFormatter;
 
 impl Formatter for TomlFormatter {
@@ -19,6 +22,23 @@ impl Formatter for TomlFormatter {
         extension_matches(identifier, self.extensions())
     }
 
+    /// Recognizes `[table]` headers and `key = value` lines whose value is a
+    /// TOML literal (quoted string, number, boolean, array or inline table).
+    fn provides_content(&self, content: &str) -> bool {
+        let mut saw_header = false;
+        for line in content_lines(content, &["#"]) {
+            if is_section_header(line) {
+                saw_header = true;
+                continue;
+            }
+            return match line.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => is_toml_literal(value.trim()),
+                _ => false,
+            };
+        }
+        saw_header
+    }
+
     fn extensions(&self) -> &[&str] {
         &["toml"]
     }
@@ -82,7 +102,7 @@ fn toml_value_to_config_value(value: &toml_edit::Value) -> ConfigValue {
         Value::Integer(i) => ConfigValue::Integer(*i.value()),
         Value::Float(f) => ConfigValue::Float(*f.value()),
         Value::Boolean(b) => ConfigValue::Bool(*b.value()),
-        Value::Datetime(dt) => ConfigValue::String(dt.to_string()),
+        Value::Datetime(dt) => ConfigValue::String(dt.value().to_string()),
         Value::Array(arr) => {
             ConfigValue::Array(arr.iter().map(toml_value_to_config_value).collect())
         }
@@ -96,6 +116,27 @@ fn toml_value_to_config_value(value: &toml_edit::Value) -> ConfigValue {
     }
 }
 
+/// Whether the right-hand side of a `key = value` line looks like a TOML value.
+fn is_toml_literal(value: &str) -> bool {
+    match value.chars().next() {
+        Some('"' | '\'' | '[' | '{' | '+' | '-') => true,
+        Some(c) if c.is_ascii_digit() => true,
+        _ => ["true", "false", "inf", "nan"]
+            .iter()
+            .any(|word| value.split_whitespace().next() == Some(*word)),
+    }
+}
+
+/// Whether a string is an RFC 3339 datetime that TOML writes back unchanged.
+///
+/// Local dates and times are left quoted, since plain strings like
+/// `2024-01-15` are more often identifiers than dates.
+fn is_offset_datetime(s: &str) -> bool {
+    s.parse::<toml_edit::Datetime>().is_ok_and(|dt| {
+        dt.date.is_some() && dt.time.is_some() && dt.offset.is_some() && dt.to_string() == s
+    })
+}
+
 fn toml_full_key(prefix: &str, key: &str) -> String {
     if prefix.is_empty() {
         key.to_string()
@@ -110,7 +151,9 @@ fn config_value_to_toml(value: &ConfigValue, key_prefix: &str) -> String {
         ConfigValue::Bool(b) => b.to_string(),
         ConfigValue::Integer(i) => i.to_string(),
         ConfigValue::Float(f) => f.to_string(),
+        ConfigValue::String(s) if is_offset_datetime(s) => s.clone(),
         ConfigValue::String(s) => format!("\"{}\"", super::escape_quotes(s)),
+        ConfigValue::Bytes(bytes) => format!("\"{}\"", super::encode_bytes(bytes)),
         ConfigValue::Array(arr) => {
             let items: Vec<String> = arr
                 .iter()
@@ -157,6 +200,18 @@ mod tests {
         assert!(!f.provides("config.yaml"));
     }
 
+    #[test]
+    fn test_provides_content() {
+        let f = TomlFormatter;
+        assert!(f.provides_content("name = \"app\"\nport = 8080"));
+        assert!(f.provides_content("# comment\n[server]\nport = 8080"));
+        assert!(f.provides_content("[[servers]]\nenabled = true"));
+        assert!(!f.provides_content("[server]\nhost = localhost"));
+        assert!(!f.provides_content(r#"{"name": "app"}"#));
+        assert!(!f.provides_content("name: app"));
+        assert!(!f.provides_content(""));
+    }
+
     #[test]
     fn test_deserialize() {
         let f = TomlFormatter;
@@ -289,4 +344,52 @@ date_val = 2024-01-15
         // Datetimes become strings
         assert!(result.get("date_val").unwrap().as_str().is_some());
     }
+
+    #[test]
+    fn test_datetime_round_trip() {
+        let f = TomlFormatter;
+        let value = f
+            .deserialize("created = 2024-01-15T10:30:00Z\nday = 2024-01-15\n")
+            .unwrap();
+
+        let serialized = f.serialize(&value).unwrap();
+        assert!(serialized.contains("created = 2024-01-15T10:30:00Z"));
+        assert!(serialized.contains("day = \"2024-01-15\""));
+
+        let doc: toml_edit::DocumentMut = serialized.parse().unwrap();
+        assert!(doc["created"].as_value().unwrap().is_datetime());
+        assert!(doc["day"].as_value().unwrap().is_str());
+        assert_eq!(f.deserialize(&serialized).unwrap(), value);
+    }
+
+    #[test]
+    fn test_serialize_datetime_like_strings_stay_quoted() {
+        let f = TomlFormatter;
+        for s in ["2024-01-15 10:30:00Z", "10:30:00", "2024-01-15T10:30:00"] {
+            assert_eq!(
+                f.serialize(&ConfigValue::String(s.into())).unwrap(),
+                format!("\"{}\"", s)
+            );
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_deserialize_datetime_as_chrono() {
+        use crate::value::FromValue;
+        use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+        let f = TomlFormatter;
+        let result = f
+            .deserialize("created = 2024-01-15T10:30:00Z\nday = 2024-01-15\n")
+            .unwrap();
+
+        let created = result.get("created").unwrap();
+        let expected = Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap();
+        assert_eq!(created.as_datetime().unwrap(), expected);
+        assert_eq!(DateTime::<Utc>::from_value(created).unwrap(), expected);
+
+        let day = NaiveDate::from_value(result.get("day").unwrap()).unwrap();
+        assert_eq!(day, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+    }
 }
@@ -3,7 +3,7 @@
 use crate::error::{Error, Result};
 use crate::formatter::{extension_matches, Formatter};
 use crate::registry::RegisteredFormatter;
-use crate::value::ConfigValue;
+use crate::value::{wide_integer, ConfigValue};
 use std::collections::HashMap;
 
 inventory::submit! { RegisteredFormatter(&XmlFormatter) }
@@ -20,6 +20,14 @@ impl Formatter for XmlFormatter {
         extension_matches(identifier, self.extensions())
     }
 
+    fn provides_content(&self, content: &str) -> bool {
+        let mut chars = content.trim_start().chars();
+        chars.next() == Some('<')
+            && chars
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '?' || c == '!')
+    }
+
     fn extensions(&self) -> &[&str] {
         &["xml"]
     }
@@ -96,6 +104,9 @@ fn parse_xml_text(text: String) -> ConfigValue {
     if let Ok(i) = text.parse::<i64>() {
         return ConfigValue::Integer(i);
     }
+    if let Some(wide) = wide_integer(&text) {
+        return wide;
+    }
     if let Ok(f) = text.parse::<f64>() {
         return ConfigValue::Float(f);
     }
@@ -112,6 +123,7 @@ fn config_value_to_xml(value: &ConfigValue) -> String {
         ConfigValue::Integer(i) => i.to_string(),
         ConfigValue::Float(f) => f.to_string(),
         ConfigValue::String(s) => s.clone(),
+        ConfigValue::Bytes(bytes) => super::encode_bytes(bytes),
         ConfigValue::Array(arr) => arr.iter().map(config_value_to_xml).collect::<String>(),
         ConfigValue::Object(map) => {
             let mut parts = Vec::new();
@@ -137,6 +149,17 @@ mod tests {
         assert!(!f.provides("config.json"));
     }
 
+    #[test]
+    fn test_provides_content() {
+        let f = XmlFormatter;
+        assert!(f.provides_content("<?xml version=\"1.0\"?>\n<config/>"));
+        assert!(f.provides_content("  <config><port>80</port></config>"));
+        assert!(f.provides_content("<!-- settings -->\n<config/>"));
+        assert!(!f.provides_content("< not xml"));
+        assert!(!f.provides_content(r#"{"name": "app"}"#));
+        assert!(!f.provides_content(""));
+    }
+
     #[test]
     fn test_deserialize() {
         let f = XmlFormatter;
@@ -147,6 +170,15 @@ mod tests {
         assert_eq!(result.get("port").unwrap().as_i64(), Some(8080));
     }
 
+    #[test]
+    fn test_deserialize_wide_integer() {
+        let f = XmlFormatter;
+        let result = f
+            .deserialize("<root><id>170141183460469231731687303715884105727</id></root>")
+            .unwrap();
+        assert_eq!(result.get("id").unwrap().as_i128(), Some(i128::MAX));
+    }
+
     #[test]
     fn test_deserialize_attributes() {
         let f = XmlFormatter;
@@ -1,39 +1,72 @@
 //! YAML format support.
 
 use crate::error::{Error, Result};
-use crate::formatter::{extension_matches, Formatter};
+use crate::formatter::{content_lines, extension_matches, Formatter};
 use crate::registry::RegisteredFormatter;
-use crate::value::ConfigValue;
+use crate::value::{wide_integer, ConfigValue};
 use std::collections::HashMap;
 
 inventory::submit! { RegisteredFormatter(&YamlFormatter) }
+inventory::submit! { RegisteredFormatter(&YamlStreamFormatter) }
 
 /// Formatter for YAML files.
 ///
 /// Uses the `yaml-rust2` crate (no serde dependency). Handles multi-document
-/// files by using the first document. Converts non-string keys to strings.
+/// files by using the first document; use `deserialize_all()` or the
+/// `.yamls` extension (`YamlStreamFormatter`) to keep every document.
+/// Converts non-string keys to strings.
 pub struct YamlFormatter;
 
+impl YamlFormatter {
+    /// Parse every `---`-separated document in the content.
+    pub fn deserialize_all(&self, content: &str) -> Result<Vec<ConfigValue>> {
+        use yaml_rust2::YamlLoader;
+
+        let docs = YamlLoader::load_from_str(content).map_err(|e| Error::ParseError {
+            format: "YAML".to_string(),
+            path: std::path::PathBuf::from("<content>"),
+            source: e.to_string().into(),
+        })?;
+
+        Ok(docs.into_iter().map(yaml_to_config_value).collect())
+    }
+}
+
 impl Formatter for YamlFormatter {
     fn provides(&self, identifier: &str) -> bool {
         extension_matches(identifier, self.extensions())
     }
 
+    /// Recognizes a leading `---`, a `- item` sequence entry, or a
+    /// `key: value` mapping entry.
+    fn provides_content(&self, content: &str) -> bool {
+        let line = match content_lines(content, &["#"]).next() {
+            Some(line) => line,
+            None => return false,
+        };
+        if line.starts_with("---") || line == "-" || line.starts_with("- ") {
+            return true;
+        }
+        if line.starts_with(['{', '[', '<']) {
+            return false;
+        }
+        let key = match line.split_once(": ") {
+            Some((key, _)) => key,
+            None => match line.strip_suffix(':') {
+                Some(key) => key,
+                None => return false,
+            },
+        };
+        !key.trim().is_empty() && !key.contains('=')
+    }
+
     fn extensions(&self) -> &[&str] {
         &["yaml", "yml"]
     }
 
     fn deserialize(&self, content: &str) -> Result<ConfigValue> {
-        use yaml_rust2::YamlLoader;
-
-        let docs = YamlLoader::load_from_str(content).map_err(|e| Error::ParseError {
-            format: "YAML".to_string(),
-            path: std::path::PathBuf::from("<content>"),
-            source: e.to_string().into(),
-        })?;
-
-        match docs.into_iter().next() {
-            Some(doc) => Ok(yaml_to_config_value(doc)),
+        match self.deserialize_all(content)?.into_iter().next() {
+            Some(doc) => Ok(doc),
             None => Ok(ConfigValue::Object(HashMap::new())),
         }
     }
@@ -47,6 +80,53 @@ impl Formatter for YamlFormatter {
     }
 }
 
+/// Formatter for multi-document YAML streams (`.yamls` files).
+///
+/// Every `---`-separated document is kept, and the result is an Array with
+/// one element per document. Serializing writes each element of an Array as
+/// its own document.
+pub struct YamlStreamFormatter;
+
+impl Formatter for YamlStreamFormatter {
+    fn provides(&self, identifier: &str) -> bool {
+        extension_matches(identifier, self.extensions())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["yamls"]
+    }
+
+    fn deserialize(&self, content: &str) -> Result<ConfigValue> {
+        YamlFormatter
+            .deserialize_all(content)
+            .map(ConfigValue::Array)
+    }
+
+    fn serialize(&self, value: &ConfigValue) -> Result<String> {
+        let docs = match value {
+            ConfigValue::Array(docs) => docs.as_slice(),
+            other => core::slice::from_ref(other),
+        };
+
+        Ok(docs
+            .iter()
+            .map(|doc| {
+                let body = config_value_to_yaml(doc, 0);
+                if body.starts_with('\n') {
+                    format!("---{}", body)
+                } else {
+                    format!("--- {}", body)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn name(&self) -> &str {
+        "yaml-stream"
+    }
+}
+
 fn yaml_to_config_value(yaml: yaml_rust2::Yaml) -> ConfigValue {
     use yaml_rust2::Yaml;
 
@@ -54,10 +134,12 @@ fn yaml_to_config_value(yaml: yaml_rust2::Yaml) -> ConfigValue {
         Yaml::Null | Yaml::BadValue => ConfigValue::Null,
         Yaml::Boolean(b) => ConfigValue::Bool(b),
         Yaml::Integer(i) => ConfigValue::Integer(i),
-        Yaml::Real(s) => s
-            .parse::<f64>()
-            .map(ConfigValue::Float)
-            .unwrap_or(ConfigValue::String(s)),
+        // yaml-rust2 reads integers that overflow i64 as reals
+        Yaml::Real(s) => wide_integer(&s).unwrap_or_else(|| {
+            s.parse::<f64>()
+                .map(ConfigValue::Float)
+                .unwrap_or(ConfigValue::String(s))
+        }),
         Yaml::String(s) => ConfigValue::String(s),
         Yaml::Array(arr) => ConfigValue::Array(arr.into_iter().map(yaml_to_config_value).collect()),
         Yaml::Hash(map) => {
@@ -90,6 +172,7 @@ fn config_value_to_yaml(value: &ConfigValue, indent: usize) -> String {
         ConfigValue::Integer(i) => i.to_string(),
         ConfigValue::Float(f) => f.to_string(),
         ConfigValue::String(s) => format!("\"{}\"", super::escape_quotes(s)),
+        ConfigValue::Bytes(bytes) => format!("\"{}\"", super::encode_bytes(bytes)),
         ConfigValue::Array(arr) => {
             if arr.is_empty() {
                 return "[]".to_string();
@@ -134,6 +217,19 @@ mod tests {
         assert!(!f.provides("config.json"));
     }
 
+    #[test]
+    fn test_provides_content() {
+        let f = YamlFormatter;
+        assert!(f.provides_content("name: app\nport: 8080"));
+        assert!(f.provides_content("# comment\nserver:\n  port: 8080"));
+        assert!(f.provides_content("---\nname: app"));
+        assert!(f.provides_content("- one\n- two"));
+        assert!(!f.provides_content(r#"{"name": "app"}"#));
+        assert!(!f.provides_content("name = \"app\""));
+        assert!(!f.provides_content("just some words"));
+        assert!(!f.provides_content(""));
+    }
+
     #[test]
     fn test_deserialize() {
         let f = YamlFormatter;
@@ -142,6 +238,22 @@ mod tests {
         assert_eq!(result.get("port").unwrap().as_i64(), Some(8080));
     }
 
+    #[test]
+    fn test_deserialize_wide_integers() {
+        let f = YamlFormatter;
+        let result = f
+            .deserialize("max: 170141183460469231731687303715884105727\nbig: 18446744073709551615")
+            .unwrap();
+        assert_eq!(
+            result.get("max").unwrap().as_i128(),
+            Some(170141183460469231731687303715884105727)
+        );
+        assert_eq!(result.get("big").unwrap().as_u128(), Some(u64::MAX.into()));
+
+        let config = crate::Config::new(result);
+        assert_eq!(config.get::<u64>("big").unwrap(), u64::MAX);
+    }
+
     #[test]
     fn test_deserialize_empty() {
         let f = YamlFormatter;
@@ -266,6 +378,51 @@ mod tests {
         assert_eq!(result.get("3.15").unwrap().as_str(), Some("float_key"));
     }
 
+    const THREE_DOCS: &str =
+        "kind: Service\nname: web\n---\nkind: Deployment\nreplicas: 3\n---\n- a\n- b\n";
+
+    #[test]
+    fn test_deserialize_all_documents() {
+        let docs = YamlFormatter.deserialize_all(THREE_DOCS).unwrap();
+        assert_eq!(docs.len(), 3);
+        assert_eq!(docs[0].get("kind").unwrap().as_str(), Some("Service"));
+        assert_eq!(docs[1].get("replicas").unwrap().as_i64(), Some(3));
+        assert_eq!(docs[2].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_deserialize_keeps_first_document() {
+        let result = YamlFormatter.deserialize(THREE_DOCS).unwrap();
+        assert_eq!(result.get("kind").unwrap().as_str(), Some("Service"));
+    }
+
+    #[test]
+    fn test_stream_formatter() {
+        let f = YamlStreamFormatter;
+        assert!(f.provides("manifests.yamls"));
+        assert!(!f.provides("config.yaml"));
+
+        let result = f.deserialize(THREE_DOCS).unwrap();
+        let docs = result.as_array().unwrap();
+        assert_eq!(docs.len(), 3);
+        assert_eq!(docs[1].get("kind").unwrap().as_str(), Some("Deployment"));
+
+        let restored = f.deserialize(&f.serialize(&result).unwrap()).unwrap();
+        assert_eq!(restored, result);
+    }
+
+    #[tokio::test]
+    async fn test_load_multi_document_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifests.yamls");
+        std::fs::write(&path, THREE_DOCS).unwrap();
+
+        let config = crate::Config::load_from_path(&path).await.unwrap();
+        let docs = config.data().as_array().unwrap();
+        assert_eq!(docs.len(), 3);
+        assert_eq!(docs[0].get("name").unwrap().as_str(), Some("web"));
+    }
+
     #[test]
     fn test_deserialize_array() {
         let f = YamlFormatter;
@@ -52,6 +52,7 @@ pub mod builder;
 pub mod config;
 #[cfg(feature = "std")]
 pub mod discovery;
+mod duration;
 pub mod error;
 #[cfg(feature = "std")]
 pub mod events;
@@ -70,17 +71,24 @@ pub mod watch;
 
 // Core types (always available)
 pub use error::{Error, Result};
-pub use value::{ConfigValue, FromValue};
+pub use value::{
+    Change, ConfigValue, FromValue, Maybe, NumberPolicy, OneOrMany, StrNum, ValueKind, WithExtras,
+};
 pub use visitor::{SeqAccess, ValueVisitor};
 
 // std-dependent types
 #[cfg(feature = "std")]
 pub use builder::ConfigBuilder;
 #[cfg(feature = "std")]
-pub use config::Config;
+pub use config::{Config, FrozenConfig};
 #[cfg(feature = "std")]
 #[allow(deprecated)]
-pub use source::{EnvSource, FileSource, LayeredSource, MemorySource, Source};
+pub use source::{
+    CachingSource, ConflictPolicy, EnvSource, FallbackSource, FileSource, KeyStyle, LayeredSource,
+    MemorySource, Source, TransformSource,
+};
+#[cfg(feature = "std")]
+pub use watch::WatchedConfig;
 
 // Re-export the derive macro when the feature is enabled
 #[cfg(feature = "derive")]
@@ -122,8 +130,9 @@ pub async fn load(identifier: &str) -> Result<Config> {
 
     Ok(Config::with_metadata(
         result.data,
-        result.source,
-        loader.name().to_string(),
+        Some(result.source),
+        Some(loader.name().to_string()),
+        result.format,
     ))
 }
 
@@ -163,3 +172,45 @@ pub async fn watch(identifier: &str) -> Result<tokio::sync::mpsc::Receiver<Confi
         .await?
         .ok_or(Error::WatchNotSupported(identifier.to_string()))
 }
+
+/// Watch a configuration source, polling when it cannot push changes.
+///
+/// Behaves like `watch()` when the resolved loader supports watching.
+/// Otherwise, falls back to `watch::poll_loader()`, which reloads the
+/// source every `interval` and yields a new `Config` whenever its data
+/// changes.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[cfg(feature = "std")]
+/// # {
+/// use prefer::watch_with_interval;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> prefer::Result<()> {
+///     let mut receiver =
+///         watch_with_interval("postgres://localhost/app", Duration::from_secs(30)).await?;
+///
+///     while let Some(config) = receiver.recv().await {
+///         println!("Configuration updated!");
+///     }
+///
+///     Ok(())
+/// }
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub async fn watch_with_interval(
+    identifier: &str,
+    interval: std::time::Duration,
+) -> Result<tokio::sync::mpsc::Receiver<Config>> {
+    let loader =
+        registry::find_loader(identifier).ok_or(Error::NoLoaderFound(identifier.to_string()))?;
+
+    match loader.watch(identifier).await? {
+        Some(receiver) => Ok(receiver),
+        None => watch::poll_loader(identifier, interval).await,
+    }
+}
@@ -6,15 +6,28 @@
 use crate::config::Config;
 use crate::discovery;
 use crate::error::{Error, Result};
-use crate::formatter::Formatter;
+use crate::formatter::{self, Formatter};
 use crate::loader::{LoadResult, Loader};
 use crate::registry::RegisteredLoader;
+use crate::source::merge_values;
+use crate::value::{ConfigValue, FromValue};
 use crate::watch as watch_mod;
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use tokio::sync::mpsc;
 
-inventory::submit! { RegisteredLoader(&FileLoader) }
+static FILE_LOADER: FileLoader = FileLoader::new();
+
+inventory::submit! { RegisteredLoader(&FILE_LOADER) }
+
+/// The key listing files to include when includes are enabled.
+const INCLUDE_KEY: &str = "include";
+
+/// Parsed file data and the name of the formatter that parsed it.
+type ParsedFile = (ConfigValue, String);
 
 /// Loader for file-based configuration sources.
 ///
@@ -26,17 +39,117 @@ inventory::submit! { RegisteredLoader(&FileLoader) }
 ///
 /// File discovery and extension search logic is delegated to the existing
 /// `discovery` module.
-pub struct FileLoader;
+///
+/// # Includes
+///
+/// With `with_includes(true)`, a top-level `include` key naming a file (or
+/// an array of files) pulls those files in. Paths are relative to the
+/// including file, and included files have lower precedence than the
+/// including file's own keys; later entries override earlier ones. Includes
+/// may nest, and a file that includes itself, directly or indirectly, is a
+/// `CyclicReference` error listing the files in the cycle.
+///
+/// ```json
+/// { "include": ["base.json", "secrets.toml"], "port": 8080 }
+/// ```
+///
+/// # Fragments
+///
+/// An identifier may end in `#` and a dotted path, as in
+/// `config.toml#services.api`, to load only that section of the file. The
+/// path uses the same syntax as `ConfigValue::path_exists()`, and a missing
+/// section is a `SourceError`. Watching a fragment yields only that section.
+pub struct FileLoader {
+    includes: bool,
+}
 
 impl FileLoader {
-    pub fn new() -> Self {
-        Self
+    pub const fn new() -> Self {
+        Self { includes: false }
+    }
+
+    /// Enable or disable processing of `include` directives.
+    ///
+    /// Disabled by default, in which case `include` is an ordinary key.
+    pub fn with_includes(mut self, enabled: bool) -> Self {
+        self.includes = enabled;
+        self
+    }
+
+    /// Load a file and, recursively, everything it includes.
+    ///
+    /// `stack` holds the canonical paths of the files currently being
+    /// included, to detect cycles. The format returned is the one `path`
+    /// itself was parsed with.
+    fn load_with_includes<'a>(
+        &'a self,
+        path: PathBuf,
+        formatters: &'a [&'a dyn Formatter],
+        stack: &'a mut Vec<PathBuf>,
+    ) -> Pin<Box<dyn Future<Output = Result<ParsedFile>> + Send + 'a>> {
+        Box::pin(async move {
+            let path = tokio::fs::canonicalize(&path)
+                .await
+                .map_err(|e| read_error(&path, e))?;
+            if let Some(start) = stack.iter().position(|p| *p == path) {
+                let cycle = stack[start..]
+                    .iter()
+                    .chain([&path])
+                    .map(|p| p.display().to_string())
+                    .collect();
+                return Err(Error::CyclicReference(cycle));
+            }
+
+            let (mut data, format) = parse_file_with_format(&path, formatters).await?;
+            let includes = take_includes(&mut data)?;
+            if includes.is_empty() {
+                return Ok((data, format));
+            }
+
+            let base_dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+            stack.push(path);
+
+            let mut merged = ConfigValue::Object(HashMap::new());
+            for include in includes {
+                let (included, _) = self
+                    .load_with_includes(base_dir.join(include), formatters, stack)
+                    .await?;
+                merge_values(&mut merged, included);
+            }
+
+            stack.pop();
+            merge_values(&mut merged, data);
+            Ok((merged, format))
+        })
     }
 
     async fn locate(&self, identifier: &str) -> Result<PathBuf> {
         let stripped = identifier.strip_prefix("file://").unwrap_or(identifier);
         discovery::find_config_file(stripped).await
     }
+
+    /// Locate a `file#section.path` identifier's file and split off the
+    /// fragment.
+    ///
+    /// The whole identifier is tried as a file first, so paths containing
+    /// `#` keep working. Only if it isn't found is the part after the last
+    /// `#` taken as a fragment.
+    async fn locate_with_fragment<'a>(
+        &self,
+        identifier: &'a str,
+    ) -> Result<(PathBuf, Option<&'a str>)> {
+        let err = match self.locate(identifier).await {
+            Ok(path) => return Ok((path, None)),
+            Err(err) => err,
+        };
+        match identifier.rsplit_once('#') {
+            Some((file, fragment)) => match self.locate(file).await {
+                Ok(path) => Ok((path, Some(fragment))),
+                Err(_) => Err(err),
+            },
+            None => Err(err),
+        }
+    }
 }
 
 impl Default for FileLoader {
@@ -62,27 +175,159 @@ impl Loader for FileLoader {
     }
 
     async fn load(&self, identifier: &str, formatters: &[&dyn Formatter]) -> Result<LoadResult> {
-        let path = self.locate(identifier).await?;
-        let content = tokio::fs::read_to_string(&path).await?;
+        let (path, fragment) = self.locate_with_fragment(identifier).await?;
         let source = path.to_string_lossy().to_string();
 
-        let fmt = formatters
-            .iter()
-            .find(|f| f.provides(&source))
-            .ok_or_else(|| Error::UnsupportedFormat(path))?;
-        let data = fmt.deserialize(&content)?;
+        let (mut data, format) = if self.includes {
+            self.load_with_includes(path, formatters, &mut Vec::new())
+                .await?
+        } else {
+            parse_file_with_format(&path, formatters).await?
+        };
+        if let Some(fragment) = fragment {
+            data = select_fragment(&data, fragment, &source)?;
+        }
 
-        Ok(LoadResult { source, data })
+        Ok(LoadResult::new(source, data).with_format(format))
     }
 
     fn name(&self) -> &str {
         "file"
     }
 
+    fn can_watch(&self) -> bool {
+        true
+    }
+
     async fn watch(&self, identifier: &str) -> Result<Option<mpsc::Receiver<Config>>> {
-        let path = self.locate(identifier).await?;
+        let (path, fragment) = self.locate_with_fragment(identifier).await?;
         let rx = watch_mod::watch_path(path).await?;
-        Ok(Some(rx))
+        match fragment {
+            Some(fragment) => Ok(Some(narrow_updates(rx, fragment.to_string()))),
+            None => Ok(Some(rx)),
+        }
+    }
+}
+
+/// Narrow parsed file data to the section named by a fragment.
+fn select_fragment(data: &ConfigValue, fragment: &str, source: &str) -> Result<ConfigValue> {
+    data.find_path(fragment)
+        .cloned()
+        .ok_or_else(|| Error::SourceError {
+            source_name: source.to_string(),
+            source: format!("fragment '{fragment}' not found").into(),
+        })
+}
+
+/// Forward watch updates, keeping only the section named by a fragment.
+///
+/// Updates where the section is missing are skipped, like failed reloads.
+fn narrow_updates(mut rx: mpsc::Receiver<Config>, fragment: String) -> mpsc::Receiver<Config> {
+    let (tx, narrowed) = mpsc::channel(32);
+    tokio::spawn(async move {
+        while let Some(config) = rx.recv().await {
+            let Some(section) = config.data().find_path(&fragment) else {
+                continue;
+            };
+            let mut update = config.clone();
+            *update.data_mut() = section.clone();
+            if tx.send(update).await.is_err() {
+                break;
+            }
+        }
+    });
+    narrowed
+}
+
+/// Read a text file, honoring a leading byte order mark.
+///
+/// Failures to open the file are reported with `read_error()`.
+pub(crate) async fn read_text_file(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|e| read_error(path, e))?;
+    Ok(decode_text(bytes)?)
+}
+
+/// Turn an I/O error from opening `path` into the most specific `Error`.
+///
+/// A missing file (including a dangling symlink) becomes `FileNotFound`,
+/// an unreadable one `PermissionDenied`, and a directory `IsADirectory`.
+/// Anything else stays an `IoError`.
+pub(crate) fn read_error(path: &Path, err: std::io::Error) -> Error {
+    match err.kind() {
+        std::io::ErrorKind::NotFound => Error::FileNotFound(path.display().to_string()),
+        std::io::ErrorKind::PermissionDenied => Error::PermissionDenied(path.to_path_buf()),
+        std::io::ErrorKind::IsADirectory => Error::IsADirectory(path.to_path_buf()),
+        _ => Error::IoError(err),
+    }
+}
+
+/// Decode file contents as text.
+///
+/// A leading UTF-8 BOM is dropped, and content starting with a UTF-16 LE or
+/// BE BOM (as saved by some Windows editors) is transcoded to UTF-8.
+/// Anything else must be valid UTF-8.
+fn decode_text(bytes: Vec<u8>) -> std::io::Result<String> {
+    match bytes.as_slice() {
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+        _ => {
+            let mut text = String::from_utf8(bytes)
+                .map_err(|_| invalid_data("stream did not contain valid UTF-8"))?;
+            if text.starts_with('\u{FEFF}') {
+                text.drain(..'\u{FEFF}'.len_utf8());
+            }
+            Ok(text)
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> std::io::Result<String> {
+    let pairs = bytes.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(invalid_data("stream did not contain valid UTF-16"));
+    }
+    let units = pairs.map(|pair| unit([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .collect::<std::result::Result<String, _>>()
+        .map_err(|_| invalid_data("stream did not contain valid UTF-16"))
+}
+
+fn invalid_data(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+}
+
+/// Read and parse a single file, choosing a formatter by extension.
+pub(crate) async fn parse_file(path: &Path, formatters: &[&dyn Formatter]) -> Result<ConfigValue> {
+    parse_file_with_format(path, formatters)
+        .await
+        .map(|(data, _)| data)
+}
+
+/// Like `parse_file()`, also returning the name of the formatter used.
+async fn parse_file_with_format(path: &Path, formatters: &[&dyn Formatter]) -> Result<ParsedFile> {
+    let content = read_text_file(path).await?;
+    let source = path.to_string_lossy();
+
+    // Fall back to sniffing the content when the extension is unknown
+    let (fmt, data) = match formatters.iter().find(|f| f.provides(&source)) {
+        Some(fmt) => (*fmt, fmt.deserialize(&content)?),
+        None => formatter::sniff(&content)?,
+    };
+    Ok((data, fmt.name().to_string()))
+}
+
+/// Remove the `include` key from a parsed file, returning the listed paths.
+fn take_includes(data: &mut ConfigValue) -> Result<Vec<String>> {
+    let Some(include) = data.as_object_mut().and_then(|obj| obj.remove(INCLUDE_KEY)) else {
+        return Ok(Vec::new());
+    };
+
+    match include {
+        ConfigValue::String(path) => Ok(vec![path]),
+        other => Vec::<String>::from_value(&other).map_err(|e| e.with_key(INCLUDE_KEY)),
     }
 }
 
@@ -140,8 +385,346 @@ mod tests {
         let result = loader.load("testapp", &formatters).await.unwrap();
 
         assert!(result.source.ends_with("testapp.json"));
+        assert_eq!(result.format.as_deref(), Some("json"));
         assert_eq!(result.data.get("host").unwrap().as_str(), Some("localhost"));
 
         std::env::set_current_dir(original_dir).unwrap();
     }
+
+    #[test]
+    fn test_decode_text_strips_utf8_bom() {
+        let text = decode_text(b"\xEF\xBB\xBF{\"a\": 1}".to_vec()).unwrap();
+        assert_eq!(text, r#"{"a": 1}"#);
+        assert_eq!(decode_text(b"plain".to_vec()).unwrap(), "plain");
+    }
+
+    #[test]
+    fn test_decode_text_transcodes_utf16() {
+        let le: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("a = 1".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        assert_eq!(decode_text(le).unwrap(), "a = 1");
+
+        let be: Vec<u8> = [0xFE, 0xFF]
+            .into_iter()
+            .chain("a = 1".encode_utf16().flat_map(u16::to_be_bytes))
+            .collect();
+        assert_eq!(decode_text(be).unwrap(), "a = 1");
+    }
+
+    #[test]
+    fn test_decode_text_rejects_invalid() {
+        let err = decode_text(vec![0xC3, 0x28]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(decode_text(vec![0xFF, 0xFE, 0x61]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_file_with_bom() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("bom.json");
+        std::fs::write(&path, "\u{FEFF}{\"host\": \"localhost\"}").unwrap();
+
+        let formatters = registry::collect_formatters();
+        let result = FileLoader::new()
+            .load(path.to_str().unwrap(), &formatters)
+            .await
+            .unwrap();
+
+        assert_eq!(result.data.get("host").unwrap().as_str(), Some("localhost"));
+    }
+
+    #[tokio::test]
+    async fn test_load_path_containing_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("build#2");
+        std::fs::create_dir(&dir).unwrap();
+        let path = dir.join("app.toml");
+        std::fs::write(&path, "[services.api]\nport = 8080\n").unwrap();
+
+        let formatters = registry::collect_formatters();
+        let loader = FileLoader::new();
+
+        let identifier = path.display().to_string();
+        let result = loader.load(&identifier, &formatters).await.unwrap();
+        assert_eq!(result.source, identifier);
+        assert!(result.data.get("services").is_some());
+
+        // A fragment still applies after a path containing `#`
+        let with_fragment = format!("{}#services.api", path.display());
+        let result = loader.load(&with_fragment, &formatters).await.unwrap();
+        assert_eq!(result.data.get("port").unwrap().as_i64(), Some(8080));
+    }
+
+    #[tokio::test]
+    async fn test_load_reports_sniffed_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("app.conf");
+        std::fs::write(&path, "name: app\nport: 8080\n").unwrap();
+
+        let formatters = registry::collect_formatters();
+        let result = FileLoader::new()
+            .load(path.to_str().unwrap(), &formatters)
+            .await
+            .unwrap();
+        assert_eq!(result.format.as_deref(), Some("yaml"));
+        assert_eq!(result.data.get("port").unwrap().as_i64(), Some(8080));
+    }
+
+    #[tokio::test]
+    async fn test_load_fragment() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("monorepo.toml");
+        std::fs::write(
+            &path,
+            "[services.api]\nport = 8080\n\n[services.worker]\nthreads = 4\n",
+        )
+        .unwrap();
+
+        let formatters = registry::collect_formatters();
+        let identifier = format!("{}#services.api", path.display());
+        let result = FileLoader::new()
+            .load(&identifier, &formatters)
+            .await
+            .unwrap();
+
+        assert_eq!(result.data.get("port").unwrap().as_i64(), Some(8080));
+        assert!(result.data.get("threads").is_none());
+        assert!(result.source.ends_with("monorepo.toml"));
+    }
+
+    #[tokio::test]
+    async fn test_load_missing_fragment_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("monorepo.toml");
+        std::fs::write(&path, "[services.api]\nport = 8080\n").unwrap();
+
+        let formatters = registry::collect_formatters();
+        let identifier = format!("{}#services.billing", path.display());
+        let Err(err) = FileLoader::new().load(&identifier, &formatters).await else {
+            panic!("expected a missing fragment error");
+        };
+
+        assert!(matches!(err, Error::SourceError { .. }));
+        assert!(
+            err.to_string()
+                .contains("fragment 'services.billing' not found"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_with_includes() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("base.json"),
+            r#"{"host": "localhost", "port": 5432, "db": {"pool": 5}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("secrets.toml"),
+            "password = \"hunter2\"\n",
+        )
+        .unwrap();
+        let main_path = temp_dir.path().join("main.json");
+        std::fs::write(
+            &main_path,
+            r#"{"include": ["base.json", "secrets.toml"], "port": 8080, "db": {"name": "app"}}"#,
+        )
+        .unwrap();
+
+        let formatters = registry::collect_formatters();
+        let loader = FileLoader::new().with_includes(true);
+        let result = loader
+            .load(main_path.to_str().unwrap(), &formatters)
+            .await
+            .unwrap();
+
+        // The format is the including file's own
+        assert_eq!(result.format.as_deref(), Some("json"));
+        let data = result.data;
+        assert!(data.get("include").is_none());
+        assert_eq!(data.get("host").unwrap().as_str(), Some("localhost"));
+        assert_eq!(data.get("password").unwrap().as_str(), Some("hunter2"));
+        // The including file's own keys win
+        assert_eq!(data.get("port").unwrap().as_i64(), Some(8080));
+        let db = data.get("db").unwrap();
+        assert_eq!(db.get("pool").unwrap().as_i64(), Some(5));
+        assert_eq!(db.get("name").unwrap().as_str(), Some("app"));
+    }
+
+    #[tokio::test]
+    async fn test_load_include_cycle_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("a.json"),
+            r#"{"include": "b.json", "a": 1}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("b.json"),
+            r#"{"include": "a.json", "b": 2}"#,
+        )
+        .unwrap();
+
+        let formatters = registry::collect_formatters();
+        let loader = FileLoader::new().with_includes(true);
+        let path = temp_dir.path().join("a.json");
+        let Err(Error::CyclicReference(cycle)) =
+            loader.load(path.to_str().unwrap(), &formatters).await
+        else {
+            panic!("expected CyclicReference");
+        };
+
+        let names: Vec<_> = cycle
+            .iter()
+            .map(|p| Path::new(p).file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, ["a.json", "b.json", "a.json"]);
+    }
+
+    #[tokio::test]
+    async fn test_load_three_file_include_cycle_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        // main.json is outside the cycle, so it is not part of the chain
+        for (name, include) in [
+            ("main.json", "x.json"),
+            ("x.json", "y.json"),
+            ("y.json", "z.json"),
+            ("z.json", "x.json"),
+        ] {
+            std::fs::write(
+                temp_dir.path().join(name),
+                format!(r#"{{"include": "{}"}}"#, include),
+            )
+            .unwrap();
+        }
+
+        let formatters = registry::collect_formatters();
+        let loader = FileLoader::new().with_includes(true);
+        let path = temp_dir.path().join("main.json");
+        let Err(Error::CyclicReference(cycle)) =
+            loader.load(path.to_str().unwrap(), &formatters).await
+        else {
+            panic!("expected CyclicReference");
+        };
+
+        let names: Vec<_> = cycle
+            .iter()
+            .map(|p| Path::new(p).file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, ["x.json", "y.json", "z.json", "x.json"]);
+    }
+
+    #[tokio::test]
+    async fn test_load_includes_disabled_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("main.json");
+        std::fs::write(&path, r#"{"include": "missing.json"}"#).unwrap();
+
+        let formatters = registry::collect_formatters();
+        let result = FileLoader::new()
+            .load(path.to_str().unwrap(), &formatters)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.data.get("include").unwrap().as_str(),
+            Some("missing.json")
+        );
+    }
+
+    #[allow(deprecated)]
+    #[tokio::test]
+    async fn test_load_directory_is_distinct_error() {
+        use crate::source::{FileSource, Source};
+
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("conf.d");
+        std::fs::create_dir(&dir).unwrap();
+        let with_extension = temp_dir.path().join("app.json");
+        std::fs::create_dir(&with_extension).unwrap();
+
+        let formatters = registry::collect_formatters();
+        for path in [&dir, &with_extension] {
+            let result = FileLoader::new()
+                .load(path.to_str().unwrap(), &formatters)
+                .await;
+            assert!(matches!(result, Err(Error::IsADirectory(p)) if p == *path));
+        }
+
+        let err = FileSource::new(&with_extension).load().await.unwrap_err();
+        assert!(matches!(err, Error::IsADirectory(p) if p == with_extension));
+
+        let err = Config::load_from_path(&with_extension).await.unwrap_err();
+        assert!(matches!(err, Error::IsADirectory(ref p) if *p == with_extension));
+        assert!(err.to_string().contains("is a directory"));
+    }
+
+    #[allow(deprecated)]
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_load_permission_denied_is_distinct_error() {
+        use crate::source::{FileSource, Source};
+
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("secret.json");
+        std::fs::write(&path, "{}").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o000)).unwrap();
+
+        // Privileged users (e.g. root in a container) can read it anyway
+        if std::fs::read(&path).is_ok() {
+            return;
+        }
+
+        let formatters = registry::collect_formatters();
+        let result = FileLoader::new()
+            .load(path.to_str().unwrap(), &formatters)
+            .await;
+        assert!(matches!(result, Err(Error::PermissionDenied(p)) if p == path));
+
+        let err = FileSource::new(&path).load().await.unwrap_err();
+        assert!(matches!(err, Error::PermissionDenied(p) if p == path));
+
+        let err = Config::load_from_path(&path).await.unwrap_err();
+        assert!(matches!(err, Error::PermissionDenied(ref p) if *p == path));
+        assert!(err.to_string().contains("Permission denied"));
+    }
+
+    #[allow(deprecated)]
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_load_dangling_symlink_is_not_found() {
+        use crate::source::{FileSource, Source};
+
+        let temp_dir = TempDir::new().unwrap();
+        let link = temp_dir.path().join("app.json");
+        std::os::unix::fs::symlink(temp_dir.path().join("gone.json"), &link).unwrap();
+
+        let err = FileSource::new(&link).load().await.unwrap_err();
+        assert!(matches!(err, Error::FileNotFound(p) if p.ends_with("app.json")));
+
+        let err = Config::load_from_path(&link).await.unwrap_err();
+        assert!(matches!(err, Error::FileNotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_load_unknown_extension_sniffs_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("app.conf");
+        std::fs::write(&file_path, "[server]\nport = 8080\n").unwrap();
+
+        let formatters = registry::collect_formatters();
+        let loader = FileLoader::new();
+        let result = loader
+            .load(file_path.to_str().unwrap(), &formatters)
+            .await
+            .unwrap();
+
+        let server = result.data.get("server").unwrap();
+        assert_eq!(server.get("port").unwrap().as_i64(), Some(8080));
+    }
 }
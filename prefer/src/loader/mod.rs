@@ -23,12 +23,36 @@ use tokio::sync::mpsc;
 /// Contains parsed configuration data ready for use. Loaders are responsible
 /// for parsing their source format (using the provided formatters, direct
 /// conversion, etc.) before returning.
+///
+/// Build one with `LoadResult::new()` rather than a struct literal, so
+/// that fields added later don't break your loader.
 pub struct LoadResult {
     /// The resolved source identifier (e.g., "/home/user/.config/myapp.toml").
     pub source: String,
 
     /// The parsed configuration data.
     pub data: ConfigValue,
+
+    /// The name of the formatter the data was parsed with (see
+    /// `Formatter::name()`), or `None` if it wasn't parsed from text.
+    pub format: Option<String>,
+}
+
+impl LoadResult {
+    /// Create a load result for data that wasn't parsed with a formatter.
+    pub fn new(source: impl Into<String>, data: ConfigValue) -> Self {
+        Self {
+            source: source.into(),
+            data,
+            format: None,
+        }
+    }
+
+    /// Record the name of the formatter the data was parsed with.
+    pub fn with_format(mut self, format: impl Into<String>) -> Self {
+        self.format = Some(format.into());
+        self
+    }
 }
 
 /// A source of configuration data that can be discovered via the registry.
@@ -64,10 +88,7 @@ pub struct LoadResult {
 ///         _formatters: &[&dyn Formatter],
 ///     ) -> Result<LoadResult> {
 ///         let data = fetch_and_parse(identifier).await?;
-///         Ok(LoadResult {
-///             source: identifier.to_string(),
-///             data,
-///         })
+///         Ok(LoadResult::new(identifier, data))
 ///     }
 ///
 ///     fn name(&self) -> &str {
@@ -95,6 +116,25 @@ pub trait Loader: Send + Sync + 'static {
     /// Human-readable name for error messages.
     fn name(&self) -> &str;
 
+    /// Priority used to choose between loaders that provide the same identifier.
+    ///
+    /// The registry picks the highest-priority loader whose `provides()`
+    /// returns `true`. Built-in loaders use the default of `0`, so a loader
+    /// with a positive priority can intercept identifiers that would
+    /// otherwise go to `FileLoader`.
+    fn priority(&self) -> i32 {
+        0
+    }
+
+    /// Whether this loader implements `watch()`.
+    ///
+    /// Loaders that override `watch()` should also override this to return
+    /// `true`, so callers can decide up front whether to rely on push
+    /// updates or poll instead.
+    fn can_watch(&self) -> bool {
+        false
+    }
+
     /// Watch the identified source for changes.
     ///
     /// Returns `None` if this loader does not support watching.
@@ -6,9 +6,21 @@
 //!
 //! External crates (e.g., `prefer_db`) can register their own loaders and
 //! formatters simply by depending on `prefer` and calling `inventory::submit!`.
+//!
+//! Loaders and formatters that are only known at runtime (for example, a
+//! loader whose connection parameters come from command-line arguments) can
+//! be added with `register_loader()` and `register_formatter()`.
+//!
+//! Loaders are checked in order of `Loader::priority()`, highest first. Among
+//! loaders with equal priority, runtime registrations come before
+//! inventory-collected ones, and ties are then broken by name so that the
+//! result does not depend on link order. Formatters are ordered the same way
+//! using `Formatter::priority()`, so registering a formatter for an existing
+//! extension (at runtime, or with a higher priority) overrides the built-in.
 
 use crate::formatter::Formatter;
 use crate::loader::Loader;
+use std::sync::RwLock;
 
 /// Wrapper for registering a `Loader` with the inventory.
 ///
@@ -23,37 +35,148 @@ pub struct RegisteredFormatter(pub &'static dyn Formatter);
 inventory::collect!(RegisteredLoader);
 inventory::collect!(RegisteredFormatter);
 
-/// Collect all registered formatters from the inventory.
+static RUNTIME_LOADERS: RwLock<Vec<&'static dyn Loader>> = RwLock::new(Vec::new());
+static RUNTIME_FORMATTERS: RwLock<Vec<&'static dyn Formatter>> = RwLock::new(Vec::new());
+
+/// Register a loader at runtime.
+///
+/// The loader is kept for the rest of the process. It is checked before
+/// any loaders of the same priority registered with `inventory::submit!`.
+pub fn register_loader(loader: Box<dyn Loader>) {
+    RUNTIME_LOADERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(Box::leak(loader));
+}
+
+/// Register a formatter at runtime.
+///
+/// The formatter is kept for the rest of the process. It is checked before
+/// any formatters of the same priority registered with `inventory::submit!`.
+pub fn register_formatter(formatter: Box<dyn Formatter>) {
+    RUNTIME_FORMATTERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(Box::leak(formatter));
+}
+
+/// All loaders in lookup order.
+///
+/// Sorted by priority (highest first), then runtime registrations before
+/// the inventory, then by name.
+fn collect_loaders() -> Vec<&'static dyn Loader> {
+    let runtime = RUNTIME_LOADERS.read().unwrap_or_else(|e| e.into_inner());
+    let mut loaders: Vec<(bool, &'static dyn Loader)> = runtime
+        .iter()
+        .map(|loader| (false, *loader))
+        .chain(
+            inventory::iter::<RegisteredLoader>
+                .into_iter()
+                .map(|r| (true, r.0)),
+        )
+        .collect();
+
+    loaders.sort_by(|(a_inventory, a), (b_inventory, b)| {
+        b.priority()
+            .cmp(&a.priority())
+            .then(a_inventory.cmp(b_inventory))
+            .then_with(|| a.name().cmp(b.name()))
+    });
+    loaders.into_iter().map(|(_, loader)| loader).collect()
+}
+
+/// Collect all registered formatters in lookup order.
+///
+/// Sorted by priority (highest first), then runtime registrations before
+/// the inventory, then by name.
 pub fn collect_formatters() -> Vec<&'static dyn Formatter> {
-    inventory::iter::<RegisteredFormatter>
+    let runtime = RUNTIME_FORMATTERS.read().unwrap_or_else(|e| e.into_inner());
+    let mut formatters: Vec<(bool, &'static dyn Formatter)> = runtime
+        .iter()
+        .map(|formatter| (false, *formatter))
+        .chain(
+            inventory::iter::<RegisteredFormatter>
+                .into_iter()
+                .map(|r| (true, r.0)),
+        )
+        .collect();
+
+    formatters.sort_by(|(a_inventory, a), (b_inventory, b)| {
+        b.priority()
+            .cmp(&a.priority())
+            .then(a_inventory.cmp(b_inventory))
+            .then_with(|| a.name().cmp(b.name()))
+    });
+    formatters
         .into_iter()
-        .map(|r| r.0)
+        .map(|(_, formatter)| formatter)
+        .collect()
+}
+
+/// All formatters that handle the given extension (without the leading
+/// dot), in lookup order.
+///
+/// The first entry is the one `find_formatter()` would choose.
+pub fn formatters_for_extension(ext: &str) -> Vec<&'static dyn Formatter> {
+    collect_formatters()
+        .into_iter()
+        .filter(|formatter| formatter.extensions().contains(&ext))
+        .collect()
+}
+
+/// Names of all registered loaders, in lookup order.
+///
+/// Useful for reporting supported sources, e.g. in a CLI's help output.
+pub fn list_loaders() -> Vec<&'static str> {
+    collect_loaders()
+        .into_iter()
+        .map(|loader| loader.name())
+        .collect()
+}
+
+/// Names of all registered formatters, in lookup order.
+pub fn list_formatters() -> Vec<&'static str> {
+    collect_formatters()
+        .into_iter()
+        .map(|formatter| formatter.name())
+        .collect()
+}
+
+/// File extensions handled by all registered formatters, without the
+/// leading dot.
+pub fn list_extensions() -> Vec<&'static str> {
+    collect_formatters()
+        .into_iter()
+        .flat_map(|formatter| formatter.extensions().iter().copied())
         .collect()
 }
 
 /// Find a loader that can handle the given identifier.
 ///
-/// Iterates over all registered loaders and returns the first one whose
-/// `provides()` method returns `true`.
+/// Returns the highest-priority registered loader whose `provides()`
+/// method returns `true`.
 pub fn find_loader(identifier: &str) -> Option<&'static dyn Loader> {
-    for entry in inventory::iter::<RegisteredLoader> {
-        if entry.0.provides(identifier) {
-            return Some(entry.0);
-        }
-    }
-    None
+    collect_loaders()
+        .into_iter()
+        .find(|loader| loader.provides(identifier))
+}
+
+/// Whether the loader for the given identifier supports watching.
+///
+/// Returns `false` if no loader provides the identifier, or if the loader
+/// that does cannot push change notifications.
+pub fn supports_watch(identifier: &str) -> bool {
+    find_loader(identifier).is_some_and(|loader| loader.can_watch())
 }
 
 /// Find a formatter that can handle the given source identifier.
 ///
-/// Matches by file extension on the source path.
+/// Matches by file extension on the source path, returning the
+/// highest-priority formatter whose `provides()` method returns `true`.
 pub fn find_formatter(source: &str) -> Option<&'static dyn Formatter> {
-    for entry in inventory::iter::<RegisteredFormatter> {
-        if entry.0.provides(source) {
-            return Some(entry.0);
-        }
-    }
-    None
+    collect_formatters()
+        .into_iter()
+        .find(|formatter| formatter.provides(source))
 }
 
 /// Find a formatter by format hint string (e.g., "json", "toml").
@@ -61,10 +184,19 @@ pub fn find_formatter(source: &str) -> Option<&'static dyn Formatter> {
 /// Used when the source has no file extension but the loader provides
 /// a format hint.
 pub fn find_formatter_by_hint(hint: &str) -> Option<&'static dyn Formatter> {
-    for entry in inventory::iter::<RegisteredFormatter> {
-        if entry.0.extensions().contains(&hint) {
-            return Some(entry.0);
-        }
-    }
-    None
+    collect_formatters()
+        .into_iter()
+        .find(|formatter| formatter.extensions().contains(&hint))
+}
+
+/// Find a formatter that recognizes the given content.
+///
+/// Used when a source has neither a file extension nor a format hint.
+/// Detection is heuristic and some content (e.g. `[section]` followed by
+/// `key = 1`) is claimed by more than one formatter; the first match in
+/// lookup order wins.
+pub fn find_formatter_by_content(content: &str) -> Option<&'static dyn Formatter> {
+    collect_formatters()
+        .into_iter()
+        .find(|formatter| formatter.provides_content(content))
 }
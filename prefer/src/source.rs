@@ -10,11 +10,19 @@
 #![allow(deprecated)] // Internal implementations still reference their own deprecated types
 
 use crate::error::{Error, Result};
+use crate::events::key_under_prefix;
+use crate::formatter::{json::JsonFormatter, Formatter};
 use crate::registry;
-use crate::value::ConfigValue;
+use crate::value::{infer_scalar, set_nested_value, ConfigValue};
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
 
 /// A source of configuration data.
 ///
@@ -59,6 +67,28 @@ pub trait Source: Send + Sync {
 
     /// Get a human-readable name for this source (used in error messages).
     fn name(&self) -> &str;
+
+    /// Post-process this source's loaded data with a transform.
+    ///
+    /// The transform runs after every successful `load()`.
+    fn map<F>(self, transform: F) -> TransformSource<Self, F>
+    where
+        Self: Sized,
+        F: Fn(ConfigValue) -> Result<ConfigValue> + Send + Sync,
+    {
+        TransformSource::new(self, transform)
+    }
+
+    /// Fall back to `other` if this source fails to load.
+    ///
+    /// See `FallbackSource` for details.
+    fn or<S>(self, other: S) -> FallbackSource
+    where
+        Self: Sized + 'static,
+        S: Source + 'static,
+    {
+        FallbackSource::new().with_source(self).with_source(other)
+    }
 }
 
 /// A configuration source that loads from a file.
@@ -90,7 +120,7 @@ impl FileSource {
 #[async_trait]
 impl Source for FileSource {
     async fn load(&self) -> Result<ConfigValue> {
-        let contents = tokio::fs::read_to_string(&self.path).await?;
+        let contents = crate::loader::file::read_text_file(&self.path).await?;
         let source = self.path.to_string_lossy().to_string();
         let formatters = registry::collect_formatters();
         let fmt = formatters
@@ -115,6 +145,36 @@ impl Source for FileSource {
 pub struct EnvSource {
     prefix: String,
     separator: String,
+    key_style: KeyStyle,
+    json_values: bool,
+}
+
+/// How `EnvSource` turns each separator-delimited part of a variable name
+/// into a key.
+///
+/// With prefix `APP`, the variable `APP__DATABASE__MaxConns` yields:
+/// - `Lowercase`: `database.maxconns`
+/// - `Preserve`: `DATABASE.MaxConns`
+/// - `Custom(f)`: `f("DATABASE")` and `f("MaxConns")`
+#[derive(Debug, Clone, Copy, Default)]
+pub enum KeyStyle {
+    /// Lowercase every key (the default).
+    #[default]
+    Lowercase,
+    /// Keep keys exactly as written in the variable name.
+    Preserve,
+    /// Transform each key with a custom function.
+    Custom(fn(&str) -> String),
+}
+
+impl KeyStyle {
+    fn apply(&self, key: &str) -> String {
+        match self {
+            KeyStyle::Lowercase => key.to_lowercase(),
+            KeyStyle::Preserve => key.to_string(),
+            KeyStyle::Custom(f) => f(key),
+        }
+    }
 }
 
 impl EnvSource {
@@ -125,6 +185,8 @@ impl EnvSource {
         Self {
             prefix: prefix.into(),
             separator: "__".to_string(),
+            key_style: KeyStyle::default(),
+            json_values: false,
         }
     }
 
@@ -133,49 +195,120 @@ impl EnvSource {
         Self {
             prefix: prefix.into(),
             separator: separator.into(),
+            key_style: KeyStyle::default(),
+            json_values: false,
         }
     }
 
+    /// Set how variable names are turned into keys.
+    ///
+    /// Defaults to `KeyStyle::Lowercase`.
+    pub fn with_key_style(mut self, key_style: KeyStyle) -> Self {
+        self.key_style = key_style;
+        self
+    }
+
+    /// Expand values holding a JSON object or array into structure.
+    ///
+    /// With this enabled, `APP__FEATURES={"a":true}` becomes the subtree
+    /// `features.a = true` instead of a string. Other values, including
+    /// invalid JSON, are typed with `infer_scalar()` as usual. Off by default.
+    pub fn with_json_values(mut self, enabled: bool) -> Self {
+        self.json_values = enabled;
+        self
+    }
+
+    /// Type a variable's value, expanding JSON if enabled.
+    fn parse_value(&self, value: &str) -> ConfigValue {
+        if self.json_values && value.trim_start().starts_with(['{', '[']) {
+            if let Ok(parsed @ (ConfigValue::Object(_) | ConfigValue::Array(_))) =
+                JsonFormatter.deserialize(value)
+            {
+                return parsed;
+            }
+        }
+        infer_scalar(value)
+    }
+
+    /// Collect the environment variables under this source's prefix.
+    fn matching_vars(&self) -> HashMap<String, String> {
+        let prefix_with_sep = format!("{}{}", self.prefix, self.separator);
+        std::env::vars()
+            .filter(|(k, _)| k.starts_with(&prefix_with_sep))
+            .collect()
+    }
+
+    /// Whether any environment variable is currently set under this
+    /// source's prefix.
+    pub(crate) fn has_vars(&self) -> bool {
+        !self.matching_vars().is_empty()
+    }
+
     /// Convert a flat map of environment variables to a nested structure.
     fn to_nested_value(&self, vars: HashMap<String, String>) -> ConfigValue {
         let mut root: HashMap<String, ConfigValue> = HashMap::new();
 
         for (key, value) in vars {
-            // Remove prefix and convert to lowercase
-            let key = key
+            // Remove prefix and apply the key style to each part
+            let rest = key
                 .strip_prefix(&self.prefix)
                 .and_then(|k| k.strip_prefix(&self.separator))
-                .unwrap_or(&key)
-                .to_lowercase();
-
-            let parts: Vec<&str> = key.split(&self.separator.to_lowercase()).collect();
-            insert_nested(&mut root, &parts, value);
+                .unwrap_or(&key);
+            let parts: Vec<String> = split_ignore_ascii_case(rest, &self.separator)
+                .into_iter()
+                .map(|part| self.key_style.apply(part))
+                .collect();
+
+            let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+            insert_nested_value(&mut root, &parts, self.parse_value(&value));
         }
 
         ConfigValue::Object(root)
     }
 }
 
-fn insert_nested(obj: &mut HashMap<String, ConfigValue>, path: &[&str], value: String) {
+/// Split `s` on every occurrence of `separator`, ignoring ASCII case.
+///
+/// Keeps nesting independent of `KeyStyle`: under a `_X_` separator,
+/// `DB_x_HOST` splits into `DB` and `HOST` whether or not keys are
+/// lowercased.
+fn split_ignore_ascii_case<'a>(s: &'a str, separator: &str) -> Vec<&'a str> {
+    if separator.is_empty() {
+        return vec![s];
+    }
+
+    let (bytes, sep) = (s.as_bytes(), separator.as_bytes());
+    let mut parts = Vec::new();
+    let (mut start, mut i) = (0, 0);
+    while i + sep.len() <= bytes.len() {
+        // A match starts and ends on char boundaries, as `separator` does
+        if bytes[i..i + sep.len()].eq_ignore_ascii_case(sep) {
+            parts.push(&s[start..i]);
+            i += sep.len();
+            start = i;
+        } else {
+            i += 1;
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Insert a string value at a nested path, inferring its scalar type with
+/// `infer_scalar()`.
+pub(crate) fn insert_nested(obj: &mut HashMap<String, ConfigValue>, path: &[&str], value: String) {
+    insert_nested_value(obj, path, infer_scalar(&value));
+}
+
+/// Insert a value at a nested path, creating intermediate objects.
+fn insert_nested_value(obj: &mut HashMap<String, ConfigValue>, path: &[&str], value: ConfigValue) {
     // path is never empty because str::split() always returns at least one element
     debug_assert!(!path.is_empty(), "path should never be empty");
 
     let key = path[0].to_string();
 
     if path.len() == 1 {
-        // Try to parse as different types
-        let parsed_value = if value.eq_ignore_ascii_case("true") {
-            ConfigValue::Bool(true)
-        } else if value.eq_ignore_ascii_case("false") {
-            ConfigValue::Bool(false)
-        } else if let Ok(n) = value.parse::<i64>() {
-            ConfigValue::Integer(n)
-        } else if let Ok(n) = value.parse::<f64>() {
-            ConfigValue::Float(n)
-        } else {
-            ConfigValue::String(value)
-        };
-        obj.insert(key, parsed_value);
+        obj.insert(key, value);
     } else {
         // Get or create nested object
         let nested = obj
@@ -183,7 +316,7 @@ fn insert_nested(obj: &mut HashMap<String, ConfigValue>, path: &[&str], value: S
             .or_insert_with(|| ConfigValue::Object(HashMap::new()));
 
         if let ConfigValue::Object(nested_obj) = nested {
-            insert_nested(nested_obj, &path[1..], value);
+            insert_nested_value(nested_obj, &path[1..], value);
         }
     }
 }
@@ -191,12 +324,7 @@ fn insert_nested(obj: &mut HashMap<String, ConfigValue>, path: &[&str], value: S
 #[async_trait]
 impl Source for EnvSource {
     async fn load(&self) -> Result<ConfigValue> {
-        let prefix_with_sep = format!("{}{}", self.prefix, self.separator);
-        let vars: HashMap<String, String> = std::env::vars()
-            .filter(|(k, _)| k.starts_with(&prefix_with_sep))
-            .collect();
-
-        Ok(self.to_nested_value(vars))
+        Ok(self.to_nested_value(self.matching_vars()))
     }
 
     fn name(&self) -> &str {
@@ -206,34 +334,70 @@ impl Source for EnvSource {
 
 /// A configuration source that holds data in memory.
 ///
-/// Useful for testing or providing default values.
+/// Useful for testing or providing default values. The data may also be
+/// computed on each load with `MemorySource::lazy`.
 pub struct MemorySource {
-    data: ConfigValue,
+    data: MemoryData,
     source_name: String,
 }
 
+/// The data held by a `MemorySource`.
+enum MemoryData {
+    Value(ConfigValue),
+    Lazy(Box<dyn Fn() -> ConfigValue + Send + Sync>),
+}
+
 impl MemorySource {
     /// Create a new memory source with the given data.
     pub fn new(data: ConfigValue) -> Self {
-        Self {
-            data,
-            source_name: "memory".to_string(),
-        }
+        Self::with_name(data, "memory")
     }
 
     /// Create a new memory source with a custom name.
     pub fn with_name(data: ConfigValue, name: impl Into<String>) -> Self {
         Self {
-            data,
+            data: MemoryData::Value(data),
             source_name: name.into(),
         }
     }
+
+    /// Create a memory source by parsing `content` with the formatter
+    /// registered for `format_hint` (an extension like `"toml"` or a
+    /// formatter name).
+    ///
+    /// The content is parsed once, up front, and the source is named after
+    /// the formatter. Returns `Error::NoFormatterFound` if no formatter
+    /// matches the hint.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(content: &str, format_hint: &str) -> Result<Self> {
+        let fmt = registry::find_formatter_by_hint(format_hint)
+            .ok_or_else(|| Error::NoFormatterFound(format_hint.to_string()))?;
+        Ok(Self::with_name(fmt.deserialize(content)?, fmt.name()))
+    }
+
+    /// Create a memory source whose data is computed by a closure.
+    ///
+    /// The closure runs on every `load()`, so it can produce fresh data,
+    /// e.g. from an embedded resource. Wrap the source in `CachingSource`
+    /// to compute it only once.
+    pub fn lazy<F>(f: F) -> Self
+    where
+        F: Fn() -> ConfigValue + Send + Sync + 'static,
+    {
+        Self {
+            data: MemoryData::Lazy(Box::new(f)),
+            source_name: "memory".to_string(),
+        }
+    }
 }
 
 #[async_trait]
 impl Source for MemorySource {
     async fn load(&self) -> Result<ConfigValue> {
-        Ok(self.data.clone())
+        match &self.data {
+            MemoryData::Value(data) => Ok(data.clone()),
+            MemoryData::Lazy(f) => Ok(f()),
+        }
     }
 
     fn name(&self) -> &str {
@@ -241,11 +405,119 @@ impl Source for MemorySource {
     }
 }
 
+/// A configuration source that applies a transform to another source's data.
+///
+/// Useful for adapting third-party sources — renaming keys, dropping a
+/// prefix, or coercing types — without writing a whole `Source`. Usually
+/// constructed via `Source::map`.
+pub struct TransformSource<S, F> {
+    inner: S,
+    transform: F,
+}
+
+impl<S, F> TransformSource<S, F>
+where
+    S: Source,
+    F: Fn(ConfigValue) -> Result<ConfigValue> + Send + Sync,
+{
+    /// Wrap a source with a transform applied after each load.
+    pub fn new(inner: S, transform: F) -> Self {
+        Self { inner, transform }
+    }
+}
+
+#[async_trait]
+impl<S, F> Source for TransformSource<S, F>
+where
+    S: Source,
+    F: Fn(ConfigValue) -> Result<ConfigValue> + Send + Sync,
+{
+    async fn load(&self) -> Result<ConfigValue> {
+        let value = self.inner.load().await?;
+        (self.transform)(value)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
+
+/// A configuration source that memoizes another source's data.
+///
+/// The inner source is loaded once and the result is returned on subsequent
+/// loads. With `with_ttl`, the cached data expires and the next load goes
+/// back to the inner source. Failed loads are not cached.
+pub struct CachingSource<S> {
+    inner: S,
+    ttl: Option<Duration>,
+    cache: Mutex<Option<(Instant, ConfigValue)>>,
+}
+
+impl<S: Source> CachingSource<S> {
+    /// Wrap a source, caching its data indefinitely.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            ttl: None,
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Expire the cached data after the given duration.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+#[async_trait]
+impl<S: Source> Source for CachingSource<S> {
+    async fn load(&self) -> Result<ConfigValue> {
+        let mut cache = self.cache.lock().await;
+
+        if let Some((loaded_at, value)) = cache.as_ref() {
+            if self.ttl.is_none_or(|ttl| loaded_at.elapsed() < ttl) {
+                return Ok(value.clone());
+            }
+        }
+
+        let value = self.inner.load().await?;
+        *cache = Some((Instant::now(), value.clone()));
+        Ok(value)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
+
 /// A configuration source that layers multiple sources with priority.
 ///
-/// Later sources override earlier sources when keys conflict.
+/// Later sources override earlier sources when keys conflict. Sources are
+/// loaded concurrently, so a slow source doesn't delay the others, but
+/// precedence always follows the order they were added.
 pub struct LayeredSource {
     pub(crate) sources: Vec<Box<dyn Source>>,
+    pub(crate) conflict_policy: ConflictPolicy,
+    pub(crate) timeout: Option<Duration>,
+}
+
+/// How `LayeredSource` handles a layer overriding an earlier layer's value.
+///
+/// A conflict is a key whose value differs between two layers, other than
+/// objects (which are merged key by key). Setting a key to the value it
+/// already has is not a conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// Later layers silently override earlier ones.
+    #[default]
+    Overwrite,
+    /// Fail the load with `Error::MergeConflict` naming the first
+    /// conflicting key.
+    Error,
+    /// Override as with `Overwrite`, but log a warning for each conflicting
+    /// key through the `log` crate.
+    Warn,
 }
 
 impl LayeredSource {
@@ -253,9 +525,26 @@ impl LayeredSource {
     pub fn new() -> Self {
         Self {
             sources: Vec::new(),
+            conflict_policy: ConflictPolicy::default(),
+            timeout: None,
         }
     }
 
+    /// Limit how long each source may take to load.
+    ///
+    /// A source that takes longer fails the load with a `SourceError`
+    /// naming it. Requires a Tokio runtime with the time driver enabled.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set how conflicting values between layers are handled.
+    pub fn with_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.conflict_policy = policy;
+        self
+    }
+
     /// Add a source to the layer (lower priority than sources added later).
     pub fn with_source<S: Source + 'static>(mut self, source: S) -> Self {
         self.sources.push(Box::new(source));
@@ -267,6 +556,39 @@ impl LayeredSource {
         self.sources.push(source);
         self
     }
+
+    /// Add a source whose data is mounted under a dotted key.
+    ///
+    /// For example, mounting a source under `"plugins.db"` makes its `host`
+    /// key available as `plugins.db.host`.
+    pub fn with_source_at<S: Source + 'static>(mut self, key: &str, source: S) -> Self {
+        self.sources.push(Box::new(MountedSource {
+            key: key.to_string(),
+            inner: Box::new(source),
+        }));
+        self
+    }
+}
+
+/// A source whose loaded data is nested under a dotted key.
+struct MountedSource {
+    key: String,
+    inner: Box<dyn Source>,
+}
+
+#[async_trait]
+impl Source for MountedSource {
+    async fn load(&self) -> Result<ConfigValue> {
+        let value = self.inner.load().await?;
+        let mut root = ConfigValue::Object(HashMap::new());
+        let parts: Vec<&str> = self.key.split('.').collect();
+        set_nested_value(&mut root, &parts, value);
+        Ok(root)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
 }
 
 impl Default for LayeredSource {
@@ -275,29 +597,241 @@ impl Default for LayeredSource {
     }
 }
 
+impl LayeredSource {
+    /// Load and merge every layer, recording which source last wrote each
+    /// leaf key.
+    ///
+    /// Sources load concurrently, but are merged in layer order once all
+    /// have finished, so precedence never depends on which finishes first.
+    pub(crate) async fn load_with_provenance(&self) -> Result<(ConfigValue, Provenance)> {
+        let mut merged = ConfigValue::Object(HashMap::new());
+        let mut provenance = Provenance::default();
+
+        let loads = self
+            .sources
+            .iter()
+            .map(|source| self.load_layer(source.as_ref()))
+            .collect();
+        let values = join_all(loads).await;
+
+        for (source, value) in self.sources.iter().zip(values) {
+            let value = value?;
+
+            if self.conflict_policy != ConflictPolicy::Overwrite {
+                let mut conflicts = Vec::new();
+                find_conflicts(&merged, &value, &mut String::new(), &mut conflicts);
+                conflicts.sort();
+
+                for key in conflicts {
+                    if self.conflict_policy == ConflictPolicy::Error {
+                        return Err(Error::MergeConflict {
+                            key,
+                            source_name: source.name().to_string(),
+                        });
+                    }
+                    log::warn!(
+                        "configuration key '{}' from '{}' overrides an earlier layer",
+                        key,
+                        source.name()
+                    );
+                }
+            }
+
+            provenance.record(&value, source.name());
+            merge_values(&mut merged, value);
+        }
+
+        Ok((merged, provenance))
+    }
+
+    /// Load one layer, applying the timeout and naming the source in errors.
+    async fn load_layer(&self, source: &dyn Source) -> Result<ConfigValue> {
+        let source_error = |e: Box<dyn std::error::Error + Send + Sync>| Error::SourceError {
+            source_name: source.name().to_string(),
+            source: e,
+        };
+
+        let result = match self.timeout {
+            Some(limit) => tokio::time::timeout(limit, source.load())
+                .await
+                .map_err(|_| source_error(format!("timed out after {:?}", limit).into()))?,
+            None => source.load().await,
+        };
+        result.map_err(|e| source_error(Box::new(e)))
+    }
+}
+
+/// Await every future concurrently, returning their outputs in order.
+async fn join_all<F: Future>(futures: Vec<F>) -> Vec<F::Output> {
+    let mut futures: Vec<Pin<Box<F>>> = futures.into_iter().map(Box::pin).collect();
+    let mut outputs: Vec<Option<F::Output>> = futures.iter().map(|_| None).collect();
+
+    std::future::poll_fn(|cx| {
+        let mut pending = false;
+        for (future, output) in futures.iter_mut().zip(outputs.iter_mut()) {
+            if output.is_none() {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(value) => *output = Some(value),
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await;
+
+    outputs.into_iter().flatten().collect()
+}
+
 #[async_trait]
 impl Source for LayeredSource {
     async fn load(&self) -> Result<ConfigValue> {
-        let mut merged = ConfigValue::Object(HashMap::new());
+        self.load_with_provenance().await.map(|(merged, _)| merged)
+    }
+
+    fn name(&self) -> &str {
+        "layered"
+    }
+}
+
+/// The name of the source that last wrote each leaf key, keyed by dotted
+/// path.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Provenance(HashMap<String, String>);
+
+impl Provenance {
+    /// Record `source_name` for every leaf of a layer about to be merged.
+    ///
+    /// Empty objects are skipped because merging them changes nothing.
+    pub(crate) fn record(&mut self, value: &ConfigValue, source_name: &str) {
+        let mut leaves: Vec<_> = value
+            .flatten_keys(".")
+            .into_iter()
+            .filter(|(_, leaf)| !matches!(leaf, ConfigValue::Object(_)))
+            .map(|(key, _)| key)
+            .collect();
+        leaves.sort();
+
+        for key in leaves {
+            self.insert(key, source_name.to_string());
+        }
+    }
+
+    /// Record the source of one leaf, replacing any entries it overwrites.
+    ///
+    /// A leaf replaces both the keys nested under it and a scalar at any of
+    /// its parents.
+    pub(crate) fn insert(&mut self, key: String, source_name: String) {
+        self.0.retain(|existing, _| {
+            !key_under_prefix(existing, &key) && !key_under_prefix(&key, existing)
+        });
+        self.0.insert(key, source_name);
+    }
+
+    /// Consume the record, returning the map of keys to source names.
+    pub(crate) fn into_map(self) -> HashMap<String, String> {
+        self.0
+    }
+}
+
+/// Collect the dotted keys whose values `overlay` would change in `base`.
+fn find_conflicts(
+    base: &ConfigValue,
+    overlay: &ConfigValue,
+    path: &mut String,
+    conflicts: &mut Vec<String>,
+) {
+    match (base, overlay) {
+        (ConfigValue::Object(base_obj), ConfigValue::Object(overlay_obj)) => {
+            for (key, overlay_value) in overlay_obj {
+                if let Some(base_value) = base_obj.get(key) {
+                    let len = path.len();
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(key);
+                    find_conflicts(base_value, overlay_value, path, conflicts);
+                    path.truncate(len);
+                }
+            }
+        }
+        (base, overlay) if base != overlay => conflicts.push(path.clone()),
+        _ => {}
+    }
+}
+
+/// A configuration source that uses the first of several sources to load.
+///
+/// Unlike `LayeredSource`, nothing is merged: sources are tried in the order
+/// they were added and the first successful load wins. If every source
+/// fails, the error lists each source's failure. Usually constructed via
+/// `Source::or`.
+pub struct FallbackSource {
+    sources: Vec<Box<dyn Source>>,
+}
+
+impl FallbackSource {
+    /// Create a new fallback source with no sources.
+    pub fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+        }
+    }
+
+    /// Add a source to try after the ones already added.
+    pub fn with_source<S: Source + 'static>(mut self, source: S) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Add a boxed source to try after the ones already added.
+    pub fn add_boxed(mut self, source: Box<dyn Source>) -> Self {
+        self.sources.push(source);
+        self
+    }
+}
+
+impl Default for FallbackSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Source for FallbackSource {
+    async fn load(&self) -> Result<ConfigValue> {
+        let mut failures = Vec::new();
 
         for source in &self.sources {
-            let value = source.load().await.map_err(|e| Error::SourceError {
-                source_name: source.name().to_string(),
-                source: Box::new(e),
-            })?;
-            merge_values(&mut merged, value);
+            match source.load().await {
+                Ok(value) => return Ok(value),
+                Err(e) => failures.push(format!("{}: {}", source.name(), e)),
+            }
         }
 
-        Ok(merged)
+        let message = if failures.is_empty() {
+            "no sources to load".to_string()
+        } else {
+            format!("all sources failed ({})", failures.join("; "))
+        };
+
+        Err(Error::SourceError {
+            source_name: self.name().to_string(),
+            source: message.into(),
+        })
     }
 
     fn name(&self) -> &str {
-        "layered"
+        "fallback"
     }
 }
 
 /// Deep merge two ConfigValues, with `overlay` taking precedence.
-fn merge_values(base: &mut ConfigValue, overlay: ConfigValue) {
+pub(crate) fn merge_values(base: &mut ConfigValue, overlay: ConfigValue) {
     match (base, overlay) {
         (ConfigValue::Object(base_obj), ConfigValue::Object(overlay_obj)) => {
             for (key, overlay_value) in overlay_obj {
@@ -318,10 +852,30 @@ fn merge_values(base: &mut ConfigValue, overlay: ConfigValue) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::value::test_helpers::{bool_val, int, obj};
+    use crate::value::test_helpers::{bool_val, int, obj, string};
     use serial_test::serial;
     use tempfile::TempDir;
 
+    #[tokio::test]
+    async fn test_memory_source_from_str() {
+        let source =
+            MemorySource::from_str("[server]\nhost = \"localhost\"\nport = 8080\n", "toml")
+                .unwrap();
+        assert_eq!(source.name(), "toml");
+
+        let loaded = source.load().await.unwrap();
+        assert_eq!(
+            loaded,
+            obj(vec![(
+                "server",
+                obj(vec![("host", string("localhost")), ("port", int(8080))])
+            )])
+        );
+
+        let err = MemorySource::from_str("x = 1", "nope").err().unwrap();
+        assert!(matches!(err, Error::NoFormatterFound(ref h) if h == "nope"));
+    }
+
     #[tokio::test]
     async fn test_memory_source() {
         let data = obj(vec![
@@ -334,6 +888,28 @@ mod tests {
         assert_eq!(loaded, data);
     }
 
+    #[tokio::test]
+    async fn test_memory_source_lazy() {
+        use std::sync::atomic::{AtomicI64, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicI64::new(0));
+        let counter = calls.clone();
+        let source = MemorySource::lazy(move || {
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            obj(vec![("calls", int(n))])
+        });
+
+        // Nothing is computed until the first load
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(source.load().await.unwrap(), obj(vec![("calls", int(1))]));
+        assert_eq!(source.load().await.unwrap(), obj(vec![("calls", int(2))]));
+        assert_eq!(source.name(), "memory");
+
+        let cached = CachingSource::new(MemorySource::lazy(|| int(7)));
+        assert_eq!(cached.load().await.unwrap(), int(7));
+    }
+
     #[tokio::test]
     async fn test_layered_source_merge() {
         let base = MemorySource::with_name(
@@ -389,6 +965,132 @@ mod tests {
         assert_eq!(result.get("debug").unwrap().as_bool(), Some(true)); // From overlay
     }
 
+    #[test]
+    fn test_env_source_key_style_lowercase() {
+        let source = EnvSource::new("APP").with_key_style(KeyStyle::Lowercase);
+        let vars = HashMap::from([("APP__Pool__MaxConns".to_string(), "10".to_string())]);
+
+        let result = source.to_nested_value(vars);
+        assert_eq!(
+            result
+                .get("pool")
+                .unwrap()
+                .get("maxconns")
+                .unwrap()
+                .as_i64(),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn test_env_source_key_style_preserve() {
+        let source = EnvSource::new("APP").with_key_style(KeyStyle::Preserve);
+        let vars = HashMap::from([("APP__Pool__MaxConns".to_string(), "10".to_string())]);
+
+        let result = source.to_nested_value(vars);
+        assert_eq!(
+            result
+                .get("Pool")
+                .unwrap()
+                .get("MaxConns")
+                .unwrap()
+                .as_i64(),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn test_env_source_separator_ignores_case() {
+        let vars = || HashMap::from([("APP_X_Db_x_Host".to_string(), "db".to_string())]);
+
+        let source = EnvSource::with_separator("APP", "_X_");
+        let result = source.to_nested_value(vars());
+        assert_eq!(
+            result.get("db").unwrap().get("host").unwrap().as_str(),
+            Some("db")
+        );
+
+        let source = EnvSource::with_separator("APP", "_X_").with_key_style(KeyStyle::Preserve);
+        let result = source.to_nested_value(vars());
+        assert_eq!(
+            result.get("Db").unwrap().get("Host").unwrap().as_str(),
+            Some("db")
+        );
+    }
+
+    #[test]
+    fn test_env_source_key_style_custom() {
+        fn snake_to_camel(key: &str) -> String {
+            let mut out = String::new();
+            for (i, word) in key.split('_').enumerate() {
+                let word = word.to_lowercase();
+                if i == 0 {
+                    out.push_str(&word);
+                } else {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        out.extend(first.to_uppercase());
+                        out.push_str(chars.as_str());
+                    }
+                }
+            }
+            out
+        }
+
+        let source = EnvSource::new("APP").with_key_style(KeyStyle::Custom(snake_to_camel));
+        let vars = HashMap::from([("APP__DATABASE__MAX_IDLE_CONNS".to_string(), "4".to_string())]);
+
+        let result = source.to_nested_value(vars);
+        assert_eq!(
+            result
+                .get("database")
+                .unwrap()
+                .get("maxIdleConns")
+                .unwrap()
+                .as_i64(),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn test_env_source_json_values() {
+        let source = EnvSource::new("APP").with_json_values(true);
+        let vars = HashMap::from([
+            (
+                "APP__FEATURES".to_string(),
+                r#"{"a": true, "b": false}"#.to_string(),
+            ),
+            ("APP__HOSTS".to_string(), r#"["a", "b"]"#.to_string()),
+            ("APP__NAME".to_string(), "plain".to_string()),
+            ("APP__BROKEN".to_string(), "{not json".to_string()),
+            ("APP__PORT".to_string(), "8080".to_string()),
+        ]);
+
+        let result = source.to_nested_value(vars);
+        assert_eq!(
+            result,
+            obj(vec![
+                (
+                    "features",
+                    obj(vec![("a", bool_val(true)), ("b", bool_val(false))])
+                ),
+                ("hosts", ConfigValue::Array(vec![string("a"), string("b")])),
+                ("name", string("plain")),
+                ("broken", string("{not json")),
+                ("port", int(8080)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_env_source_json_values_off_by_default() {
+        let source = EnvSource::new("APP");
+        let vars = HashMap::from([("APP__HOSTS".to_string(), r#"["a"]"#.to_string())]);
+
+        let result = source.to_nested_value(vars);
+        assert_eq!(result, obj(vec![("hosts", string(r#"["a"]"#))]));
+    }
+
     #[test]
     fn test_env_source_nested() {
         let source = EnvSource::new("TEST");
@@ -462,6 +1164,16 @@ mod tests {
         assert_eq!(value.get("source").unwrap().as_str(), Some("file"));
     }
 
+    #[tokio::test]
+    async fn test_file_source_load_with_bom() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("bom.toml");
+        std::fs::write(&config_path, "\u{FEFF}name = \"app\"\n").unwrap();
+
+        let value = FileSource::new(&config_path).load().await.unwrap();
+        assert_eq!(value.get("name").unwrap().as_str(), Some("app"));
+    }
+
     #[tokio::test]
     async fn test_file_source_not_found() {
         let source = FileSource::new("/nonexistent/path.json");
@@ -604,6 +1316,252 @@ mod tests {
         std::env::remove_var("ENVBOOL__DISABLED");
     }
 
+    #[tokio::test]
+    async fn test_layered_source_with_source_at() {
+        let base = MemorySource::with_name(obj(vec![("debug", bool_val(true))]), "base");
+        let db = MemorySource::with_name(
+            obj(vec![
+                ("host", ConfigValue::String("db.local".to_string())),
+                ("port", int(5432)),
+            ]),
+            "db",
+        );
+
+        let layered = LayeredSource::new()
+            .with_source(base)
+            .with_source_at("plugins.db", db);
+
+        let value = layered.load().await.unwrap();
+        let plugin = value.get("plugins").unwrap().get("db").unwrap();
+        assert_eq!(plugin.get("host").unwrap().as_str(), Some("db.local"));
+        assert_eq!(plugin.get("port").unwrap().as_i64(), Some(5432));
+        assert_eq!(value.get("debug").unwrap().as_bool(), Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_layered_source_with_source_at_error_uses_inner_name() {
+        let layered = LayeredSource::new()
+            .with_source_at("plugins", FileSource::new("/nonexistent/plugin.json"));
+        match layered.load().await.unwrap_err() {
+            Error::SourceError { source_name, .. } => {
+                assert_eq!(source_name, "/nonexistent/plugin.json")
+            }
+            other => panic!("expected SourceError, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transform_source_uppercases_strings() {
+        let source = MemorySource::with_name(
+            obj(vec![
+                ("host", ConfigValue::String("localhost".to_string())),
+                ("mode", ConfigValue::String("dev".to_string())),
+                ("port", int(8080)),
+            ]),
+            "inner",
+        )
+        .map(|value| {
+            let ConfigValue::Object(map) = value else {
+                return Ok(value);
+            };
+            Ok(ConfigValue::Object(
+                map.into_iter()
+                    .map(|(k, v)| match v {
+                        ConfigValue::String(s) => (k, ConfigValue::String(s.to_uppercase())),
+                        other => (k, other),
+                    })
+                    .collect(),
+            ))
+        });
+        assert_eq!(source.name(), "inner");
+
+        let value = source.load().await.unwrap();
+        assert_eq!(value.get("host").unwrap().as_str(), Some("LOCALHOST"));
+        assert_eq!(value.get("mode").unwrap().as_str(), Some("DEV"));
+        assert_eq!(value.get("port").unwrap().as_i64(), Some(8080));
+    }
+
+    #[tokio::test]
+    async fn test_transform_source_error_propagates() {
+        let source = TransformSource::new(MemorySource::new(obj(vec![])), |_| {
+            Err(Error::KeyNotFound("required".into()))
+        });
+        let layered = LayeredSource::new().with_source(source);
+        assert!(matches!(
+            layered.load().await.unwrap_err(),
+            Error::SourceError { .. }
+        ));
+    }
+
+    struct CountingSource {
+        loads: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Source for CountingSource {
+        async fn load(&self) -> Result<ConfigValue> {
+            let n = self.loads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(obj(vec![("loads", int(n as i64 + 1))]))
+        }
+
+        fn name(&self) -> &str {
+            "counting"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_caching_source_loads_once() {
+        let loads = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let source = CachingSource::new(CountingSource {
+            loads: loads.clone(),
+        });
+        assert_eq!(source.name(), "counting");
+
+        for _ in 0..3 {
+            let value = source.load().await.unwrap();
+            assert_eq!(value.get("loads").unwrap().as_i64(), Some(1));
+        }
+        assert_eq!(loads.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_caching_source_ttl_expires() {
+        let loads = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let source = CachingSource::new(CountingSource {
+            loads: loads.clone(),
+        })
+        .with_ttl(Duration::from_millis(50));
+
+        source.load().await.unwrap();
+        tokio::time::advance(Duration::from_millis(40)).await;
+        source.load().await.unwrap();
+        assert_eq!(loads.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        tokio::time::advance(Duration::from_millis(40)).await;
+        let value = source.load().await.unwrap();
+        assert_eq!(value.get("loads").unwrap().as_i64(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_caching_source_does_not_cache_errors() {
+        let source = CachingSource::new(FileSource::new("/nonexistent/cached.json"));
+        assert!(source.load().await.is_err());
+        assert!(source.cache.lock().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fallback_source_uses_first_success() {
+        let local = FileSource::new("/nonexistent/local.json");
+        let remote = MemorySource::with_name(obj(vec![("port", int(8080))]), "remote");
+        let unused = MemorySource::with_name(obj(vec![("port", int(9090))]), "unused");
+
+        let source = local.or(remote).or(unused);
+        let loaded = source.load().await.unwrap();
+        assert_eq!(loaded, obj(vec![("port", int(8080))]));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_source_all_fail() {
+        let source = FallbackSource::new()
+            .with_source(FileSource::new("/nonexistent/first.json"))
+            .add_boxed(Box::new(FileSource::new("/nonexistent/second.json")));
+
+        match source.load().await {
+            Err(Error::SourceError {
+                source_name,
+                source,
+            }) => {
+                assert_eq!(source_name, "fallback");
+                let message = source.to_string();
+                assert!(message.contains("/nonexistent/first.json"));
+                assert!(message.contains("/nonexistent/second.json"));
+            }
+            other => panic!("expected SourceError, got {:?}", other),
+        }
+
+        assert!(FallbackSource::default().load().await.is_err());
+    }
+
+    fn conflicting_layers(policy: ConflictPolicy) -> LayeredSource {
+        let system = MemorySource::with_name(
+            obj(vec![(
+                "security",
+                obj(vec![("tls", bool_val(true)), ("port", int(443))]),
+            )]),
+            "system",
+        );
+        let user = MemorySource::with_name(
+            obj(vec![(
+                "security",
+                obj(vec![("tls", bool_val(false)), ("port", int(443))]),
+            )]),
+            "user",
+        );
+
+        LayeredSource::new()
+            .with_conflict_policy(policy)
+            .with_source(system)
+            .with_source(user)
+    }
+
+    #[tokio::test]
+    async fn test_layered_source_conflict_overwrite() {
+        let merged = conflicting_layers(ConflictPolicy::Overwrite)
+            .load()
+            .await
+            .unwrap();
+        assert_eq!(
+            merged.get("security").unwrap().get("tls"),
+            Some(&bool_val(false))
+        );
+        assert_eq!(
+            LayeredSource::new().conflict_policy,
+            ConflictPolicy::Overwrite
+        );
+    }
+
+    #[tokio::test]
+    async fn test_layered_source_conflict_error() {
+        match conflicting_layers(ConflictPolicy::Error).load().await {
+            Err(Error::MergeConflict { key, source_name }) => {
+                // Equal values (`security.port`) are not conflicts
+                assert_eq!(key, "security.tls");
+                assert_eq!(source_name, "user");
+            }
+            other => panic!("expected MergeConflict, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_layered_source_conflict_warn() {
+        let merged = conflicting_layers(ConflictPolicy::Warn)
+            .load()
+            .await
+            .unwrap();
+        assert_eq!(
+            merged.get("security").unwrap().get("tls"),
+            Some(&bool_val(false))
+        );
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let base = obj(vec![
+            ("a", obj(vec![("b", int(1)), ("c", int(2))])),
+            ("d", string("x")),
+        ]);
+        let overlay = obj(vec![
+            ("a", obj(vec![("b", int(1)), ("c", int(3))])),
+            ("d", obj(vec![("e", int(1))])),
+            ("f", int(4)),
+        ]);
+
+        let mut conflicts = Vec::new();
+        find_conflicts(&base, &overlay, &mut String::new(), &mut conflicts);
+        conflicts.sort();
+        assert_eq!(conflicts, vec!["a.c", "d"]);
+    }
+
     #[tokio::test]
     async fn test_layered_source_error_propagation() {
         struct FailingSource;
@@ -623,4 +1581,107 @@ mod tests {
         let result = layered.load().await;
         assert!(matches!(result.unwrap_err(), Error::SourceError { .. }));
     }
+
+    struct SlowSource {
+        name: &'static str,
+        delay: Duration,
+        data: ConfigValue,
+    }
+
+    #[async_trait]
+    impl Source for SlowSource {
+        async fn load(&self) -> Result<ConfigValue> {
+            tokio::time::sleep(self.delay).await;
+            Ok(self.data.clone())
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    #[tokio::test]
+    async fn test_layered_source_timeout() {
+        let layered = LayeredSource::new()
+            .with_source(MemorySource::new(obj(vec![("a", int(1))])))
+            .with_source(SlowSource {
+                name: "slow",
+                delay: Duration::from_secs(10),
+                data: obj(vec![]),
+            })
+            .with_timeout(Duration::from_millis(50));
+
+        match layered.load().await.unwrap_err() {
+            Error::SourceError {
+                source_name,
+                source,
+            } => {
+                assert_eq!(source_name, "slow");
+                assert!(source.to_string().contains("timed out"));
+            }
+            other => panic!("expected SourceError, got {:?}", other),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_layered_source_loads_concurrently_in_layer_order() {
+        // The first layer finishes last but is still overridden by the second
+        let layered = LayeredSource::new()
+            .with_source(SlowSource {
+                name: "first",
+                delay: Duration::from_millis(300),
+                data: obj(vec![("a", int(1)), ("b", int(1))]),
+            })
+            .with_source(SlowSource {
+                name: "second",
+                delay: Duration::from_millis(200),
+                data: obj(vec![("a", int(2))]),
+            })
+            .with_timeout(Duration::from_secs(5));
+
+        let started = Instant::now();
+        let value = layered.load().await.unwrap();
+        assert_eq!(started.elapsed(), Duration::from_millis(300));
+
+        assert_eq!(value.get("a").unwrap().as_i64(), Some(2));
+        assert_eq!(value.get("b").unwrap().as_i64(), Some(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_layered_source_load_time_is_max_not_sum() {
+        let slow = |name, data| SlowSource {
+            name,
+            delay: Duration::from_millis(200),
+            data,
+        };
+        let layered = LayeredSource::new()
+            .with_source(slow("file", obj(vec![("a", int(1))])))
+            .with_source(slow("remote", obj(vec![("b", int(2))])))
+            .with_source(slow("env", obj(vec![("a", int(3))])));
+
+        let started = Instant::now();
+        let value = layered.load().await.unwrap();
+        assert_eq!(started.elapsed(), Duration::from_millis(200));
+
+        assert_eq!(value.get("a").unwrap().as_i64(), Some(3));
+        assert_eq!(value.get("b").unwrap().as_i64(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_layered_source_concurrent_error_names_source() {
+        let layered = LayeredSource::new()
+            .with_source(SlowSource {
+                name: "slow",
+                delay: Duration::from_millis(100),
+                data: obj(vec![]),
+            })
+            .with_source(FileSource::new("/nonexistent/prefer/layer.toml"));
+
+        match layered.load().await.unwrap_err() {
+            Error::SourceError { source_name, .. } => {
+                assert_eq!(source_name, "/nonexistent/prefer/layer.toml")
+            }
+            other => panic!("expected SourceError, got {:?}", other),
+        }
+    }
 }
@@ -4,19 +4,34 @@
 //! for converting configuration values to Rust types.
 
 use crate::error::{Error, Result};
+use crate::visitor::{SeqAccess, ValueVisitor};
 
+#[cfg(not(feature = "std"))]
+use alloc::collections::btree_map::Entry as MapEntry;
 #[cfg(not(feature = "std"))]
 use alloc::{
+    borrow::Cow,
+    boxed::Box,
     collections::BTreeMap as HashMap,
     format,
+    rc::Rc,
     string::{String, ToString},
+    sync::Arc,
+    vec,
     vec::Vec,
 };
 #[cfg(not(feature = "std"))]
 use core::fmt;
 
 #[cfg(feature = "std")]
-use std::{collections::HashMap, fmt, hash::Hash};
+use std::{
+    borrow::Cow,
+    collections::{hash_map::Entry as MapEntry, HashMap},
+    fmt,
+    hash::Hash,
+    rc::Rc,
+    sync::Arc,
+};
 
 /// A configuration value that can represent any supported type.
 ///
@@ -30,23 +45,344 @@ pub enum ConfigValue {
     /// Boolean value
     Bool(bool),
     /// Signed 64-bit integer
+    ///
+    /// Parsed integers outside the `i64` range that still fit in `i128` or
+    /// `u128` are stored as decimal strings instead; read them with
+    /// `ConfigValue::as_i128()` or `ConfigValue::as_u128()`.
     Integer(i64),
     /// 64-bit floating point number
     Float(f64),
     /// UTF-8 string
     String(String),
+    /// Raw binary data
+    ///
+    /// Text formatters serialize bytes as a standard base64 string, so they
+    /// load back as a `String` (see `ConfigValue::as_bytes_base64()`).
+    Bytes(Vec<u8>),
     /// Ordered array of values
     Array(Vec<ConfigValue>),
     /// Key-value object/map
     Object(HashMap<String, ConfigValue>),
 }
 
+/// Infer a typed scalar from an untyped string, such as an environment
+/// variable or INI value.
+///
+/// The rules are deliberately conservative, so that values survive a round
+/// trip unchanged whenever a number would be lossy or surprising:
+///
+/// - `true` and `false`, in any case, become `Bool`.
+/// - Decimal integers with an optional sign become `Integer`. Integers that
+///   overflow `i64` are kept as strings.
+/// - Decimal floats (`1.5`, `-2e3`, `.5`) become `Float`, unless they are
+///   not finite (`inf`, `NaN`, `1e999`), which are kept as strings.
+/// - Numbers with leading zeros (`007`, `00.5`) are kept as strings, since
+///   they are usually identifiers such as ZIP codes or file modes.
+/// - Hexadecimal, octal, and binary literals (`0x10`) are kept as strings.
+/// - Anything else, including the empty string, is a `String`.
+pub fn infer_scalar(s: &str) -> ConfigValue {
+    if s.eq_ignore_ascii_case("true") {
+        return ConfigValue::Bool(true);
+    }
+    if s.eq_ignore_ascii_case("false") {
+        return ConfigValue::Bool(false);
+    }
+
+    let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+    let is_numeric_syntax = unsigned
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+        && unsigned.starts_with(|c: char| c.is_ascii_digit() || c == '.');
+    let int_digits = unsigned.split(['.', 'e', 'E']).next().unwrap_or_default();
+    let has_leading_zero = int_digits.len() > 1 && int_digits.starts_with('0');
+
+    if !is_numeric_syntax || has_leading_zero {
+        return ConfigValue::String(s.to_string());
+    }
+
+    if unsigned.chars().all(|c| c.is_ascii_digit()) {
+        return match s.parse::<i64>() {
+            Ok(n) => ConfigValue::Integer(n),
+            Err(_) => ConfigValue::String(s.to_string()),
+        };
+    }
+
+    match s.parse::<f64>() {
+        Ok(n) if n.is_finite() => ConfigValue::Float(n),
+        _ => ConfigValue::String(s.to_string()),
+    }
+}
+
+/// Parse a decimal integer that overflows `i64` but fits in `i128`.
+pub(crate) fn wide_i128(s: &str) -> Option<i128> {
+    s.parse::<i128>()
+        .ok()
+        .filter(|n| i64::try_from(*n).is_err())
+}
+
+/// Parse a decimal integer that overflows `i64` but fits in `u128`.
+pub(crate) fn wide_u128(s: &str) -> Option<u128> {
+    s.parse::<u128>()
+        .ok()
+        .filter(|n| i64::try_from(*n).is_err())
+}
+
+/// Split a JSON Pointer into unescaped tokens, or `None` if malformed.
+fn pointer_tokens(ptr: &str) -> Option<Vec<String>> {
+    if ptr.is_empty() {
+        return Some(Vec::new());
+    }
+    let tokens = ptr.strip_prefix('/')?.split('/');
+    Some(
+        tokens
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect(),
+    )
+}
+
+/// Split a valid, non-empty JSON Pointer into its parent pointer and
+/// unescaped last token.
+fn split_pointer(ptr: &str) -> Option<(&str, String)> {
+    let (parent, last) = ptr.rsplit_once('/')?;
+    Some((parent, pointer_tokens(&format!("/{}", last))?.remove(0)))
+}
+
+/// An error from a JSON Patch operation on `path`.
+fn patch_error(path: &str, message: String) -> Error {
+    Error::ConversionError {
+        key: path.to_string(),
+        type_name: "JSON Patch".into(),
+        source: message.into(),
+    }
+}
+
+/// Parse a JSON Pointer array index: decimal digits without leading zeros.
+fn array_index(token: &str) -> Option<usize> {
+    let canonical = token == "0" || !token.starts_with('0');
+    if canonical && token.bytes().all(|b| b.is_ascii_digit()) {
+        token.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Check that `ConfigValue::pointer_set()` can follow `tokens` from `root`,
+/// without modifying anything.
+fn check_pointer_path(root: &ConfigValue, tokens: &[String], ptr: &str) -> Result<()> {
+    let conflict = |depth: usize, type_name: &str, message: String| {
+        let prefix: Vec<&str> = ptr.split('/').take(depth + 2).collect();
+        Error::ConversionError {
+            key: prefix.join("/"),
+            type_name: type_name.into(),
+            source: message.into(),
+        }
+    };
+
+    // `None` once the path leaves the existing structure
+    let mut current = Some(root).filter(|value| !value.is_null());
+    for (depth, token) in tokens.iter().enumerate() {
+        match current {
+            Some(ConfigValue::Object(map)) => {
+                current = map.get(token).filter(|value| !value.is_null());
+            }
+            Some(ConfigValue::Array(arr)) => {
+                let index = match token.as_str() {
+                    "-" => arr.len(),
+                    _ => array_index(token).ok_or_else(|| {
+                        conflict(depth, "array", format!("invalid array index {:?}", token))
+                    })?,
+                };
+                if index > arr.len() {
+                    return Err(conflict(
+                        depth,
+                        "array",
+                        format!("index {} is past the end of {} elements", index, arr.len()),
+                    ));
+                }
+                current = arr.get(index).filter(|value| !value.is_null());
+            }
+            Some(other) => {
+                return Err(conflict(
+                    depth,
+                    "object",
+                    format!("cannot set {:?} on {}", token, other.type_name()),
+                ));
+            }
+            // A new array starts empty, so only index 0 fits
+            None => match array_index(token) {
+                Some(index) if index > 0 => {
+                    return Err(conflict(
+                        depth,
+                        "array",
+                        format!("index {} is past the end of 0 elements", index),
+                    ));
+                }
+                _ => {}
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Represent an integer literal too large for `Integer`, for formatters.
+///
+/// Returns its canonical decimal string if `text` overflows `i64` but fits
+/// in `i128` or `u128`, and `None` otherwise.
+#[cfg(feature = "std")]
+pub(crate) fn wide_integer(text: &str) -> Option<ConfigValue> {
+    let digits = match wide_i128(text) {
+        Some(n) => n.to_string(),
+        None => wide_u128(text)?.to_string(),
+    };
+    Some(ConfigValue::String(digits))
+}
+
+/// Convert a value, parsing strings into scalars if direct conversion fails.
+///
+/// When `T::from_value` fails on a `String`, the string is parsed with
+/// `T`'s `FromStr`, so `"8080"` and `"08080"` can both become a `u16`. If
+/// that fails too, the string is run through `infer_scalar()` and converted
+/// again, which accepts booleans in any case. The original error is returned
+/// if every retry fails. This is what `#[prefer(coerce)]` uses in derived
+/// `FromValue` impls.
+pub fn from_value_coerced<T: FromValue + core::str::FromStr>(value: &ConfigValue) -> Result<T> {
+    match (T::from_value(value), value) {
+        (Err(e), ConfigValue::String(s)) => match s.parse() {
+            Ok(parsed) => Ok(parsed),
+            Err(_) => T::from_value(&infer_scalar(s)).map_err(|_| e),
+        },
+        (result, _) => result,
+    }
+}
+
+/// Like `from_value_coerced()`, for an optional value that may be `null`.
+pub fn from_value_coerced_option<T: FromValue + core::str::FromStr>(
+    value: &ConfigValue,
+) -> Result<Option<T>> {
+    if value.is_null() {
+        Ok(None)
+    } else {
+        from_value_coerced(value).map(Some)
+    }
+}
+
+/// The kind of a `ConfigValue`, without its payload.
+///
+/// Useful for branching on a value's type without matching on (and
+/// borrowing) the value itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    /// Null/missing value
+    Null,
+    /// Boolean value
+    Bool,
+    /// Signed 64-bit integer
+    Integer,
+    /// 64-bit floating point number
+    Float,
+    /// UTF-8 string
+    String,
+    /// Raw binary data
+    Bytes,
+    /// Ordered array of values
+    Array,
+    /// Key-value object/map
+    Object,
+}
+
+impl ValueKind {
+    /// Returns a human-readable name for this kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValueKind::Null => "null",
+            ValueKind::Bool => "boolean",
+            ValueKind::Integer => "integer",
+            ValueKind::Float => "float",
+            ValueKind::String => "string",
+            ValueKind::Bytes => "bytes",
+            ValueKind::Array => "array",
+            ValueKind::Object => "object",
+        }
+    }
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single leaf-level difference reported by `ConfigValue::diff()`.
+///
+/// Keys are dot-joined paths as produced by `ConfigValue::flatten_keys()`.
+/// The empty key stands for the root, when either side is not an object.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The key exists only in the newer value.
+    Added { key: String, value: ConfigValue },
+    /// The key exists only in the older value.
+    Removed { key: String, value: ConfigValue },
+    /// The key exists in both, with different values.
+    Modified {
+        key: String,
+        old: ConfigValue,
+        new: ConfigValue,
+    },
+}
+
+/// Which number representation `ConfigValue::canonicalize_numbers()`
+/// converts to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberPolicy {
+    /// Whole-number floats become integers.
+    #[default]
+    PreferInteger,
+    /// Integers that fit exactly in an `f64` become floats.
+    PreferFloat,
+}
+
+impl Change {
+    /// Returns the dot-joined key this change applies to.
+    pub fn key(&self) -> &str {
+        match self {
+            Change::Added { key, .. }
+            | Change::Removed { key, .. }
+            | Change::Modified { key, .. } => key,
+        }
+    }
+}
+
 impl ConfigValue {
     /// Returns true if this value is null.
     pub fn is_null(&self) -> bool {
         matches!(self, ConfigValue::Null)
     }
 
+    /// Returns true if this value is empty.
+    ///
+    /// Null and empty strings, arrays, and objects are empty. Booleans and
+    /// numbers are never empty.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            ConfigValue::Null => true,
+            _ => self.len() == Some(0),
+        }
+    }
+
+    /// Returns the number of elements in an Array or Object, or the length
+    /// in bytes of a String or Bytes.
+    ///
+    /// Returns `None` for Null, booleans, and numbers, which have no length.
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            ConfigValue::String(s) => Some(s.len()),
+            ConfigValue::Bytes(bytes) => Some(bytes.len()),
+            ConfigValue::Array(arr) => Some(arr.len()),
+            ConfigValue::Object(obj) => Some(obj.len()),
+            _ => None,
+        }
+    }
+
     /// Returns the boolean value if this is a Bool.
     pub fn as_bool(&self) -> Option<bool> {
         match self {
@@ -71,6 +407,26 @@ impl ConfigValue {
         }
     }
 
+    /// Returns the value as i128 if it's an Integer, or a string holding an
+    /// integer too large for `i64`.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            ConfigValue::Integer(n) => Some((*n).into()),
+            ConfigValue::String(s) => wide_i128(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as u128 if it's a non-negative Integer, or a string
+    /// holding a non-negative integer too large for `i64`.
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            ConfigValue::Integer(n) => u128::try_from(*n).ok(),
+            ConfigValue::String(s) => wide_u128(s),
+            _ => None,
+        }
+    }
+
     /// Returns the float value if this is a Float or Integer.
     pub fn as_f64(&self) -> Option<f64> {
         match self {
@@ -88,6 +444,16 @@ impl ConfigValue {
         }
     }
 
+    /// Returns the raw bytes if this is Bytes.
+    ///
+    /// Base64 strings are not decoded; see `as_bytes_base64()` for those.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            ConfigValue::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
     /// Returns the array if this is an Array.
     pub fn as_array(&self) -> Option<&Vec<ConfigValue>> {
         match self {
@@ -120,38 +486,945 @@ impl ConfigValue {
         }
     }
 
+    /// Iterate over the elements of an Array or the values of an Object.
+    ///
+    /// Object values come in the map's iteration order. Any other value
+    /// yields nothing. To treat a scalar as a one-element list instead,
+    /// iterate `&value` directly.
+    pub fn iter(&self) -> impl Iterator<Item = &ConfigValue> + '_ {
+        let elements = self.as_array().into_iter().flatten();
+        let values = self.as_object().into_iter().flat_map(|obj| obj.values());
+        elements.chain(values)
+    }
+
+    /// Decode a base64 String into bytes.
+    ///
+    /// Returns `None` if this is not a String or is not valid base64.
+    #[cfg(feature = "base64")]
+    pub fn as_bytes_base64(&self) -> Option<Vec<u8>> {
+        use base64::Engine;
+
+        self.as_str()
+            .and_then(|s| base64::engine::general_purpose::STANDARD.decode(s).ok())
+    }
+
+    /// Parse an RFC 3339 datetime String, such as a TOML offset datetime.
+    ///
+    /// Formats store datetimes as strings, so this re-parses the stored
+    /// value. Returns `None` if this is not a String or is not RFC 3339.
+    #[cfg(feature = "chrono")]
+    pub fn as_datetime(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.as_str()
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+    }
+
     /// Get a value from an object by key.
     pub fn get(&self, key: &str) -> Option<&ConfigValue> {
         self.as_object().and_then(|obj| obj.get(key))
     }
 
+    /// Get a value from an object by key, as a `Result`.
+    ///
+    /// Unlike `get()`, this distinguishes a missing key (`KeyNotFound`) from
+    /// a receiver that is not an object (`ConversionError`).
+    pub fn try_get(&self, key: &str) -> Result<&ConfigValue> {
+        let obj = self.as_object().ok_or_else(|| Error::ConversionError {
+            key: String::new(),
+            type_name: "object".into(),
+            source: format!("not an object: found {}", self.type_name()).into(),
+        })?;
+
+        obj.get(key)
+            .ok_or_else(|| Error::KeyNotFound(key.to_string()))
+    }
+
+    /// Whether a value exists at a dotted path.
+    ///
+    /// Paths use the same syntax as `walk()`: object members are joined
+    /// with `.` and array elements are selected with brackets, as in
+    /// `servers[0].host`. The empty path is the value itself.
+    pub fn path_exists(&self, dotted: &str) -> bool {
+        self.find_path(dotted).is_some()
+    }
+
+    /// Whether a value of the given kind exists at a dotted path.
+    ///
+    /// Uses the same path syntax as `path_exists()`. Returns `false` both
+    /// when the path is missing and when the value there has another kind.
+    pub fn matches_type(&self, dotted: &str, kind: ValueKind) -> bool {
+        self.find_path(dotted)
+            .is_some_and(|value| value.kind() == kind)
+    }
+
+    /// Look up a value by RFC 6901 JSON Pointer, such as `/servers/0/host`.
+    ///
+    /// The empty pointer is the value itself. In tokens, `~1` stands for
+    /// `/` and `~0` for `~`. Returns `None` if the pointer is malformed or
+    /// nothing is there.
+    pub fn pointer(&self, ptr: &str) -> Option<&ConfigValue> {
+        let mut current = self;
+        for token in pointer_tokens(ptr)? {
+            current = match current {
+                ConfigValue::Object(map) => map.get(&token)?,
+                ConfigValue::Array(arr) => arr.get(array_index(&token)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Set a value by RFC 6901 JSON Pointer, creating the path as needed.
+    ///
+    /// Missing or null containers along the way are created: an array if
+    /// the next token is a number or `-`, and an object otherwise. On an
+    /// array, a numeric token selects an element, and may be one past the
+    /// end to append; `-` always appends. The empty pointer replaces the
+    /// whole value.
+    ///
+    /// Fails with a `ConversionError` keyed by the pointer prefix if the
+    /// path runs into a scalar, uses a non-numeric token or an index past
+    /// the end on an array, or the pointer is malformed. On error `self` is
+    /// left unchanged.
+    pub fn pointer_set(&mut self, ptr: &str, value: ConfigValue) -> Result<()> {
+        let tokens = pointer_tokens(ptr).ok_or_else(|| Error::ConversionError {
+            key: ptr.to_string(),
+            type_name: "JSON Pointer".into(),
+            source: "must be empty or start with '/'".into(),
+        })?;
+        check_pointer_path(self, &tokens, ptr)?;
+
+        let mut current = self;
+        for token in &tokens {
+            if current.is_null() {
+                *current = if token == "-" || array_index(token).is_some() {
+                    ConfigValue::Array(Vec::new())
+                } else {
+                    ConfigValue::Object(HashMap::new())
+                };
+            }
+            current = match current {
+                ConfigValue::Object(map) => map.entry(token.clone()).or_insert(ConfigValue::Null),
+                ConfigValue::Array(arr) => {
+                    let index = array_index(token).unwrap_or(arr.len());
+                    if index == arr.len() {
+                        arr.push(ConfigValue::Null);
+                    }
+                    &mut arr[index]
+                }
+                _ => unreachable!("checked by check_pointer_path"),
+            };
+        }
+        *current = value;
+        Ok(())
+    }
+
+    /// Apply an RFC 6902 JSON Patch.
+    ///
+    /// `patch` is an array of operation objects, each with an `op` of
+    /// `add`, `remove`, `replace`, `move`, `copy` or `test`, a `path`
+    /// JSON Pointer, and a `value` or `from` pointer as the operation
+    /// requires. As in the RFC, `add` inserts into arrays (`-` appends) and
+    /// needs the parent to exist; use `pointer_set()` to create paths.
+    ///
+    /// Operations are applied in order to a copy, which replaces `self`
+    /// only if all of them succeed. A malformed operation, an invalid path,
+    /// or a failed `test` returns a `ConversionError` keyed by the
+    /// operation's path and leaves `self` unchanged.
+    pub fn apply_patch(&mut self, patch: &ConfigValue) -> Result<()> {
+        let ops = patch.as_array().ok_or_else(|| Error::ConversionError {
+            key: String::new(),
+            type_name: "JSON Patch".into(),
+            source: format!(
+                "expected an array of operations, found {}",
+                patch.type_name()
+            )
+            .into(),
+        })?;
+
+        let mut patched = self.clone();
+        for op in ops {
+            patched.apply_patch_op(op)?;
+        }
+        *self = patched;
+        Ok(())
+    }
+
+    /// Apply one JSON Patch operation.
+    fn apply_patch_op(&mut self, op: &ConfigValue) -> Result<()> {
+        let field = |name: &str| {
+            let field = op
+                .get(name)
+                .and_then(ConfigValue::as_str)
+                .ok_or_else(|| patch_error("", format!("missing string field {:?}", name)))?;
+            match (name, pointer_tokens(field)) {
+                ("path" | "from", None) => Err(patch_error(field, "invalid JSON Pointer".into())),
+                _ => Ok(field),
+            }
+        };
+        let value = || {
+            op.get("value")
+                .cloned()
+                .ok_or_else(|| patch_error("", "missing field \"value\"".into()))
+        };
+        let path = field("path")?;
+
+        match field("op")? {
+            "add" => self.patch_add(path, value()?),
+            "remove" => self.patch_remove(path).map(drop),
+            "replace" => {
+                let target = self
+                    .pointer_mut(path)
+                    .ok_or_else(|| patch_error(path, "no value at path".into()))?;
+                *target = value()?;
+                Ok(())
+            }
+            "move" => {
+                let from = field("from")?;
+                if path.starts_with(from) && path[from.len()..].starts_with('/') {
+                    return Err(patch_error(
+                        path,
+                        format!("cannot move {:?} into itself", from),
+                    ));
+                }
+                let moved = self.patch_remove(from)?;
+                self.patch_add(path, moved)
+            }
+            "copy" => {
+                let from = field("from")?;
+                let copied = self
+                    .pointer(from)
+                    .cloned()
+                    .ok_or_else(|| patch_error(from, "no value at path".into()))?;
+                self.patch_add(path, copied)
+            }
+            "test" => {
+                let expected = value()?;
+                match self.pointer(path) {
+                    Some(actual) if *actual == expected => Ok(()),
+                    Some(actual) => Err(patch_error(
+                        path,
+                        format!("test failed: expected {}, found {}", expected, actual),
+                    )),
+                    None => Err(patch_error(path, "test failed: no value at path".into())),
+                }
+            }
+            other => Err(patch_error(path, format!("unknown op {:?}", other))),
+        }
+    }
+
+    /// The JSON Patch `add` operation.
+    fn patch_add(&mut self, path: &str, value: ConfigValue) -> Result<()> {
+        let Some((parent, token)) = split_pointer(path) else {
+            *self = value;
+            return Ok(());
+        };
+        match self.pointer_mut(parent) {
+            Some(ConfigValue::Object(map)) => {
+                map.insert(token, value);
+                Ok(())
+            }
+            Some(ConfigValue::Array(arr)) => {
+                let index = match token.as_str() {
+                    "-" => arr.len(),
+                    _ => array_index(&token)
+                        .filter(|index| *index <= arr.len())
+                        .ok_or_else(|| patch_error(path, "invalid array index".into()))?,
+                };
+                arr.insert(index, value);
+                Ok(())
+            }
+            _ => Err(patch_error(path, "parent is not an object or array".into())),
+        }
+    }
+
+    /// The JSON Patch `remove` operation, returning the removed value.
+    fn patch_remove(&mut self, path: &str) -> Result<ConfigValue> {
+        let missing = || patch_error(path, "no value at path".into());
+        let (parent, token) = split_pointer(path).ok_or_else(missing)?;
+        match self.pointer_mut(parent) {
+            Some(ConfigValue::Object(map)) => map.remove(&token).ok_or_else(missing),
+            Some(ConfigValue::Array(arr)) => {
+                let index = array_index(&token)
+                    .filter(|index| *index < arr.len())
+                    .ok_or_else(missing)?;
+                Ok(arr.remove(index))
+            }
+            _ => Err(missing()),
+        }
+    }
+
+    /// Look up a mutable value by JSON Pointer. See `pointer()`.
+    fn pointer_mut(&mut self, ptr: &str) -> Option<&mut ConfigValue> {
+        let mut current = self;
+        for token in pointer_tokens(ptr)? {
+            current = match current {
+                ConfigValue::Object(map) => map.get_mut(&token)?,
+                ConfigValue::Array(arr) => arr.get_mut(array_index(&token)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Resolve a `walk()`-style path such as `servers[0].host`.
+    pub(crate) fn find_path(&self, dotted: &str) -> Option<&ConfigValue> {
+        if dotted.is_empty() {
+            return Some(self);
+        }
+
+        let mut current = self;
+        for segment in dotted.split('.') {
+            let (key, mut indices) = match segment.find('[') {
+                Some(pos) => segment.split_at(pos),
+                None => (segment, ""),
+            };
+            if !key.is_empty() {
+                current = current.get(key)?;
+            } else if indices.is_empty() {
+                return None;
+            }
+            while !indices.is_empty() {
+                let (index, rest) = indices.strip_prefix('[')?.split_once(']')?;
+                current = current.as_array()?.get(index.parse::<usize>().ok()?)?;
+                indices = rest;
+            }
+        }
+        Some(current)
+    }
+
     /// Get a mutable value from an object by key.
     pub fn get_mut(&mut self, key: &str) -> Option<&mut ConfigValue> {
         self.as_object_mut().and_then(|obj| obj.get_mut(key))
     }
 
+    /// Get the entry for a key in an object, for in-place insertion or update.
+    ///
+    /// Returns `None` if this is not an Object.
+    pub fn entry(&mut self, key: &str) -> Option<Entry<'_>> {
+        self.as_object_mut().map(|obj| Entry {
+            inner: obj.entry(key.to_string()),
+        })
+    }
+
+    /// Returns the kind of this value.
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            ConfigValue::Null => ValueKind::Null,
+            ConfigValue::Bool(_) => ValueKind::Bool,
+            ConfigValue::Integer(_) => ValueKind::Integer,
+            ConfigValue::Float(_) => ValueKind::Float,
+            ConfigValue::String(_) => ValueKind::String,
+            ConfigValue::Bytes(_) => ValueKind::Bytes,
+            ConfigValue::Array(_) => ValueKind::Array,
+            ConfigValue::Object(_) => ValueKind::Object,
+        }
+    }
+
     /// Returns a human-readable type name for this value.
     pub fn type_name(&self) -> &'static str {
+        self.kind().as_str()
+    }
+
+    /// Call `f` for every node in the tree, with its dotted path.
+    ///
+    /// Nodes are visited in pre-order: a parent before its children. The
+    /// root has the path `""`, object members are joined with `.` (as in
+    /// `server.port`) and array elements use brackets (as in `hosts[0]`).
+    /// Object members are visited in the map's iteration order.
+    pub fn walk<F: FnMut(&str, &ConfigValue)>(&self, f: &mut F) {
+        let mut path = String::new();
+        walk_node(self, &mut path, f);
+    }
+
+    /// Call `f` for every node in the tree, allowing it to be modified.
+    ///
+    /// Visits nodes in the same order and with the same paths as `walk()`.
+    /// Because a parent is visited first, any children `f` gives it are
+    /// visited too.
+    pub fn walk_mut<F: FnMut(&str, &mut ConfigValue)>(&mut self, f: &mut F) {
+        let mut path = String::new();
+        walk_node_mut(self, &mut path, f);
+    }
+
+    /// Keep only the members of an Object for which `f` returns `true`.
+    ///
+    /// `f` receives each member's key and value. Nested values are not
+    /// filtered; use `retain_deep()` for that. Does nothing to other values.
+    pub fn retain<F: FnMut(&str, &ConfigValue) -> bool>(&mut self, mut f: F) {
+        if let ConfigValue::Object(map) = self {
+            map.retain(|key, value| f(key, value));
+        }
+    }
+
+    /// Keep only the elements of an Array for which `f` returns `true`.
+    ///
+    /// `f` receives each element's original index and value. Does nothing
+    /// to other values.
+    pub fn retain_elements<F: FnMut(usize, &ConfigValue) -> bool>(&mut self, mut f: F) {
+        if let ConfigValue::Array(arr) = self {
+            let mut index = 0;
+            arr.retain(|value| {
+                index += 1;
+                f(index - 1, value)
+            });
+        }
+    }
+
+    /// Remove every object member and array element, at any depth, for
+    /// which `f` returns `false`.
+    ///
+    /// `f` receives each node's path, as in `walk()`, and its value; array
+    /// elements keep their original indices in the path. A removed node's
+    /// children are not visited. The root itself is never removed.
+    pub fn retain_deep<F: FnMut(&str, &ConfigValue) -> bool>(&mut self, mut f: F) {
+        let mut path = String::new();
+        retain_node(self, &mut path, &mut f);
+    }
+
+    /// Convert an Array into a `Vec<T>` one element at a time.
+    ///
+    /// Drives a visitor through `ValueVisitor::visit_seq`, pulling each
+    /// element with `SeqAccess::next_element()`, so the array is never
+    /// copied. Gives the same result as `Vec::<T>::from_value()` for arrays,
+    /// with the index added to element errors (e.g. `[2]`).
+    pub fn deserialize_seq<T: FromValue>(&self) -> Result<Vec<T>> {
+        crate::visitor::visit(self, &mut SeqVisitor(core::marker::PhantomData))
+    }
+
+    /// Convert numbers throughout this value to one representation where
+    /// that loses nothing.
+    ///
+    /// With `NumberPolicy::PreferInteger`, a `Float` holding a whole number
+    /// in `i64` range (such as `42.0`) becomes an `Integer`. With
+    /// `NumberPolicy::PreferFloat`, an `Integer` that `f64` represents
+    /// exactly becomes a `Float`. Other numbers are left alone, so values
+    /// from formats with different number models compare equal afterwards.
+    pub fn canonicalize_numbers(&mut self, policy: NumberPolicy) {
+        match self {
+            ConfigValue::Float(f) if policy == NumberPolicy::PreferInteger => {
+                // 2^63 is exactly representable, unlike i64::MAX
+                const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+                if (-LIMIT..LIMIT).contains(f) && (*f as i64) as f64 == *f {
+                    *self = ConfigValue::Integer(*f as i64);
+                }
+            }
+            ConfigValue::Integer(i) if policy == NumberPolicy::PreferFloat => {
+                let f = *i as f64;
+                if f < 9_223_372_036_854_775_808.0 && f as i64 == *i {
+                    *self = ConfigValue::Float(f);
+                }
+            }
+            ConfigValue::Array(arr) => {
+                for item in arr {
+                    item.canonicalize_numbers(policy);
+                }
+            }
+            ConfigValue::Object(map) => {
+                for value in map.values_mut() {
+                    value.canonicalize_numbers(policy);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Flatten an Object into a map of leaf values keyed by their path.
+    ///
+    /// Nested keys are joined with `sep`, so with `"."` the value at
+    /// `server.port` gets the key `"server.port"`. Arrays and empty objects
+    /// are kept whole as leaves. A non-object root yields an empty map.
+    /// `unflatten()` is the inverse.
+    pub fn flatten_keys(&self, sep: &str) -> HashMap<String, ConfigValue> {
+        let mut out = HashMap::new();
+        if let ConfigValue::Object(map) = self {
+            if !map.is_empty() {
+                flatten_into(self, &mut String::new(), sep, &mut out);
+            }
+        }
+        out
+    }
+
+    /// Rebuild a nested Object from a map of `sep`-joined keys.
+    ///
+    /// Keys are applied in sorted order, so when one key is a prefix of
+    /// another (`a` and `a.b`) the nested one wins. The inverse of
+    /// `flatten_keys()`.
+    pub fn unflatten(map: HashMap<String, ConfigValue>, sep: &str) -> ConfigValue {
+        let mut entries: Vec<_> = map.into_iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut root = ConfigValue::Object(HashMap::new());
+        for (key, value) in entries {
+            let parts: Vec<&str> = if sep.is_empty() {
+                core::iter::once(key.as_str()).collect()
+            } else {
+                key.split(sep).collect()
+            };
+            set_nested_value(&mut root, &parts, value);
+        }
+        root
+    }
+
+    /// Compare this value against a newer one, leaf by leaf.
+    ///
+    /// Both sides are flattened with `flatten_keys(".")`, so arrays are
+    /// compared whole. Changes are returned sorted by key. If either side
+    /// is not an object (an array-rooted YAML stream, say), the two roots
+    /// are compared whole and a difference is one `Change::Modified` with
+    /// the empty key.
+    pub fn diff(&self, newer: &ConfigValue) -> Vec<Change> {
+        if !matches!(
+            (self, newer),
+            (ConfigValue::Object(_), ConfigValue::Object(_))
+        ) {
+            if self == newer {
+                return Vec::new();
+            }
+            return vec![Change::Modified {
+                key: String::new(),
+                old: self.clone(),
+                new: newer.clone(),
+            }];
+        }
+
+        let mut old = self.flatten_keys(".");
+        let mut new = newer.flatten_keys(".");
+        let mut keys: Vec<String> = old.keys().chain(new.keys()).cloned().collect();
+        keys.sort();
+        keys.dedup();
+
+        let mut changes = Vec::new();
+        for key in keys {
+            match (old.remove(&key), new.remove(&key)) {
+                (Some(old), Some(new)) if old != new => {
+                    changes.push(Change::Modified { key, old, new })
+                }
+                (None, Some(value)) => changes.push(Change::Added { key, value }),
+                (Some(value), None) => changes.push(Change::Removed { key, value }),
+                _ => {}
+            }
+        }
+        changes
+    }
+}
+
+/// Set a value at a nested path within a `ConfigValue` tree.
+///
+/// Creates intermediate `Object` nodes as needed. If a non-object value
+/// exists at an intermediate path, it is replaced with an object.
+pub fn set_nested_value(root: &mut ConfigValue, path: &[&str], value: ConfigValue) {
+    if path.is_empty() {
+        return;
+    }
+
+    if path.len() == 1 {
+        if let ConfigValue::Object(map) = root {
+            map.insert(path[0].to_string(), value);
+        }
+        return;
+    }
+
+    if let ConfigValue::Object(map) = root {
+        let child = map
+            .entry(path[0].to_string())
+            .or_insert_with(|| ConfigValue::Object(HashMap::new()));
+
+        if !matches!(child, ConfigValue::Object(_)) {
+            *child = ConfigValue::Object(HashMap::new());
+        }
+
+        set_nested_value(child, &path[1..], value);
+    }
+}
+
+fn flatten_into(
+    value: &ConfigValue,
+    prefix: &mut String,
+    sep: &str,
+    out: &mut HashMap<String, ConfigValue>,
+) {
+    match value {
+        ConfigValue::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let len = prefix.len();
+                if !prefix.is_empty() {
+                    prefix.push_str(sep);
+                }
+                prefix.push_str(key);
+                flatten_into(child, prefix, sep, out);
+                prefix.truncate(len);
+            }
+        }
+        leaf => {
+            out.insert(prefix.clone(), leaf.clone());
+        }
+    }
+}
+
+/// Append an object key to a walk path, returning the previous length.
+pub(crate) fn push_key(path: &mut String, key: &str) -> usize {
+    let len = path.len();
+    if !path.is_empty() {
+        path.push('.');
+    }
+    path.push_str(key);
+    len
+}
+
+/// Append an array index to a walk path, returning the previous length.
+pub(crate) fn push_index(path: &mut String, index: usize) -> usize {
+    let len = path.len();
+    path.push_str(&format!("[{index}]"));
+    len
+}
+
+fn walk_node<F: FnMut(&str, &ConfigValue)>(value: &ConfigValue, path: &mut String, f: &mut F) {
+    f(path, value);
+
+    match value {
+        ConfigValue::Object(map) => {
+            for (key, child) in map {
+                let len = push_key(path, key);
+                walk_node(child, path, f);
+                path.truncate(len);
+            }
+        }
+        ConfigValue::Array(arr) => {
+            for (i, child) in arr.iter().enumerate() {
+                let len = push_index(path, i);
+                walk_node(child, path, f);
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn walk_node_mut<F: FnMut(&str, &mut ConfigValue)>(
+    value: &mut ConfigValue,
+    path: &mut String,
+    f: &mut F,
+) {
+    f(path, value);
+
+    match value {
+        ConfigValue::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                let len = push_key(path, key);
+                walk_node_mut(child, path, f);
+                path.truncate(len);
+            }
+        }
+        ConfigValue::Array(arr) => {
+            for (i, child) in arr.iter_mut().enumerate() {
+                let len = push_index(path, i);
+                walk_node_mut(child, path, f);
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn retain_node<F: FnMut(&str, &ConfigValue) -> bool>(
+    value: &mut ConfigValue,
+    path: &mut String,
+    f: &mut F,
+) {
+    match value {
+        ConfigValue::Object(map) => map.retain(|key, child| {
+            let len = push_key(path, key);
+            let keep = f(path, child);
+            if keep {
+                retain_node(child, path, f);
+            }
+            path.truncate(len);
+            keep
+        }),
+        ConfigValue::Array(arr) => {
+            let mut index = 0;
+            arr.retain_mut(|child| {
+                let len = push_index(path, index);
+                index += 1;
+                let keep = f(path, child);
+                if keep {
+                    retain_node(child, path, f);
+                }
+                path.truncate(len);
+                keep
+            });
+        }
+        _ => {}
+    }
+}
+
+/// A view into a single key of an Object, returned by `ConfigValue::entry()`.
+pub struct Entry<'a> {
+    inner: MapEntry<'a, String, ConfigValue>,
+}
+
+impl<'a> Entry<'a> {
+    /// Returns the key of this entry.
+    pub fn key(&self) -> &str {
+        self.inner.key()
+    }
+
+    /// Insert `default` if the key is absent, and return the value.
+    pub fn or_insert(self, default: ConfigValue) -> &'a mut ConfigValue {
+        self.inner.or_insert(default)
+    }
+
+    /// Insert the result of `default` if the key is absent, and return the value.
+    pub fn or_insert_with<F: FnOnce() -> ConfigValue>(self, default: F) -> &'a mut ConfigValue {
+        self.inner.or_insert_with(default)
+    }
+
+    /// Modify the value in place if the key is present.
+    pub fn and_modify<F: FnOnce(&mut ConfigValue)>(self, f: F) -> Self {
+        Self {
+            inner: self.inner.and_modify(f),
+        }
+    }
+}
+
+impl ConfigValue {
+    /// Write an indented, multi-line rendering with object keys sorted.
+    fn fmt_pretty(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        const INDENT: &str = "  ";
+
+        match self {
+            ConfigValue::Array(arr) if !arr.is_empty() => {
+                writeln!(f, "[")?;
+                for (i, v) in arr.iter().enumerate() {
+                    write!(f, "{}", INDENT.repeat(depth + 1))?;
+                    v.fmt_pretty(f, depth + 1)?;
+                    writeln!(f, "{}", if i + 1 < arr.len() { "," } else { "" })?;
+                }
+                write!(f, "{}]", INDENT.repeat(depth))
+            }
+            ConfigValue::Object(obj) if !obj.is_empty() => {
+                let mut entries: Vec<_> = obj.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+
+                writeln!(f, "{{")?;
+                for (i, (k, v)) in entries.iter().enumerate() {
+                    write!(f, "{}\"{}\": ", INDENT.repeat(depth + 1), k)?;
+                    v.fmt_pretty(f, depth + 1)?;
+                    writeln!(f, "{}", if i + 1 < entries.len() { "," } else { "" })?;
+                }
+                write!(f, "{}}}", INDENT.repeat(depth))
+            }
+            other => write!(f, "{}", other),
+        }
+    }
+
+    /// Serialize to compact JSON.
+    ///
+    /// Needs only `alloc`, so it is available without the `std` feature and
+    /// the JSON formatter. Object keys are sorted, strings are escaped per
+    /// RFC 8259, floats always keep a decimal point or exponent, and NaN
+    /// and infinities become `null`. `Bytes` are written as a standard
+    /// base64 string, as the JSON formatter writes them.
+    pub fn to_json_string(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    fn write_json<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
         match self {
-            ConfigValue::Null => "null",
-            ConfigValue::Bool(_) => "boolean",
-            ConfigValue::Integer(_) => "integer",
-            ConfigValue::Float(_) => "float",
-            ConfigValue::String(_) => "string",
-            ConfigValue::Array(_) => "array",
-            ConfigValue::Object(_) => "object",
+            ConfigValue::Null => out.write_str("null"),
+            ConfigValue::Bool(b) => write!(out, "{}", b),
+            ConfigValue::Integer(n) => write!(out, "{}", n),
+            ConfigValue::Float(n) if n.is_finite() => write!(out, "{:?}", n),
+            ConfigValue::Float(_) => out.write_str("null"),
+            ConfigValue::String(s) => write_json_string(s, out),
+            ConfigValue::Bytes(bytes) => write_json_base64(bytes, out),
+            ConfigValue::Array(arr) => {
+                out.write_char('[')?;
+                for (i, v) in arr.iter().enumerate() {
+                    if i > 0 {
+                        out.write_char(',')?;
+                    }
+                    v.write_json(out)?;
+                }
+                out.write_char(']')
+            }
+            ConfigValue::Object(obj) => {
+                let mut entries: Vec<_> = obj.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+
+                out.write_char('{')?;
+                for (i, (k, v)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        out.write_char(',')?;
+                    }
+                    write_json_string(k, out)?;
+                    out.write_char(':')?;
+                    v.write_json(out)?;
+                }
+                out.write_char('}')
+            }
         }
     }
+
+    /// A content hash that is the same for structurally equal values.
+    ///
+    /// Object keys are hashed in sorted order, floats by their bits with
+    /// every NaN treated alike and `-0.0` as `0.0`. The algorithm (64-bit
+    /// FNV-1a) is fixed, so hashes are stable across runs, platforms, and
+    /// Rust versions and can be stored, e.g. as an ETag or to skip
+    /// reloads whose content is unchanged.
+    ///
+    /// `Integer(1)` and `Float(1.0)` hash differently, as they are not
+    /// `==`; see `canonicalize_numbers()`.
+    #[cfg(feature = "stable_hash")]
+    pub fn stable_hash(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        self.hash_into(&mut hasher);
+        hasher.finish()
+    }
+
+    #[cfg(feature = "stable_hash")]
+    fn hash_into(&self, h: &mut StableHasher) {
+        match self {
+            ConfigValue::Null => h.write(&[0]),
+            ConfigValue::Bool(b) => h.write(&[1, *b as u8]),
+            ConfigValue::Integer(n) => {
+                h.write(&[2]);
+                h.write(&n.to_le_bytes());
+            }
+            ConfigValue::Float(f) => {
+                let bits = if f.is_nan() {
+                    f64::NAN.to_bits()
+                } else if *f == 0.0 {
+                    0
+                } else {
+                    f.to_bits()
+                };
+                h.write(&[3]);
+                h.write(&bits.to_le_bytes());
+            }
+            ConfigValue::String(s) => {
+                h.write(&[4]);
+                h.write_len_prefixed(s.as_bytes());
+            }
+            ConfigValue::Bytes(bytes) => {
+                h.write(&[5]);
+                h.write_len_prefixed(bytes);
+            }
+            ConfigValue::Array(arr) => {
+                h.write(&[6]);
+                h.write(&(arr.len() as u64).to_le_bytes());
+                for item in arr {
+                    item.hash_into(h);
+                }
+            }
+            ConfigValue::Object(obj) => {
+                let mut entries: Vec<_> = obj.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+
+                h.write(&[7]);
+                h.write(&(entries.len() as u64).to_le_bytes());
+                for (key, value) in entries {
+                    h.write_len_prefixed(key.as_bytes());
+                    value.hash_into(h);
+                }
+            }
+        }
+    }
+}
+
+/// 64-bit FNV-1a, used by `ConfigValue::stable_hash()`.
+#[cfg(feature = "stable_hash")]
+struct StableHasher(u64);
+
+#[cfg(feature = "stable_hash")]
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Write a length before the bytes, so adjacent fields can't run
+    /// together (`"ab","c"` vs `"a","bc"`).
+    fn write_len_prefixed(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Write `s` as a quoted JSON string.
+fn write_json_string<W: fmt::Write>(s: &str, out: &mut W) -> fmt::Result {
+    out.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            '\u{8}' => out.write_str("\\b")?,
+            '\u{c}' => out.write_str("\\f")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+/// Write bytes as a quoted, padded standard base64 string.
+///
+/// Hand-rolled so `to_json_string()` doesn't need the `base64` crate.
+fn write_json_base64<W: fmt::Write>(bytes: &[u8], out: &mut W) -> fmt::Result {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    out.write_char('"')?;
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (u32::from(b) << (16 - 8 * i)));
+        // A chunk of k bytes fills k + 1 characters; the rest is padding
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.write_char(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char)?;
+            } else {
+                out.write_char('=')?;
+            }
+        }
+    }
+    out.write_char('"')
 }
 
+/// The default format is compact and single-line. The alternate format
+/// (`{:#}`) is indented by two spaces per level, with object keys sorted.
 impl fmt::Display for ConfigValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return self.fmt_pretty(f, 0);
+        }
+
         match self {
             ConfigValue::Null => write!(f, "null"),
             ConfigValue::Bool(b) => write!(f, "{}", b),
             ConfigValue::Integer(n) => write!(f, "{}", n),
             ConfigValue::Float(n) => write!(f, "{}", n),
             ConfigValue::String(s) => write!(f, "\"{}\"", s),
+            ConfigValue::Bytes(bytes) => write!(f, "{:?}", bytes),
             ConfigValue::Array(arr) => {
                 write!(f, "[")?;
                 for (i, v) in arr.iter().enumerate() {
@@ -176,6 +1449,37 @@ impl fmt::Display for ConfigValue {
     }
 }
 
+/// Iterates a value as a list: an Array yields its elements, `Null`
+/// yields nothing, and any other value (including an Object) yields
+/// itself once.
+impl IntoIterator for ConfigValue {
+    type Item = ConfigValue;
+    type IntoIter = <Vec<ConfigValue> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            ConfigValue::Array(arr) => arr.into_iter(),
+            ConfigValue::Null => Vec::new().into_iter(),
+            other => vec![other].into_iter(),
+        }
+    }
+}
+
+/// Borrowing counterpart of `impl IntoIterator for ConfigValue`, with the
+/// same list semantics.
+impl<'a> IntoIterator for &'a ConfigValue {
+    type Item = &'a ConfigValue;
+    type IntoIter = core::slice::Iter<'a, ConfigValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            ConfigValue::Array(arr) => arr.iter(),
+            ConfigValue::Null => [].iter(),
+            other => core::slice::from_ref(other).iter(),
+        }
+    }
+}
+
 // Convenient From implementations
 impl From<bool> for ConfigValue {
     fn from(v: bool) -> Self {
@@ -269,10 +1573,86 @@ impl From<()> for ConfigValue {
 pub trait FromValue: Sized {
     /// Attempt to construct Self from a configuration value.
     fn from_value(value: &ConfigValue) -> Result<Self>;
+
+    /// Attempt to construct a `Vec<Self>` from a configuration value.
+    ///
+    /// `Vec<T>` delegates here so that element types can accept
+    /// representations other than an array. `u8` overrides it to read
+    /// base64 strings as bytes.
+    #[doc(hidden)]
+    fn vec_from_value(value: &ConfigValue) -> Result<Vec<Self>> {
+        array_from_value(value)
+    }
+
+    /// The top-level object keys `from_value()` reads, if known.
+    ///
+    /// Used by `WithExtras` to tell which keys are left over. The default
+    /// is `None` (unknown). `#[derive(FromValue)]` implements it for
+    /// structs, except those with a flattened map field, which already
+    /// capture the remaining keys themselves.
+    fn consumed_keys() -> Option<Vec<String>> {
+        None
+    }
+}
+
+/// Convert an Array into a `Vec<T>`, adding the index to element errors.
+fn array_from_value<T: FromValue>(value: &ConfigValue) -> Result<Vec<T>> {
+    let arr = value.as_array().ok_or_else(|| Error::ConversionError {
+        key: String::new(),
+        type_name: "Vec".into(),
+        source: format!("expected array, found {}", value.type_name()).into(),
+    })?;
+
+    arr.iter()
+        .enumerate()
+        .map(|(i, v)| T::from_value(v).map_err(|e| e.with_key(format!("[{i}]"))))
+        .collect()
+}
+
+/// Collects a sequence into a `Vec<T>` for `ConfigValue::deserialize_seq()`.
+struct SeqVisitor<T>(core::marker::PhantomData<T>);
+
+impl<T: FromValue> ValueVisitor for SeqVisitor<T> {
+    type Output = Vec<T>;
+
+    fn expecting(&self) -> &'static str {
+        "Vec"
+    }
+
+    fn visit_seq(&mut self, mut seq: SeqAccess<'_>) -> Result<Self::Output> {
+        let mut out = Vec::with_capacity(seq.len());
+        loop {
+            let index = seq.position();
+            match seq.next_element() {
+                Ok(Some(item)) => out.push(item),
+                Ok(None) => return Ok(out),
+                Err(e) => return Err(e.with_key(format!("[{index}]"))),
+            }
+        }
+    }
+
+    fn visit_array(&mut self, arr: &[ConfigValue]) -> Result<Self::Output> {
+        self.visit_seq(SeqAccess::new(arr))
+    }
 }
 
 // Primitive type implementations
 
+/// Converts an Integer value to a fixed-width integer type, telling a value
+/// of the wrong type apart from one that doesn't fit in `T`.
+fn integer_in_range<T: TryFrom<i64>>(value: &ConfigValue, type_name: &str) -> Result<T> {
+    let n = value.as_i64().ok_or_else(|| Error::ConversionError {
+        key: String::new(),
+        type_name: type_name.into(),
+        source: format!("expected {}, found {}", type_name, value.type_name()).into(),
+    })?;
+    T::try_from(n).map_err(|_| Error::ConversionError {
+        key: String::new(),
+        type_name: type_name.into(),
+        source: format!("value {} out of range for {}", n, type_name).into(),
+    })
+}
+
 impl FromValue for bool {
     fn from_value(value: &ConfigValue) -> Result<Self> {
         value.as_bool().ok_or_else(|| Error::ConversionError {
@@ -285,40 +1665,19 @@ impl FromValue for bool {
 
 impl FromValue for i8 {
     fn from_value(value: &ConfigValue) -> Result<Self> {
-        value
-            .as_i64()
-            .and_then(|n| i8::try_from(n).ok())
-            .ok_or_else(|| Error::ConversionError {
-                key: String::new(),
-                type_name: "i8".into(),
-                source: format!("expected i8, found {}", value.type_name()).into(),
-            })
+        integer_in_range(value, "i8")
     }
 }
 
 impl FromValue for i16 {
     fn from_value(value: &ConfigValue) -> Result<Self> {
-        value
-            .as_i64()
-            .and_then(|n| i16::try_from(n).ok())
-            .ok_or_else(|| Error::ConversionError {
-                key: String::new(),
-                type_name: "i16".into(),
-                source: format!("expected i16, found {}", value.type_name()).into(),
-            })
+        integer_in_range(value, "i16")
     }
 }
 
 impl FromValue for i32 {
     fn from_value(value: &ConfigValue) -> Result<Self> {
-        value
-            .as_i64()
-            .and_then(|n| i32::try_from(n).ok())
-            .ok_or_else(|| Error::ConversionError {
-                key: String::new(),
-                type_name: "i32".into(),
-                source: format!("expected i32, found {}", value.type_name()).into(),
-            })
+        integer_in_range(value, "i32")
     }
 }
 
@@ -334,50 +1693,86 @@ impl FromValue for i64 {
 
 impl FromValue for u8 {
     fn from_value(value: &ConfigValue) -> Result<Self> {
-        value
-            .as_u64()
-            .and_then(|n| u8::try_from(n).ok())
-            .ok_or_else(|| Error::ConversionError {
-                key: String::new(),
-                type_name: "u8".into(),
-                source: format!("expected u8, found {}", value.type_name()).into(),
-            })
+        integer_in_range(value, "u8")
+    }
+
+    /// Bytes may be given as a Bytes value, an array of integers 0–255, or
+    /// (with the `base64` feature) as a base64-encoded string.
+    fn vec_from_value(value: &ConfigValue) -> Result<Vec<Self>> {
+        if let Some(bytes) = value.as_bytes() {
+            return Ok(bytes.to_vec());
+        }
+
+        #[cfg(feature = "base64")]
+        if value.as_str().is_some() {
+            return value
+                .as_bytes_base64()
+                .ok_or_else(|| Error::ConversionError {
+                    key: String::new(),
+                    type_name: "Vec<u8>".into(),
+                    source: "invalid base64 string".into(),
+                });
+        }
+
+        array_from_value(value)
+    }
+}
+
+impl FromValue for u16 {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        integer_in_range(value, "u16")
+    }
+}
+
+impl FromValue for u32 {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        integer_in_range(value, "u32")
     }
 }
 
-impl FromValue for u16 {
+/// Also reads wide integers above `i64::MAX` (see `ConfigValue::as_u128()`).
+impl FromValue for u64 {
     fn from_value(value: &ConfigValue) -> Result<Self> {
-        value
-            .as_u64()
-            .and_then(|n| u16::try_from(n).ok())
-            .ok_or_else(|| Error::ConversionError {
+        match value.as_u128() {
+            Some(n) => u64::try_from(n).map_err(|_| Error::ConversionError {
                 key: String::new(),
-                type_name: "u16".into(),
-                source: format!("expected u16, found {}", value.type_name()).into(),
-            })
+                type_name: "u64".into(),
+                source: format!("value {} out of range for u64", n).into(),
+            }),
+            None => integer_in_range(value, "u64"),
+        }
     }
 }
 
-impl FromValue for u32 {
+/// The error for a value that `as_i128()`/`as_u128()` rejected.
+fn wide_integer_error(value: &ConfigValue, type_name: &str) -> Error {
+    let source = match value {
+        ConfigValue::Integer(n) => format!("value {} out of range for {}", n, type_name),
+        ConfigValue::String(s) if wide_i128(s).is_some() || wide_u128(s).is_some() => {
+            format!("value {} out of range for {}", s, type_name)
+        }
+        _ => format!("expected {}, found {}", type_name, value.type_name()),
+    };
+    Error::ConversionError {
+        key: String::new(),
+        type_name: type_name.into(),
+        source: source.into(),
+    }
+}
+
+impl FromValue for i128 {
     fn from_value(value: &ConfigValue) -> Result<Self> {
         value
-            .as_u64()
-            .and_then(|n| u32::try_from(n).ok())
-            .ok_or_else(|| Error::ConversionError {
-                key: String::new(),
-                type_name: "u32".into(),
-                source: format!("expected u32, found {}", value.type_name()).into(),
-            })
+            .as_i128()
+            .ok_or_else(|| wide_integer_error(value, "i128"))
     }
 }
 
-impl FromValue for u64 {
+impl FromValue for u128 {
     fn from_value(value: &ConfigValue) -> Result<Self> {
-        value.as_u64().ok_or_else(|| Error::ConversionError {
-            key: String::new(),
-            type_name: "u64".into(),
-            source: format!("expected u64, found {}", value.type_name()).into(),
-        })
+        value
+            .as_u128()
+            .ok_or_else(|| wide_integer_error(value, "u128"))
     }
 }
 
@@ -411,6 +1806,44 @@ impl FromValue for isize {
     }
 }
 
+/// Implement `FromValue` for a `NonZero*` type via its primitive integer.
+macro_rules! impl_from_value_non_zero {
+    ($($non_zero:ident => $int:ty),* $(,)?) => {
+        $(
+            impl FromValue for core::num::$non_zero {
+                fn from_value(value: &ConfigValue) -> Result<Self> {
+                    let n = <$int>::from_value(value).map_err(|e| match e {
+                        Error::ConversionError { key, source, .. } => Error::ConversionError {
+                            key,
+                            type_name: stringify!($non_zero).into(),
+                            source,
+                        },
+                        other => other,
+                    })?;
+                    Self::new(n).ok_or_else(|| Error::ConversionError {
+                        key: String::new(),
+                        type_name: stringify!($non_zero).into(),
+                        source: "value must be non-zero".into(),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value_non_zero! {
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroUsize => usize,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroIsize => isize,
+}
+
 impl FromValue for f32 {
     fn from_value(value: &ConfigValue) -> Result<Self> {
         value
@@ -434,16 +1867,90 @@ impl FromValue for f64 {
     }
 }
 
+/// Borrow a String value, reporting `type_name` if it isn't one.
+fn str_from_value<'a>(value: &'a ConfigValue, type_name: &str) -> Result<&'a str> {
+    value.as_str().ok_or_else(|| Error::ConversionError {
+        key: String::new(),
+        type_name: type_name.into(),
+        source: format!("expected string, found {}", value.type_name()).into(),
+    })
+}
+
 impl FromValue for String {
     fn from_value(value: &ConfigValue) -> Result<Self> {
-        value
-            .as_str()
-            .map(String::from)
-            .ok_or_else(|| Error::ConversionError {
+        str_from_value(value, "String").map(String::from)
+    }
+}
+
+impl FromValue for Cow<'static, str> {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        str_from_value(value, "Cow<str>").map(|s| Cow::Owned(s.to_string()))
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl FromValue for compact_str::CompactString {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        str_from_value(value, "CompactString").map(compact_str::CompactString::from)
+    }
+}
+
+#[cfg(feature = "smol_str")]
+impl FromValue for smol_str::SmolStr {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        str_from_value(value, "SmolStr").map(smol_str::SmolStr::from)
+    }
+}
+
+impl FromValue for char {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        let s = value.as_str().ok_or_else(|| Error::ConversionError {
+            key: String::new(),
+            type_name: "char".into(),
+            source: format!("expected string, found {}", value.type_name()).into(),
+        })?;
+
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(Error::ConversionError {
                 key: String::new(),
-                type_name: "String".into(),
-                source: format!("expected string, found {}", value.type_name()).into(),
-            })
+                type_name: "char".into(),
+                source: format!("expected a single character, found {:?}", s).into(),
+            }),
+        }
+    }
+}
+
+/// Accepts a non-negative number of seconds (integer or float), or a
+/// human-readable string such as `"30s"`, `"1500ms"` or `"1h30m"`.
+impl FromValue for core::time::Duration {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        use core::time::Duration;
+
+        let parsed = match value {
+            ConfigValue::Integer(n) => u64::try_from(*n).ok().map(Duration::from_secs),
+            ConfigValue::Float(f) => Duration::try_from_secs_f64(*f).ok(),
+            ConfigValue::String(s) => crate::duration::parse_duration(s),
+            other => {
+                return Err(Error::ConversionError {
+                    key: String::new(),
+                    type_name: "Duration".into(),
+                    source: format!("expected string or number, found {}", other.type_name())
+                        .into(),
+                })
+            }
+        };
+
+        parsed.ok_or_else(|| Error::ConversionError {
+            key: String::new(),
+            type_name: "Duration".into(),
+            source: format!(
+                "invalid duration {}, expected seconds or a string like \"30s\", \"1500ms\" or \"1h30m\"",
+                value
+            )
+            .into(),
+        })
     }
 }
 
@@ -467,20 +1974,26 @@ impl FromValue for ConfigValue {
     }
 }
 
+/// Ignores the value, so a unit field accepts anything (or nothing).
+impl FromValue for () {
+    fn from_value(_value: &ConfigValue) -> Result<Self> {
+        Ok(())
+    }
+}
+
+/// Ignores the value, so marker fields in generic structs need no
+/// `#[prefer(skip)]`.
+impl<T: ?Sized> FromValue for core::marker::PhantomData<T> {
+    fn from_value(_value: &ConfigValue) -> Result<Self> {
+        Ok(core::marker::PhantomData)
+    }
+}
+
 // Collection implementations
 
 impl<T: FromValue> FromValue for Vec<T> {
     fn from_value(value: &ConfigValue) -> Result<Self> {
-        let arr = value.as_array().ok_or_else(|| Error::ConversionError {
-            key: String::new(),
-            type_name: "Vec".into(),
-            source: format!("expected array, found {}", value.type_name()).into(),
-        })?;
-
-        arr.iter()
-            .enumerate()
-            .map(|(i, v)| T::from_value(v).map_err(|e| e.with_key(format!("[{i}]"))))
-            .collect()
+        T::vec_from_value(value)
     }
 }
 
@@ -494,6 +2007,294 @@ impl<T: FromValue> FromValue for Option<T> {
     }
 }
 
+/// An object with a single `ok` or `err` key, such as a status snapshot.
+///
+/// `{ "ok": <value> }` becomes `Ok`, with the value converted to `T`, and
+/// `{ "err": "<message>" }` becomes `Err` with the message. Any other shape,
+/// including an object with both keys, is a conversion error.
+impl<T: FromValue> FromValue for core::result::Result<T, String> {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        let invalid = || Error::ConversionError {
+            key: String::new(),
+            type_name: "Result".into(),
+            source: format!(
+                "expected an object with a single \"ok\" or \"err\" key, found {}",
+                value.type_name()
+            )
+            .into(),
+        };
+
+        let obj = value.as_object().filter(|obj| obj.len() == 1);
+        let (tag, inner) = obj.and_then(|obj| obj.iter().next()).ok_or_else(invalid)?;
+        match tag.as_str() {
+            "ok" => T::from_value(inner).map(Ok).map_err(|e| e.with_key("ok")),
+            "err" => String::from_value(inner)
+                .map(Err)
+                .map_err(|e| e.with_key("err")),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// A value that may be written as either a single item or an array.
+///
+/// Lets a field accept both `hosts = "a"` and `hosts = ["a", "b"]`. A
+/// single item becomes a one-element list; an array is converted element by
+/// element, so an empty array gives an empty list.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OneOrMany<T>(pub Vec<T>);
+
+impl<T> OneOrMany<T> {
+    /// Consume the wrapper and return the items.
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> core::ops::Deref for OneOrMany<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> From<OneOrMany<T>> for Vec<T> {
+    fn from(value: OneOrMany<T>) -> Self {
+        value.0
+    }
+}
+
+impl<T: FromValue> FromValue for OneOrMany<T> {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        match value {
+            ConfigValue::Array(_) | ConfigValue::Bytes(_) => Vec::from_value(value).map(OneOrMany),
+            single => T::from_value(single).map(|item| OneOrMany(Vec::from([item]))),
+        }
+    }
+}
+
+/// An optional value that distinguishes a missing key from an explicit
+/// `null`.
+///
+/// `Option<T>` reads both as `None`. As a derived struct field, `Maybe<T>`
+/// is `Absent` when the key is missing, `Null` when it is `null`, and
+/// `Value` otherwise. Converting a value directly never yields `Absent`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Maybe<T> {
+    /// The key was not present.
+    #[default]
+    Absent,
+    /// The key was present with a `null` value.
+    Null,
+    /// The key was present with a value.
+    Value(T),
+}
+
+impl<T> Maybe<T> {
+    /// Whether the key was missing.
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Maybe::Absent)
+    }
+
+    /// Whether the key was present with a `null` value.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Maybe::Null)
+    }
+
+    /// The value, if there was one, discarding the absent/null distinction.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Maybe::Value(value) => Some(value),
+            Maybe::Absent | Maybe::Null => None,
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Maybe<T> {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        if value.is_null() {
+            Ok(Maybe::Null)
+        } else {
+            T::from_value(value).map(Maybe::Value)
+        }
+    }
+}
+
+/// A number that may also be written as a string.
+///
+/// Accepts whatever `T` accepts, plus strings holding a decimal integer or
+/// float (`"8080"`, `" -1.5 "`), for sources that quote every value. Only
+/// fields wrapped in `StrNum` are loosened; the primitive impls still
+/// reject numeric strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct StrNum<T>(pub T);
+
+impl<T> StrNum<T> {
+    /// Consume the wrapper and return the number.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> core::ops::Deref for StrNum<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: FromValue> FromValue for StrNum<T> {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        let ConfigValue::String(s) = value else {
+            return T::from_value(value).map(StrNum);
+        };
+
+        let trimmed = s.trim();
+        let number = if let Ok(n) = trimmed.parse::<i64>() {
+            ConfigValue::Integer(n)
+        } else if let Some(f) = trimmed.parse::<f64>().ok().filter(|f| f.is_finite()) {
+            ConfigValue::Float(f)
+        } else {
+            return Err(Error::ConversionError {
+                key: String::new(),
+                type_name: core::any::type_name::<T>().into(),
+                source: format!("expected a number or numeric string, found '{}'", s).into(),
+            });
+        };
+        T::from_value(&number).map(StrNum)
+    }
+}
+
+/// A value together with the top-level keys it didn't read.
+///
+/// Converts `T` as usual and collects every object key outside
+/// `T::consumed_keys()` into `extras`, so unknown settings survive for
+/// forward compatibility. `T` must report its keys, which derived structs
+/// do; other types are a `ConversionError`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithExtras<T> {
+    /// The converted value.
+    pub value: T,
+    /// Top-level keys that `T` does not read.
+    pub extras: HashMap<String, ConfigValue>,
+}
+
+impl<T: FromValue> FromValue for WithExtras<T> {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        let consumed = T::consumed_keys().ok_or_else(|| Error::ConversionError {
+            key: String::new(),
+            type_name: "WithExtras".into(),
+            source: format!(
+                "{} does not report the keys it reads",
+                core::any::type_name::<T>()
+            )
+            .into(),
+        })?;
+
+        let inner = T::from_value(value)?;
+        let obj = value.as_object().ok_or_else(|| Error::ConversionError {
+            key: String::new(),
+            type_name: "WithExtras".into(),
+            source: format!("expected object, found {}", value.type_name()).into(),
+        })?;
+
+        let extras = obj
+            .iter()
+            .filter(|(key, _)| !consumed.contains(key))
+            .map(|(key, v)| (key.clone(), v.clone()))
+            .collect();
+        Ok(Self {
+            value: inner,
+            extras,
+        })
+    }
+}
+
+// Date/time implementations
+
+#[cfg(feature = "chrono")]
+impl FromValue for chrono::DateTime<chrono::FixedOffset> {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        value.as_datetime().ok_or_else(|| Error::ConversionError {
+            key: String::new(),
+            type_name: "DateTime".into(),
+            source: format!("expected RFC 3339 datetime, found {}", value.type_name()).into(),
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromValue for chrono::DateTime<chrono::Utc> {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        chrono::DateTime::<chrono::FixedOffset>::from_value(value).map(|dt| dt.to_utc())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromValue for chrono::NaiveDate {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        value
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| Error::ConversionError {
+                key: String::new(),
+                type_name: "NaiveDate".into(),
+                source: format!("expected YYYY-MM-DD date, found {}", value.type_name()).into(),
+            })
+    }
+}
+
+/// Reads a string exactly, or an integer. Floats are rejected, since they
+/// may already have lost precision.
+#[cfg(feature = "rust_decimal")]
+impl FromValue for rust_decimal::Decimal {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        let source = match value {
+            ConfigValue::String(s) => match s.trim().parse() {
+                Ok(decimal) => return Ok(decimal),
+                Err(_) => format!("'{}' is not a decimal number", s),
+            },
+            ConfigValue::Integer(n) => return Ok((*n).into()),
+            ConfigValue::Float(f) => format!(
+                "float {} may have lost precision; write it as a string, e.g. \"{}\"",
+                f, f
+            ),
+            other => format!(
+                "expected decimal string or integer, found {}",
+                other.type_name()
+            ),
+        };
+
+        Err(Error::ConversionError {
+            key: String::new(),
+            type_name: "Decimal".into(),
+            source: source.into(),
+        })
+    }
+}
+
+// Smart pointer implementations
+
+impl<T: FromValue> FromValue for Box<T> {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        T::from_value(value).map(Box::new)
+    }
+}
+
+impl<T: FromValue> FromValue for Rc<T> {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        T::from_value(value).map(Rc::new)
+    }
+}
+
+impl<T: FromValue> FromValue for Arc<T> {
+    fn from_value(value: &ConfigValue) -> Result<Self> {
+        T::from_value(value).map(Arc::new)
+    }
+}
+
 #[cfg(feature = "std")]
 impl<K, V> FromValue for HashMap<K, V>
 where
@@ -589,6 +2390,67 @@ mod tests {
         assert!(i8::from_value(&int(1000)).is_err()); // overflow
     }
 
+    #[test]
+    fn test_from_value_wide_integers() {
+        let max = string("170141183460469231731687303715884105727");
+        assert_eq!(max.as_i128(), Some(i128::MAX));
+        assert_eq!(i128::from_value(&max).unwrap(), i128::MAX);
+        assert_eq!(u128::from_value(&max).unwrap(), i128::MAX as u128);
+        assert_eq!(i128::from_value(&int(-5)).unwrap(), -5);
+        assert_eq!(u128::from_value(&int(5)).unwrap(), 5);
+
+        let u64_max = string("18446744073709551615");
+        assert_eq!(u64::from_value(&u64_max).unwrap(), u64::MAX);
+        assert_eq!(u64::from_value(&int(7)).unwrap(), 7);
+        let err = u64::from_value(&max).unwrap_err();
+        assert!(err.to_string().contains("out of range for u64"), "{err}");
+        let err = u64::from_value(&int(-1)).unwrap_err();
+        assert!(
+            err.to_string().contains("value -1 out of range for u64"),
+            "{err}"
+        );
+
+        let beyond = string("340282366920938463463374607431768211455");
+        assert_eq!(u128::from_value(&beyond).unwrap(), u128::MAX);
+        let err = i128::from_value(&beyond).unwrap_err();
+        assert!(err.to_string().contains("out of range for i128"), "{err}");
+
+        let err = u128::from_value(&int(-1)).unwrap_err();
+        assert!(
+            err.to_string().contains("value -1 out of range for u128"),
+            "{err}"
+        );
+
+        // Small numeric strings are not integers, as with i64
+        assert_eq!(string("42").as_i128(), None);
+        let err = i128::from_value(&string("42")).unwrap_err();
+        assert!(
+            err.to_string().contains("expected i128, found string"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_from_value_integer_out_of_range_message() {
+        let err = i8::from_value(&int(1000)).unwrap_err();
+        assert!(
+            err.to_string().contains("value 1000 out of range for i8"),
+            "{err}"
+        );
+
+        let err = u16::from_value(&int(-1)).unwrap_err();
+        assert!(
+            err.to_string().contains("value -1 out of range for u16"),
+            "{err}"
+        );
+
+        let err = i8::from_value(&string("1")).unwrap_err();
+        assert!(
+            err.to_string().contains("expected i8, found string"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn test_from_value_floats() {
         assert!((f64::from_value(&float(1.5)).unwrap() - 1.5).abs() < f64::EPSILON);
@@ -603,18 +2465,215 @@ mod tests {
         assert!(String::from_value(&int(123)).is_err());
     }
 
+    #[test]
+    fn test_from_value_cow() {
+        let cow = Cow::<'static, str>::from_value(&string("hello")).unwrap();
+        assert!(matches!(cow, Cow::Owned(ref s) if s == "hello"));
+
+        match Cow::<'static, str>::from_value(&int(1)) {
+            Err(Error::ConversionError { type_name, .. }) => assert_eq!(type_name, "Cow<str>"),
+            other => panic!("expected ConversionError, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "compact_str")]
+    #[test]
+    fn test_from_value_compact_str() {
+        let s = compact_str::CompactString::from_value(&string("hello")).unwrap();
+        assert_eq!(s, "hello");
+        assert!(compact_str::CompactString::from_value(&int(1)).is_err());
+    }
+
+    #[cfg(feature = "smol_str")]
+    #[test]
+    fn test_from_value_smol_str() {
+        let s = smol_str::SmolStr::from_value(&string("hello")).unwrap();
+        assert_eq!(s, "hello");
+        assert!(smol_str::SmolStr::from_value(&int(1)).is_err());
+    }
+
     #[test]
     fn test_from_value_vec() {
         let result: Vec<i32> = Vec::from_value(&array(vec![int(1), int(2), int(3)])).unwrap();
         assert_eq!(result, vec![1, 2, 3]);
     }
 
+    #[test]
+    fn test_from_value_bytes_array() {
+        let result: Vec<u8> = Vec::from_value(&array(vec![int(0), int(127), int(255)])).unwrap();
+        assert_eq!(result, vec![0, 127, 255]);
+
+        let err = Vec::<u8>::from_value(&array(vec![int(1), int(256)])).unwrap_err();
+        match err {
+            Error::ConversionError { key, .. } => assert_eq!(key, "[1]"),
+            _ => panic!("expected ConversionError"),
+        }
+        assert!(Vec::<u8>::from_value(&array(vec![int(-1)])).is_err());
+    }
+
+    #[test]
+    fn test_bytes_variant() {
+        let value = ConfigValue::Bytes(vec![0, 127, 255]);
+        assert_eq!(value.as_bytes(), Some(&[0u8, 127, 255][..]));
+        assert_eq!(value.kind(), ValueKind::Bytes);
+        assert_eq!(value.type_name(), "bytes");
+        assert_eq!(value.len(), Some(3));
+        assert_eq!(format!("{}", value), "[0, 127, 255]");
+        assert_eq!(Vec::<u8>::from_value(&value).unwrap(), vec![0, 127, 255]);
+
+        assert_eq!(string("AAEC").as_bytes(), None);
+        assert!(ConfigValue::Bytes(vec![]).is_empty());
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_as_bytes_base64() {
+        assert_eq!(
+            string("aGVsbG8=").as_bytes_base64(),
+            Some(b"hello".to_vec())
+        );
+        assert_eq!(string("not base64!").as_bytes_base64(), None);
+        assert_eq!(int(42).as_bytes_base64(), None);
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_from_value_bytes_base64() {
+        let result: Vec<u8> = Vec::from_value(&string("AAEC/w==")).unwrap();
+        assert_eq!(result, vec![0, 1, 2, 255]);
+
+        assert!(Vec::<u8>::from_value(&string("%%%")).is_err());
+        // Other element types still require arrays
+        assert!(Vec::<i32>::from_value(&string("AAEC/w==")).is_err());
+    }
+
+    #[test]
+    fn test_from_value_char() {
+        assert_eq!(char::from_value(&string("x")).unwrap(), 'x');
+        assert_eq!(char::from_value(&string("é")).unwrap(), 'é');
+        assert!(char::from_value(&string("")).is_err());
+        assert!(char::from_value(&string("ab")).is_err());
+        assert!(char::from_value(&int(65)).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_as_datetime() {
+        let dt = string("2024-01-15T10:30:00+02:00").as_datetime().unwrap();
+        assert_eq!(dt.to_rfc3339(), "2024-01-15T10:30:00+02:00");
+
+        assert!(string("yesterday").as_datetime().is_none());
+        assert!(int(1705314600).as_datetime().is_none());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_from_value_chrono() {
+        use chrono::{DateTime, NaiveDate, Utc};
+
+        let utc = DateTime::<Utc>::from_value(&string("2024-01-15T12:30:00+02:00")).unwrap();
+        assert_eq!(utc.to_rfc3339(), "2024-01-15T10:30:00+00:00");
+
+        let date = NaiveDate::from_value(&string("2024-01-15")).unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+
+        assert!(DateTime::<Utc>::from_value(&string("2024-01-15")).is_err());
+        assert!(NaiveDate::from_value(&string("not a date")).is_err());
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_from_value_decimal() {
+        use rust_decimal::Decimal;
+
+        let price = Decimal::from_value(&string("19.99")).unwrap();
+        assert_eq!(price, Decimal::new(1999, 2));
+        assert_eq!(price.to_string(), "19.99");
+        assert_eq!(Decimal::from_value(&int(42)).unwrap(), Decimal::from(42));
+
+        match Decimal::from_value(&float(19.99)) {
+            Err(Error::ConversionError { source, .. }) => {
+                assert!(source.to_string().contains("\"19.99\""))
+            }
+            other => panic!("expected ConversionError, got {:?}", other),
+        }
+        assert!(Decimal::from_value(&string("cheap")).is_err());
+        assert!(Decimal::from_value(&bool_val(true)).is_err());
+    }
+
+    #[test]
+    fn test_from_value_smart_pointers() {
+        assert_eq!(*Box::<u16>::from_value(&int(8080)).unwrap(), 8080);
+        assert_eq!(*Rc::<String>::from_value(&string("a")).unwrap(), "a");
+        assert!(*Arc::<bool>::from_value(&bool_val(true)).unwrap());
+        assert!(Box::<u16>::from_value(&int(70000)).is_err());
+    }
+
+    #[test]
+    fn test_from_value_non_zero() {
+        use core::num::{NonZeroI32, NonZeroU16, NonZeroUsize};
+
+        assert_eq!(
+            NonZeroUsize::from_value(&int(8)).unwrap(),
+            NonZeroUsize::new(8).unwrap()
+        );
+        assert_eq!(
+            NonZeroI32::from_value(&int(-3)).unwrap(),
+            NonZeroI32::new(-3).unwrap()
+        );
+
+        match NonZeroU16::from_value(&int(0)) {
+            Err(Error::ConversionError {
+                type_name, source, ..
+            }) => {
+                assert_eq!(type_name, "NonZeroU16");
+                assert_eq!(source.to_string(), "value must be non-zero");
+            }
+            other => panic!("expected ConversionError, got {:?}", other),
+        }
+
+        // Range and type errors come from the underlying integer conversion
+        assert!(NonZeroU16::from_value(&int(70_000)).is_err());
+        assert!(NonZeroU16::from_value(&string("8")).is_err());
+    }
+
     #[test]
     fn test_from_value_option() {
         assert_eq!(Option::<i32>::from_value(&ConfigValue::Null).unwrap(), None);
         assert_eq!(Option::<i32>::from_value(&int(42)).unwrap(), Some(42));
     }
 
+    #[test]
+    fn test_from_value_result() {
+        type Status = core::result::Result<u16, String>;
+
+        let ok = obj(vec![("ok", int(200))]);
+        assert_eq!(Status::from_value(&ok).unwrap(), Ok(200));
+
+        let err = obj(vec![("err", string("timed out"))]);
+        assert_eq!(
+            Status::from_value(&err).unwrap(),
+            Err("timed out".to_string())
+        );
+
+        match Status::from_value(&obj(vec![("ok", string("fine"))])) {
+            Err(Error::ConversionError { key, .. }) => assert_eq!(key, "ok"),
+            other => panic!("expected ConversionError, got {:?}", other),
+        }
+
+        for invalid in [
+            int(1),
+            obj(vec![]),
+            obj(vec![("status", int(200))]),
+            obj(vec![("ok", int(200)), ("err", string("no"))]),
+        ] {
+            match Status::from_value(&invalid) {
+                Err(Error::ConversionError { type_name, .. }) => assert_eq!(type_name, "Result"),
+                other => panic!("expected ConversionError, got {:?}", other),
+            }
+        }
+    }
+
     #[test]
     fn test_from_value_hashmap() {
         let result: HashMap<String, i32> =
@@ -675,6 +2734,27 @@ mod tests {
         assert_eq!(format!("{}", empty), "{}");
     }
 
+    #[test]
+    fn test_config_value_display_alternate() {
+        let value = obj(vec![
+            ("b", array(vec![int(1), int(2)])),
+            ("a", obj(vec![("host", string("localhost"))])),
+            ("c", array(vec![])),
+        ]);
+
+        assert_eq!(
+            format!("{:#}", value),
+            "{\n  \"a\": {\n    \"host\": \"localhost\"\n  },\n  \"b\": [\n    1,\n    2\n  ],\n  \"c\": []\n}"
+        );
+
+        let compact = format!("{}", value);
+        assert!(!compact.contains('\n'));
+        assert!(compact.contains("\"host\": \"localhost\""));
+
+        assert_eq!(format!("{:#}", int(7)), "7");
+        assert_eq!(format!("{:#}", obj(vec![])), "{}");
+    }
+
     #[test]
     fn test_config_value_display_float() {
         assert_eq!(format!("{}", float(1.5)), "1.5");
@@ -688,6 +2768,34 @@ mod tests {
         assert!(!string("").is_null());
     }
 
+    #[test]
+    fn test_is_empty() {
+        assert!(ConfigValue::Null.is_empty());
+        assert!(string("").is_empty());
+        assert!(array(vec![]).is_empty());
+        assert!(obj(vec![]).is_empty());
+
+        assert!(!string("a").is_empty());
+        assert!(!array(vec![ConfigValue::Null]).is_empty());
+        assert!(!obj(vec![("a", int(1))]).is_empty());
+        assert!(!bool_val(false).is_empty());
+        assert!(!int(0).is_empty());
+        assert!(!float(0.0).is_empty());
+    }
+
+    #[test]
+    fn test_len() {
+        assert_eq!(string("héllo").len(), Some(6));
+        assert_eq!(array(vec![int(1), int(2)]).len(), Some(2));
+        assert_eq!(obj(vec![("a", int(1))]).len(), Some(1));
+        assert_eq!(obj(vec![]).len(), Some(0));
+
+        assert_eq!(ConfigValue::Null.len(), None);
+        assert_eq!(bool_val(true).len(), None);
+        assert_eq!(int(10).len(), None);
+        assert_eq!(float(1.5).len(), None);
+    }
+
     #[test]
     fn test_as_u64() {
         // Positive number
@@ -760,6 +2868,861 @@ mod tests {
         assert_eq!(obj(vec![]).type_name(), "object");
     }
 
+    #[test]
+    fn test_kind() {
+        assert_eq!(ConfigValue::Null.kind(), ValueKind::Null);
+        assert_eq!(ConfigValue::Bool(true).kind(), ValueKind::Bool);
+        assert_eq!(int(1).kind(), ValueKind::Integer);
+        assert_eq!(float(1.0).kind(), ValueKind::Float);
+        assert_eq!(string("").kind(), ValueKind::String);
+        assert_eq!(array(vec![]).kind(), ValueKind::Array);
+        assert_eq!(obj(vec![]).kind(), ValueKind::Object);
+    }
+
+    #[test]
+    fn test_kind_display_matches_type_name() {
+        let values = [
+            ConfigValue::Null,
+            ConfigValue::Bool(false),
+            int(1),
+            float(1.0),
+            string("s"),
+            array(vec![]),
+            obj(vec![]),
+        ];
+        for value in &values {
+            assert_eq!(value.kind().to_string(), value.type_name());
+        }
+    }
+
+    #[test]
+    fn test_entry_insert_if_absent() {
+        let mut value = obj(vec![("a", int(1))]);
+
+        *value.entry("b").unwrap().or_insert(int(2)) = int(3);
+        value.entry("a").unwrap().or_insert(int(100));
+        value.entry("c").unwrap().or_insert_with(|| string("new"));
+
+        assert_eq!(value.get("a"), Some(&int(1)));
+        assert_eq!(value.get("b"), Some(&int(3)));
+        assert_eq!(value.get("c"), Some(&string("new")));
+    }
+
+    #[test]
+    fn test_entry_modify_if_present() {
+        let mut value = obj(vec![("count", int(1))]);
+
+        for key in ["count", "other"] {
+            value
+                .entry(key)
+                .unwrap()
+                .and_modify(|v| *v = int(v.as_i64().unwrap() + 1))
+                .or_insert(int(0));
+        }
+
+        assert_eq!(value.get("count"), Some(&int(2)));
+        assert_eq!(value.get("other"), Some(&int(0)));
+        assert_eq!(value.entry("count").unwrap().key(), "count");
+    }
+
+    #[test]
+    fn test_entry_on_scalar() {
+        assert!(int(1).entry("a").is_none());
+        assert!(array(vec![]).entry("a").is_none());
+    }
+
+    #[test]
+    fn test_walk_visits_every_node() {
+        let value = obj(vec![
+            ("name", string("app")),
+            (
+                "server",
+                obj(vec![("hosts", array(vec![string("a"), string("b")]))]),
+            ),
+        ]);
+
+        let mut paths = Vec::new();
+        value.walk(&mut |path, _| paths.push(path.to_string()));
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            vec![
+                "",
+                "name",
+                "server",
+                "server.hosts",
+                "server.hosts[0]",
+                "server.hosts[1]"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_pre_order() {
+        let value = obj(vec![("a", array(vec![int(1)]))]);
+
+        let mut order = Vec::new();
+        value.walk(&mut |path, _| order.push(path.to_string()));
+        assert_eq!(order, vec!["", "a", "a[0]"]);
+    }
+
+    #[test]
+    fn test_walk_mut_string_leaves() {
+        let mut value = obj(vec![
+            ("name", string("app")),
+            ("port", int(8080)),
+            ("tags", array(vec![string("x"), string("y")])),
+        ]);
+
+        value.walk_mut(&mut |_, v| {
+            if let ConfigValue::String(s) = v {
+                *s = s.to_uppercase();
+            }
+        });
+
+        assert_eq!(value.get("name"), Some(&string("APP")));
+        assert_eq!(value.get("port"), Some(&int(8080)));
+        assert_eq!(
+            value.get("tags"),
+            Some(&array(vec![string("X"), string("Y")]))
+        );
+    }
+
+    #[test]
+    fn test_retain_keys_with_prefix() {
+        let mut value = obj(vec![
+            ("app_name", string("demo")),
+            ("app_port", int(8080)),
+            ("internal", obj(vec![("app_secret", string("x"))])),
+        ]);
+
+        value.retain(|key, _| key.starts_with("app_"));
+
+        assert_eq!(
+            value,
+            obj(vec![("app_name", string("demo")), ("app_port", int(8080))])
+        );
+    }
+
+    #[test]
+    fn test_retain_elements() {
+        let mut value = array(vec![int(1), int(2), int(3), int(4)]);
+        value.retain_elements(|i, v| i != 0 && v.as_i64().is_some_and(|n| n % 2 == 0));
+        assert_eq!(value, array(vec![int(2), int(4)]));
+
+        // Other kinds are left alone
+        let mut value = string("x");
+        value.retain_elements(|_, _| false);
+        value.retain(|_, _| false);
+        assert_eq!(value, string("x"));
+    }
+
+    #[test]
+    fn test_deserialize_seq_matches_from_value() {
+        let ints = array(vec![int(1), int(2), int(3)]);
+        assert_eq!(
+            ints.deserialize_seq::<i64>().unwrap(),
+            Vec::<i64>::from_value(&ints).unwrap()
+        );
+
+        let nested = array(vec![
+            array(vec![string("a")]),
+            array(vec![]),
+            array(vec![string("b"), string("c")]),
+        ]);
+        assert_eq!(
+            nested.deserialize_seq::<Vec<String>>().unwrap(),
+            Vec::<Vec<String>>::from_value(&nested).unwrap()
+        );
+
+        let empty = array(vec![]);
+        assert!(empty.deserialize_seq::<bool>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_seq_errors() {
+        let mixed = array(vec![int(1), string("two"), int(3)]);
+        let Err(Error::ConversionError { key, .. }) = mixed.deserialize_seq::<i64>() else {
+            panic!("expected ConversionError");
+        };
+        assert_eq!(key, "[1]");
+        let Err(Error::ConversionError { key, .. }) = Vec::<i64>::from_value(&mixed) else {
+            panic!("expected ConversionError");
+        };
+        assert_eq!(key, "[1]");
+
+        assert!(string("x").deserialize_seq::<i64>().is_err());
+        assert!(obj(vec![]).deserialize_seq::<i64>().is_err());
+    }
+
+    #[test]
+    fn test_canonicalize_numbers_prefer_integer() {
+        let mut value = obj(vec![
+            ("port", float(42.0)),
+            ("ratio", float(0.5)),
+            ("big", float(1e300)),
+            ("nan", float(f64::NAN)),
+            ("neg", float(-3.0)),
+            (
+                "nested",
+                obj(vec![(
+                    "list",
+                    array(vec![float(1.0), int(2), string("3.0")]),
+                )]),
+            ),
+        ]);
+        value.canonicalize_numbers(NumberPolicy::PreferInteger);
+
+        assert_eq!(value.get("port"), Some(&int(42)));
+        assert_eq!(value.get("ratio"), Some(&float(0.5)));
+        assert_eq!(value.get("big"), Some(&float(1e300)));
+        assert!(value.get("nan").unwrap().as_f64().unwrap().is_nan());
+        assert_eq!(value.get("neg"), Some(&int(-3)));
+        assert_eq!(
+            value.get("nested").unwrap().get("list"),
+            Some(&array(vec![int(1), int(2), string("3.0")]))
+        );
+
+        let mut edge = array(vec![
+            float(-9_223_372_036_854_775_808.0),
+            float(9_223_372_036_854_775_808.0),
+        ]);
+        edge.canonicalize_numbers(NumberPolicy::default());
+        assert_eq!(
+            edge,
+            array(vec![int(i64::MIN), float(9_223_372_036_854_775_808.0)])
+        );
+    }
+
+    #[cfg(feature = "stable_hash")]
+    #[test]
+    fn test_stable_hash_equal_for_equal_values() {
+        let a = obj(vec![
+            ("name", string("app")),
+            (
+                "server",
+                obj(vec![("port", int(8080)), ("host", string("h"))]),
+            ),
+            ("tags", array(vec![string("x"), float(0.5)])),
+        ]);
+        let mut b = obj(vec![]);
+        for (key, value) in [
+            ("tags", array(vec![string("x"), float(0.5)])),
+            (
+                "server",
+                obj(vec![("host", string("h")), ("port", int(8080))]),
+            ),
+            ("name", string("app")),
+        ] {
+            b.as_object_mut().unwrap().insert(key.to_string(), value);
+        }
+
+        assert_eq!(a, b);
+        assert_eq!(a.stable_hash(), b.stable_hash());
+        assert_eq!(a.stable_hash(), a.clone().stable_hash());
+
+        assert_eq!(
+            float(f64::NAN).stable_hash(),
+            float(-f64::NAN).stable_hash()
+        );
+        assert_eq!(float(0.0).stable_hash(), float(-0.0).stable_hash());
+
+        // Pins the algorithm: FNV-1a over the Null tag byte
+        assert_eq!(ConfigValue::Null.stable_hash(), 0xaf63_bd4c_8601_b7df);
+    }
+
+    #[cfg(feature = "stable_hash")]
+    #[test]
+    fn test_stable_hash_differs_for_changes() {
+        let base = obj(vec![("server", obj(vec![("port", int(8080))]))]);
+        let changed = obj(vec![("server", obj(vec![("port", int(8081))]))]);
+        let renamed = obj(vec![("server", obj(vec![("prt", int(8080))]))]);
+        assert_ne!(base.stable_hash(), changed.stable_hash());
+        assert_ne!(base.stable_hash(), renamed.stable_hash());
+
+        assert_ne!(int(1).stable_hash(), float(1.0).stable_hash());
+        assert_ne!(string("1").stable_hash(), int(1).stable_hash());
+        assert_ne!(ConfigValue::Null.stable_hash(), obj(vec![]).stable_hash());
+        assert_ne!(obj(vec![]).stable_hash(), array(vec![]).stable_hash());
+        assert_ne!(
+            array(vec![string("ab"), string("c")]).stable_hash(),
+            array(vec![string("a"), string("bc")]).stable_hash()
+        );
+        assert_ne!(
+            ConfigValue::Bytes(vec![1, 2]).stable_hash(),
+            array(vec![int(1), int(2)]).stable_hash()
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_numbers_makes_formats_equal() {
+        let mut from_json = obj(vec![("port", float(8080.0)), ("timeout", float(1.5))]);
+        let mut from_yaml = obj(vec![("port", int(8080)), ("timeout", float(1.5))]);
+        assert_ne!(from_json, from_yaml);
+
+        from_json.canonicalize_numbers(NumberPolicy::PreferInteger);
+        from_yaml.canonicalize_numbers(NumberPolicy::PreferInteger);
+        assert_eq!(from_json, from_yaml);
+    }
+
+    #[test]
+    fn test_canonicalize_numbers_prefer_float() {
+        let mut value = array(vec![int(42), int(i64::MAX), int((1 << 53) + 1), float(0.5)]);
+        value.canonicalize_numbers(NumberPolicy::PreferFloat);
+        assert_eq!(
+            value,
+            array(vec![
+                float(42.0),
+                int(i64::MAX),
+                int((1 << 53) + 1),
+                float(0.5)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_into_iter_array() {
+        let value = array(vec![int(1), string("two"), int(3)]);
+
+        let borrowed: Vec<&ConfigValue> = (&value).into_iter().collect();
+        assert_eq!(borrowed, vec![&int(1), &string("two"), &int(3)]);
+
+        let mut sum = 0;
+        for item in &value {
+            sum += item.as_i64().unwrap_or(0);
+        }
+        assert_eq!(sum, 4);
+
+        let owned: Vec<ConfigValue> = value.into_iter().collect();
+        assert_eq!(owned, vec![int(1), string("two"), int(3)]);
+
+        assert_eq!(array(vec![]).into_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_into_iter_scalar_and_null() {
+        let scalar = string("only");
+        assert_eq!((&scalar).into_iter().collect::<Vec<_>>(), vec![&scalar]);
+        assert_eq!(scalar.clone().into_iter().collect::<Vec<_>>(), vec![scalar]);
+
+        let object = obj(vec![("a", int(1))]);
+        assert_eq!(object.clone().into_iter().collect::<Vec<_>>(), vec![object]);
+
+        assert_eq!((&ConfigValue::Null).into_iter().count(), 0);
+        assert_eq!(ConfigValue::Null.into_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_iter() {
+        let value = array(vec![int(1), int(2)]);
+        assert_eq!(value.iter().collect::<Vec<_>>(), vec![&int(1), &int(2)]);
+
+        let value = obj(vec![("a", int(1)), ("b", int(2))]);
+        let mut values: Vec<i64> = value.iter().filter_map(|v| v.as_i64()).collect();
+        values.sort();
+        assert_eq!(values, vec![1, 2]);
+
+        assert_eq!(int(5).iter().count(), 0);
+        assert_eq!(ConfigValue::Null.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_retain_deep() {
+        let mut value = obj(vec![
+            (
+                "plugins",
+                array(vec![
+                    obj(vec![("name", string("a")), ("enabled", bool_val(true))]),
+                    obj(vec![("name", string("b")), ("enabled", bool_val(false))]),
+                    obj(vec![("name", string("c")), ("_internal", int(1))]),
+                ]),
+            ),
+            ("_internal", string("x")),
+        ]);
+
+        let mut seen = Vec::new();
+        value.retain_deep(|path, v| {
+            seen.push(path.to_string());
+            !path.ends_with("_internal")
+                && v.get("enabled").and_then(ConfigValue::as_bool) != Some(false)
+        });
+
+        assert_eq!(
+            value,
+            obj(vec![(
+                "plugins",
+                array(vec![
+                    obj(vec![("name", string("a")), ("enabled", bool_val(true))]),
+                    obj(vec![("name", string("c"))]),
+                ]),
+            )])
+        );
+        // Removed nodes are not descended into, and indices are original
+        assert!(seen.contains(&"plugins[2].name".to_string()));
+        assert!(!seen.contains(&"plugins[1].name".to_string()));
+    }
+
+    #[test]
+    fn test_pointer() {
+        let value = obj(vec![
+            ("servers", array(vec![obj(vec![("host", string("a"))])])),
+            ("a/b", obj(vec![("m~n", int(1))])),
+        ]);
+        assert_eq!(value.pointer(""), Some(&value));
+        assert_eq!(value.pointer("/servers/0/host"), Some(&string("a")));
+        assert_eq!(value.pointer("/a~1b/m~0n"), Some(&int(1)));
+        assert_eq!(value.pointer("/servers/01"), None);
+        assert_eq!(value.pointer("/servers/1"), None);
+        assert_eq!(value.pointer("servers"), None);
+    }
+
+    #[test]
+    fn test_pointer_set_creates_path() {
+        let mut value = ConfigValue::Null;
+        value.pointer_set("/server/tls/port", int(443)).unwrap();
+        value.pointer_set("/server/hosts/0", string("a")).unwrap();
+        assert_eq!(
+            value,
+            obj(vec![(
+                "server",
+                obj(vec![
+                    ("tls", obj(vec![("port", int(443))])),
+                    ("hosts", array(vec![string("a")])),
+                ])
+            )])
+        );
+
+        value.pointer_set("", int(1)).unwrap();
+        assert_eq!(value, int(1));
+    }
+
+    #[test]
+    fn test_pointer_set_arrays() {
+        let mut value = obj(vec![("tags", array(vec![string("a")]))]);
+        value.pointer_set("/tags/-", string("b")).unwrap();
+        value.pointer_set("/tags/2", string("c")).unwrap();
+        value.pointer_set("/tags/0", string("z")).unwrap();
+        assert_eq!(
+            value.get("tags"),
+            Some(&array(vec![string("z"), string("b"), string("c")]))
+        );
+
+        assert!(value.pointer_set("/tags/9", string("x")).is_err());
+        assert!(value.pointer_set("/tags/01", string("x")).is_err());
+    }
+
+    fn patch_doc() -> ConfigValue {
+        obj(vec![
+            ("name", string("app")),
+            ("tags", array(vec![string("a"), string("c")])),
+            ("server", obj(vec![("port", int(80))])),
+        ])
+    }
+
+    #[test]
+    fn test_apply_patch_add_remove_replace() {
+        let mut value = patch_doc();
+        value
+            .apply_patch(&array(vec![
+                obj(vec![
+                    ("op", string("add")),
+                    ("path", string("/tags/1")),
+                    ("value", string("b")),
+                ]),
+                obj(vec![
+                    ("op", string("add")),
+                    ("path", string("/tags/-")),
+                    ("value", string("d")),
+                ]),
+                obj(vec![
+                    ("op", string("add")),
+                    ("path", string("/server/host")),
+                    ("value", string("localhost")),
+                ]),
+                obj(vec![("op", string("remove")), ("path", string("/name"))]),
+                obj(vec![
+                    ("op", string("replace")),
+                    ("path", string("/server/port")),
+                    ("value", int(443)),
+                ]),
+            ]))
+            .unwrap();
+
+        assert_eq!(
+            value,
+            obj(vec![
+                (
+                    "tags",
+                    array(vec![string("a"), string("b"), string("c"), string("d")])
+                ),
+                (
+                    "server",
+                    obj(vec![("port", int(443)), ("host", string("localhost"))])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_move_copy_test() {
+        let mut value = patch_doc();
+        value
+            .apply_patch(&array(vec![
+                obj(vec![
+                    ("op", string("test")),
+                    ("path", string("/server/port")),
+                    ("value", int(80)),
+                ]),
+                obj(vec![
+                    ("op", string("copy")),
+                    ("from", string("/tags/0")),
+                    ("path", string("/primary")),
+                ]),
+                obj(vec![
+                    ("op", string("move")),
+                    ("from", string("/name")),
+                    ("path", string("/server/name")),
+                ]),
+            ]))
+            .unwrap();
+
+        assert_eq!(value.pointer("/primary"), Some(&string("a")));
+        assert_eq!(value.pointer("/server/name"), Some(&string("app")));
+        assert_eq!(value.pointer("/name"), None);
+        assert_eq!(value.pointer("/tags/0"), Some(&string("a")));
+    }
+
+    #[test]
+    fn test_apply_patch_failure_rolls_back() {
+        let mut value = patch_doc();
+        let result = value.apply_patch(&array(vec![
+            obj(vec![("op", string("remove")), ("path", string("/name"))]),
+            obj(vec![
+                ("op", string("test")),
+                ("path", string("/server/port")),
+                ("value", int(443)),
+            ]),
+        ]));
+
+        match result {
+            Err(Error::ConversionError { key, source, .. }) => {
+                assert_eq!(key, "/server/port");
+                assert!(source.to_string().contains("test failed"), "{source}");
+            }
+            other => panic!("expected ConversionError, got {:?}", other),
+        }
+        assert_eq!(value, patch_doc());
+
+        for op in [
+            obj(vec![("op", string("remove")), ("path", string("/missing"))]),
+            obj(vec![
+                ("op", string("add")),
+                ("path", string("/missing/key")),
+                ("value", int(1)),
+            ]),
+            obj(vec![
+                ("op", string("add")),
+                ("path", string("/tags/5")),
+                ("value", int(1)),
+            ]),
+            obj(vec![
+                ("op", string("move")),
+                ("from", string("/server")),
+                ("path", string("/server/inner")),
+            ]),
+            obj(vec![("op", string("frobnicate")), ("path", string(""))]),
+            obj(vec![("op", string("remove")), ("path", string("name"))]),
+        ] {
+            assert!(value.apply_patch(&array(vec![op])).is_err());
+        }
+        assert!(value.apply_patch(&obj(vec![])).is_err());
+        assert_eq!(value, patch_doc());
+    }
+
+    #[test]
+    fn test_pointer_set_type_conflict() {
+        let mut value = obj(vec![
+            ("tags", array(vec![string("a")])),
+            ("name", string("app")),
+        ]);
+        let before = value.clone();
+
+        match value.pointer_set("/tags/first", int(1)) {
+            Err(Error::ConversionError { key, type_name, .. }) => {
+                assert_eq!(key, "/tags/first");
+                assert_eq!(type_name, "array");
+            }
+            other => panic!("expected ConversionError, got {:?}", other),
+        }
+        assert!(value.pointer_set("/name/first", int(1)).is_err());
+        assert!(value.pointer_set("/new/deep/5", int(1)).is_err());
+        assert!(value.pointer_set("no-slash", int(1)).is_err());
+        assert_eq!(value, before);
+    }
+
+    #[test]
+    fn test_path_exists() {
+        let value = obj(vec![
+            ("server", obj(vec![("port", int(8080))])),
+            (
+                "replicas",
+                array(vec![obj(vec![("host", string("a"))]), string("b")]),
+            ),
+            ("matrix", array(vec![array(vec![int(1), int(2)])])),
+            ("nothing", ConfigValue::Null),
+        ]);
+
+        assert!(value.path_exists(""));
+        assert!(value.path_exists("server"));
+        assert!(value.path_exists("server.port"));
+        assert!(value.path_exists("replicas[0].host"));
+        assert!(value.path_exists("replicas[1]"));
+        assert!(value.path_exists("matrix[0][1]"));
+        assert!(value.path_exists("nothing"));
+
+        assert!(!value.path_exists("server.host"));
+        assert!(!value.path_exists("server.port.value"));
+        assert!(!value.path_exists("replicas[2]"));
+        assert!(!value.path_exists("replicas[x]"));
+        assert!(!value.path_exists("replicas[0"));
+        assert!(!value.path_exists("server[0]"));
+        assert!(!value.path_exists("server..port"));
+    }
+
+    #[test]
+    fn test_path_exists_root_array() {
+        let value = array(vec![obj(vec![("name", string("a"))])]);
+        assert!(value.path_exists("[0].name"));
+        assert!(!value.path_exists("[1]"));
+    }
+
+    #[test]
+    fn test_matches_type() {
+        let value = obj(vec![
+            ("server", obj(vec![("port", int(8080))])),
+            ("hosts", array(vec![string("a")])),
+            ("nothing", ConfigValue::Null),
+        ]);
+
+        assert!(value.matches_type("server", ValueKind::Object));
+        assert!(value.matches_type("server.port", ValueKind::Integer));
+        assert!(value.matches_type("hosts[0]", ValueKind::String));
+        assert!(value.matches_type("nothing", ValueKind::Null));
+
+        assert!(!value.matches_type("server.port", ValueKind::String));
+        assert!(!value.matches_type("hosts", ValueKind::Object));
+        assert!(!value.matches_type("server.host", ValueKind::Null));
+    }
+
+    #[test]
+    fn test_flatten_keys() {
+        let value = obj(vec![
+            ("name", string("app")),
+            (
+                "server",
+                obj(vec![
+                    ("port", int(8080)),
+                    ("tls", obj(vec![("enabled", bool_val(true))])),
+                ]),
+            ),
+            ("hosts", array(vec![string("a"), string("b")])),
+        ]);
+
+        let flat = value.flatten_keys(".");
+        assert_eq!(flat.len(), 4);
+        assert_eq!(flat.get("name"), Some(&string("app")));
+        assert_eq!(flat.get("server.port"), Some(&int(8080)));
+        assert_eq!(flat.get("server.tls.enabled"), Some(&bool_val(true)));
+        assert_eq!(
+            flat.get("hosts"),
+            Some(&array(vec![string("a"), string("b")]))
+        );
+    }
+
+    #[test]
+    fn test_flatten_keys_custom_separator() {
+        let value = obj(vec![("db", obj(vec![("host", string("localhost"))]))]);
+        let flat = value.flatten_keys("__");
+        assert_eq!(flat.get("db__host"), Some(&string("localhost")));
+    }
+
+    #[test]
+    fn test_flatten_keys_non_object_root() {
+        assert!(int(1).flatten_keys(".").is_empty());
+        assert!(obj(vec![]).flatten_keys(".").is_empty());
+    }
+
+    #[test]
+    fn test_flatten_unflatten_round_trip() {
+        let value = obj(vec![
+            ("name", string("app")),
+            (
+                "server",
+                obj(vec![
+                    ("port", int(8080)),
+                    ("ratio", float(0.5)),
+                    ("tls", obj(vec![("enabled", bool_val(true))])),
+                    ("extra", obj(vec![])),
+                ]),
+            ),
+            ("hosts", array(vec![string("a"), string("b")])),
+        ]);
+
+        for sep in [".", "/", "__"] {
+            let flat = value.flatten_keys(sep);
+            assert_eq!(ConfigValue::unflatten(flat, sep), value);
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = obj(vec![
+            ("name", string("app")),
+            (
+                "server",
+                obj(vec![("port", int(8080)), ("host", string("a"))]),
+            ),
+            ("hosts", array(vec![string("a")])),
+        ]);
+        let new = obj(vec![
+            ("name", string("app")),
+            ("server", obj(vec![("port", int(9090))])),
+            ("hosts", array(vec![string("a"), string("b")])),
+            ("debug", bool_val(true)),
+        ]);
+
+        let changes = old.diff(&new);
+        assert_eq!(
+            changes,
+            vec![
+                Change::Added {
+                    key: "debug".into(),
+                    value: bool_val(true)
+                },
+                Change::Modified {
+                    key: "hosts".into(),
+                    old: array(vec![string("a")]),
+                    new: array(vec![string("a"), string("b")]),
+                },
+                Change::Removed {
+                    key: "server.host".into(),
+                    value: string("a")
+                },
+                Change::Modified {
+                    key: "server.port".into(),
+                    old: int(8080),
+                    new: int(9090),
+                },
+            ]
+        );
+        assert_eq!(changes[3].key(), "server.port");
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn test_diff_non_object_roots() {
+        let list = array(vec![int(1), int(2)]);
+        assert!(list.diff(&list).is_empty());
+        assert_eq!(
+            list.diff(&array(vec![int(1)])),
+            vec![Change::Modified {
+                key: String::new(),
+                old: list.clone(),
+                new: array(vec![int(1)]),
+            }]
+        );
+
+        // A type mismatch at the root is a change too
+        let empty = obj(vec![]);
+        assert_eq!(empty.diff(&list).len(), 1);
+        assert_eq!(int(1).diff(&string("1"))[0].key(), "");
+    }
+
+    #[test]
+    fn test_unflatten_nested_key_wins_over_prefix() {
+        let mut flat = HashMap::new();
+        flat.insert("a".to_string(), int(1));
+        flat.insert("a.b".to_string(), int(2));
+
+        let value = ConfigValue::unflatten(flat, ".");
+        assert_eq!(value.get("a").unwrap().get("b"), Some(&int(2)));
+    }
+
+    #[test]
+    fn test_infer_scalar_bools() {
+        assert_eq!(infer_scalar("true"), bool_val(true));
+        assert_eq!(infer_scalar("FALSE"), bool_val(false));
+        assert_eq!(infer_scalar("yes"), string("yes"));
+    }
+
+    #[test]
+    fn test_infer_scalar_integers() {
+        assert_eq!(infer_scalar("0"), int(0));
+        assert_eq!(infer_scalar("42"), int(42));
+        assert_eq!(infer_scalar("-17"), int(-17));
+        assert_eq!(infer_scalar("+5"), int(5));
+        // Overflowing i64 would lose precision as a float
+        assert_eq!(
+            infer_scalar("99999999999999999999"),
+            string("99999999999999999999")
+        );
+    }
+
+    #[test]
+    fn test_infer_scalar_floats() {
+        assert_eq!(infer_scalar("1.5"), float(1.5));
+        assert_eq!(infer_scalar("-2e3"), float(-2000.0));
+        assert_eq!(infer_scalar(".5"), float(0.5));
+        assert_eq!(infer_scalar("0.25"), float(0.25));
+    }
+
+    #[test]
+    fn test_infer_scalar_non_finite_kept_as_strings() {
+        assert_eq!(infer_scalar("1e999"), string("1e999"));
+        assert_eq!(infer_scalar("inf"), string("inf"));
+        assert_eq!(infer_scalar("-infinity"), string("-infinity"));
+        assert_eq!(infer_scalar("NaN"), string("NaN"));
+    }
+
+    #[test]
+    fn test_infer_scalar_leading_zeros_and_radix() {
+        assert_eq!(infer_scalar("007"), string("007"));
+        assert_eq!(infer_scalar("-007"), string("-007"));
+        assert_eq!(infer_scalar("00.5"), string("00.5"));
+        assert_eq!(infer_scalar("0755"), string("0755"));
+        assert_eq!(infer_scalar("0x10"), string("0x10"));
+        assert_eq!(infer_scalar("0b101"), string("0b101"));
+    }
+
+    #[test]
+    fn test_infer_scalar_strings() {
+        assert_eq!(infer_scalar(""), string(""));
+        assert_eq!(infer_scalar(" 42"), string(" 42"));
+        assert_eq!(infer_scalar("1.2.3"), string("1.2.3"));
+        assert_eq!(infer_scalar("e5"), string("e5"));
+        assert_eq!(infer_scalar("localhost"), string("localhost"));
+    }
+
+    #[test]
+    fn test_try_get() {
+        let value = obj(vec![("port", int(8080))]);
+        assert_eq!(value.try_get("port").unwrap(), &int(8080));
+
+        match value.try_get("host") {
+            Err(Error::KeyNotFound(key)) => assert_eq!(key, "host"),
+            other => panic!("expected KeyNotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_try_get_non_object() {
+        match int(1).try_get("port") {
+            Err(Error::ConversionError { type_name, .. }) => assert_eq!(type_name, "object"),
+            other => panic!("expected ConversionError, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_default() {
         let value: ConfigValue = Default::default();
@@ -881,6 +3844,113 @@ mod tests {
         assert!(isize::from_value(&string("42")).is_err());
     }
 
+    #[test]
+    fn test_one_or_many_scalar() {
+        let hosts = OneOrMany::<String>::from_value(&string("a")).unwrap();
+        assert_eq!(hosts.into_vec(), vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn test_one_or_many_array() {
+        let hosts =
+            OneOrMany::<String>::from_value(&array(vec![string("a"), string("b")])).unwrap();
+        assert_eq!(hosts.len(), 2);
+        assert_eq!(hosts[1], "b");
+        assert_eq!(Vec::from(hosts), vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_one_or_many_empty_array() {
+        let ports = OneOrMany::<u16>::from_value(&array(vec![])).unwrap();
+        assert!(ports.is_empty());
+    }
+
+    #[test]
+    fn test_one_or_many_errors() {
+        match OneOrMany::<u16>::from_value(&array(vec![int(80), string("x")])) {
+            Err(Error::ConversionError { key, .. }) => assert_eq!(key, "[1]"),
+            other => panic!("expected ConversionError, got {:?}", other),
+        }
+        assert!(OneOrMany::<u16>::from_value(&string("x")).is_err());
+    }
+
+    #[test]
+    fn test_maybe_from_value() {
+        assert_eq!(
+            Maybe::<i64>::from_value(&ConfigValue::Null).unwrap(),
+            Maybe::Null
+        );
+        assert_eq!(Maybe::<i64>::from_value(&int(5)).unwrap(), Maybe::Value(5));
+        assert!(Maybe::<i64>::from_value(&string("x")).is_err());
+        assert_eq!(Maybe::Value(5).into_option(), Some(5));
+        assert_eq!(Maybe::<i64>::Null.into_option(), None);
+        assert!(Maybe::<i64>::default().is_absent());
+    }
+
+    #[test]
+    fn test_str_num_from_string() {
+        assert_eq!(
+            StrNum::<u16>::from_value(&string("8080")).unwrap(),
+            StrNum(8080)
+        );
+        assert_eq!(*StrNum::<i32>::from_value(&string(" -7 ")).unwrap(), -7);
+        assert_eq!(
+            StrNum::<f64>::from_value(&string("1.5"))
+                .unwrap()
+                .into_inner(),
+            1.5
+        );
+        assert_eq!(StrNum::<u16>::from_value(&int(443)).unwrap(), StrNum(443));
+    }
+
+    #[test]
+    fn test_str_num_errors() {
+        match StrNum::<u16>::from_value(&string("eighty")) {
+            Err(Error::ConversionError { source, .. }) => {
+                assert!(source.to_string().contains("'eighty'"))
+            }
+            other => panic!("expected ConversionError, got {:?}", other),
+        }
+        assert!(StrNum::<u16>::from_value(&string("70000")).is_err());
+        assert!(StrNum::<f64>::from_value(&string("NaN")).is_err());
+        assert!(StrNum::<u16>::from_value(&bool_val(true)).is_err());
+        // Plain integers still reject strings
+        assert!(u16::from_value(&string("8080")).is_err());
+    }
+
+    #[test]
+    fn test_from_value_duration() {
+        use core::time::Duration;
+
+        assert_eq!(
+            Duration::from_value(&string("1500ms")).unwrap(),
+            Duration::from_millis(1500)
+        );
+        assert_eq!(
+            Duration::from_value(&string("2m")).unwrap(),
+            Duration::from_secs(120)
+        );
+        assert_eq!(
+            Duration::from_value(&int(90)).unwrap(),
+            Duration::from_secs(90)
+        );
+        assert_eq!(
+            Duration::from_value(&float(0.25)).unwrap(),
+            Duration::from_millis(250)
+        );
+    }
+
+    #[test]
+    fn test_from_value_duration_invalid() {
+        use core::time::Duration;
+
+        let err = Duration::from_value(&string("soon")).unwrap_err();
+        assert!(err.to_string().contains("\"soon\""), "{err}");
+        assert!(err.to_string().contains("1h30m"), "{err}");
+        assert!(Duration::from_value(&int(-1)).is_err());
+        assert!(Duration::from_value(&bool_val(true)).is_err());
+    }
+
     #[test]
     fn test_from_value_pathbuf() {
         use std::path::PathBuf;
@@ -4,12 +4,14 @@
 //! deserialization logic when extracting values from configuration.
 
 use crate::error::{Error, Result};
-use crate::value::ConfigValue;
+use crate::value::{push_index, push_key, wide_i128, wide_u128, ConfigValue};
 
 #[cfg(not(feature = "std"))]
 use alloc::{
     collections::BTreeMap as HashMap,
+    format,
     string::{String, ToString},
+    vec::Vec,
 };
 #[cfg(feature = "std")]
 use std::collections::HashMap;
@@ -87,6 +89,23 @@ pub trait ValueVisitor {
         })
     }
 
+    /// Visit an integer too large for `i64` that fits in `i128`.
+    ///
+    /// Such integers are stored as strings (see `ConfigValue::as_i128()`),
+    /// so by default the value is passed to `visit_str` as its decimal
+    /// digits. Override it to accept the number itself.
+    fn visit_i128(&mut self, v: i128) -> Result<Self::Output> {
+        self.visit_str(&v.to_string())
+    }
+
+    /// Visit an integer too large for `i64` or `i128` that fits in `u128`.
+    ///
+    /// Like `visit_i128`, this passes the decimal digits to `visit_str` by
+    /// default.
+    fn visit_u128(&mut self, v: u128) -> Result<Self::Output> {
+        self.visit_str(&v.to_string())
+    }
+
     /// Visit a floating-point value.
     fn visit_f64(&mut self, _v: f64) -> Result<Self::Output> {
         Err(Error::ConversionError {
@@ -114,6 +133,19 @@ pub trait ValueVisitor {
         })
     }
 
+    /// Visit a byte string.
+    ///
+    /// Errors by default, even for visitors that accept arrays, so binary
+    /// data is never mistaken for numbers. Override it to handle bytes, or
+    /// to forward them to `visit_array` as integers.
+    fn visit_bytes(&mut self, _bytes: &[u8]) -> Result<Self::Output> {
+        Err(Error::ConversionError {
+            key: String::new(),
+            type_name: self.expecting().into(),
+            source: format!("expected {}, found bytes", self.expecting()).into(),
+        })
+    }
+
     /// Visit an array value with sequential access.
     ///
     /// This provides an iterator-like interface for processing array elements
@@ -153,6 +185,12 @@ pub trait ValueVisitor {
         Ok(())
     }
 
+    /// Note the path of the leaf about to be visited.
+    ///
+    /// Called by `visit_with_path()` before each leaf is dispatched, with
+    /// paths like `server.ports[2]`. Ignored by default.
+    fn enter_path(&mut self, _path: &str) {}
+
     /// Finish visiting and potentially transform the output.
     ///
     /// This hook is called after successful visitation, allowing for final
@@ -259,7 +297,8 @@ impl<'a> SeqAccess<'a> {
 /// Drive a visitor through a configuration value.
 ///
 /// This function dispatches to the appropriate visitor method based on the
-/// value's type.
+/// value's type. Arrays go to `visit_array`; visitors that prefer
+/// sequential access can forward it to `visit_seq` with `SeqAccess::new()`.
 ///
 /// # Examples
 ///
@@ -295,12 +334,111 @@ pub fn visit<V: ValueVisitor>(value: &ConfigValue, visitor: &mut V) -> Result<V:
         ConfigValue::Bool(b) => visitor.visit_bool(*b),
         ConfigValue::Integer(n) => visitor.visit_i64(*n),
         ConfigValue::Float(f) => visitor.visit_f64(*f),
-        ConfigValue::String(s) => visitor.visit_str(s),
+        ConfigValue::String(s) => visit_str_or_wide_integer(s, visitor),
+        ConfigValue::Bytes(bytes) => visitor.visit_bytes(bytes),
         ConfigValue::Array(arr) => visitor.visit_array(arr),
         ConfigValue::Object(map) => visitor.visit_map(MapAccess::new(map)),
     }
 }
 
+/// Dispatch a string, sending canonical wide integers to `visit_i128` or
+/// `visit_u128`.
+fn visit_str_or_wide_integer<V: ValueVisitor>(s: &str, visitor: &mut V) -> Result<V::Output> {
+    if let Some(n) = wide_i128(s).filter(|n| n.to_string() == s) {
+        visitor.visit_i128(n)
+    } else if let Some(n) = wide_u128(s).filter(|n| n.to_string() == s) {
+        visitor.visit_u128(n)
+    } else {
+        visitor.visit_str(s)
+    }
+}
+
+/// Drive a visitor through every leaf of a configuration value.
+///
+/// Unlike `visit()`, this descends into objects (in sorted key order) and
+/// arrays, calling `enter_path()` with each leaf's path before dispatching
+/// the leaf as `visit()` would. Leaf outputs are discarded, so the visitor
+/// accumulates whatever it needs. Stops at the first error; a
+/// `ConversionError` without a key is given the leaf's path.
+///
+/// # Examples
+///
+/// ```
+/// use prefer::{ConfigValue, ValueVisitor, Result};
+/// use prefer::visitor::visit_with_path;
+///
+/// struct PortValidator {
+///     path: String,
+/// }
+///
+/// impl ValueVisitor for PortValidator {
+///     type Output = ();
+///
+///     fn enter_path(&mut self, path: &str) {
+///         self.path = path.to_string();
+///     }
+///
+///     fn visit_i64(&mut self, v: i64) -> Result<()> {
+///         if u16::try_from(v).is_err() {
+///             return Err(prefer::Error::ConversionError {
+///                 key: self.path.clone(),
+///                 type_name: "u16".into(),
+///                 source: "port out of range".into(),
+///             });
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let value = ConfigValue::Object(
+///     [(
+///         "ports".to_string(),
+///         ConfigValue::Array(vec![ConfigValue::Integer(80), ConfigValue::Integer(70000)]),
+///     )]
+///     .into_iter()
+///     .collect(),
+/// );
+/// let mut validator = PortValidator { path: String::new() };
+/// let err = visit_with_path(&value, &mut validator).unwrap_err();
+/// assert!(err.to_string().contains("ports[1]"));
+/// ```
+pub fn visit_with_path<V: ValueVisitor>(value: &ConfigValue, visitor: &mut V) -> Result<()> {
+    visit_node_with_path(value, &mut String::new(), visitor)
+}
+
+fn visit_node_with_path<V: ValueVisitor>(
+    value: &ConfigValue,
+    path: &mut String,
+    visitor: &mut V,
+) -> Result<()> {
+    match value {
+        ConfigValue::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            for key in keys {
+                let len = push_key(path, key);
+                visit_node_with_path(&map[key], path, visitor)?;
+                path.truncate(len);
+            }
+        }
+        ConfigValue::Array(arr) => {
+            for (i, child) in arr.iter().enumerate() {
+                let len = push_index(path, i);
+                visit_node_with_path(child, path, visitor)?;
+                path.truncate(len);
+            }
+        }
+        leaf => {
+            visitor.enter_path(path);
+            visit(leaf, visitor).map_err(|e| match e {
+                Error::ConversionError { ref key, .. } if key.is_empty() => e.with_key(&**path),
+                e => e,
+            })?;
+        }
+    }
+    Ok(())
+}
+
 /// A visitor that collects values into a type using `FromValue`.
 pub struct FromValueVisitor<T> {
     _marker: core::marker::PhantomData<T>,
@@ -347,6 +485,10 @@ impl<T: crate::FromValue> ValueVisitor for FromValueVisitor<T> {
         T::from_value(&ConfigValue::String(v.to_string()))
     }
 
+    fn visit_bytes(&mut self, bytes: &[u8]) -> Result<Self::Output> {
+        T::from_value(&ConfigValue::Bytes(bytes.to_vec()))
+    }
+
     fn visit_array(&mut self, arr: &[ConfigValue]) -> Result<Self::Output> {
         T::from_value(&ConfigValue::Array(arr.to_vec()))
     }
@@ -407,6 +549,103 @@ mod tests {
         assert_eq!(result, 15);
     }
 
+    #[test]
+    fn test_visit_bytes_default_errors() {
+        // Accepting arrays doesn't mean accepting bytes
+        let mut visitor = SumVisitor { sum: 0 };
+        let err = visit(&ConfigValue::Bytes(vec![1, 2, 3]), &mut visitor).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected a number or array of numbers, found bytes"));
+
+        let mut visitor = KeyCollector { keys: vec![] };
+        assert!(visit(&ConfigValue::Bytes(vec![1]), &mut visitor).is_err());
+    }
+
+    struct HexBytesVisitor;
+
+    impl ValueVisitor for HexBytesVisitor {
+        type Output = Vec<u8>;
+
+        fn expecting(&self) -> &'static str {
+            "bytes or a hex string"
+        }
+
+        fn visit_bytes(&mut self, bytes: &[u8]) -> Result<Self::Output> {
+            Ok(bytes.to_vec())
+        }
+
+        fn visit_str(&mut self, v: &str) -> Result<Self::Output> {
+            let bytes = (0..v.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&v[i..i + 2], 16))
+                .collect::<core::result::Result<Vec<u8>, _>>()
+                .map_err(|e| Error::ConversionError {
+                    key: String::new(),
+                    type_name: self.expecting().into(),
+                    source: e.to_string().into(),
+                })?;
+            self.visit_bytes(&bytes)
+        }
+    }
+
+    #[test]
+    fn test_visit_bytes_override() {
+        let mut visitor = HexBytesVisitor;
+        let from_bytes = visit(&ConfigValue::Bytes(vec![0xde, 0xad]), &mut visitor).unwrap();
+        let from_str = visit(&ConfigValue::String("dead".to_string()), &mut visitor).unwrap();
+        assert_eq!(from_bytes, vec![0xde, 0xad]);
+        assert_eq!(from_str, from_bytes);
+    }
+
+    struct WideVisitor;
+
+    impl ValueVisitor for WideVisitor {
+        type Output = String;
+
+        fn visit_i128(&mut self, v: i128) -> Result<Self::Output> {
+            Ok(format!("i128 {v}"))
+        }
+
+        fn visit_u128(&mut self, v: u128) -> Result<Self::Output> {
+            Ok(format!("u128 {v}"))
+        }
+
+        fn visit_str(&mut self, v: &str) -> Result<Self::Output> {
+            Ok(format!("str {v}"))
+        }
+    }
+
+    #[test]
+    fn test_visit_wide_integers() {
+        let max = "170141183460469231731687303715884105727";
+        let beyond = "170141183460469231731687303715884105728";
+        let string = |s: &str| ConfigValue::String(s.to_string());
+
+        assert_eq!(
+            visit(&string(max), &mut WideVisitor).unwrap(),
+            format!("i128 {max}")
+        );
+        assert_eq!(
+            visit(&string(beyond), &mut WideVisitor).unwrap(),
+            format!("u128 {beyond}")
+        );
+        // Fits in i64, or isn't written canonically: still a string
+        assert_eq!(visit(&string("42"), &mut WideVisitor).unwrap(), "str 42");
+        assert_eq!(
+            visit(&string(&format!("+{max}")), &mut WideVisitor).unwrap(),
+            format!("str +{max}")
+        );
+
+        // By default, wide integers fall back to visit_str
+        let mut visitor = SumVisitor { sum: 0 };
+        assert!(visit(&string(max), &mut visitor).is_err());
+        assert_eq!(
+            visit(&string(max), &mut FromValueVisitor::<String>::new()).unwrap(),
+            max
+        );
+    }
+
     #[test]
     fn test_visit_string_error() {
         let mut visitor = SumVisitor { sum: 0 };
@@ -498,6 +737,37 @@ mod tests {
         assert_eq!(result, 60);
     }
 
+    struct ArrayOverSeqVisitor;
+
+    impl ValueVisitor for ArrayOverSeqVisitor {
+        type Output = &'static str;
+
+        fn expecting(&self) -> &'static str {
+            "an array"
+        }
+
+        fn visit_array(&mut self, _arr: &[ConfigValue]) -> Result<Self::Output> {
+            Ok("array")
+        }
+
+        fn visit_seq(&mut self, _seq: super::SeqAccess<'_>) -> Result<Self::Output> {
+            Ok("seq")
+        }
+    }
+
+    #[test]
+    fn test_visit_dispatches_arrays_to_visit_array() {
+        let value = ConfigValue::Array(vec![ConfigValue::Integer(1), ConfigValue::Integer(2)]);
+
+        // visit_array overrides are honored even alongside visit_seq
+        assert_eq!(visit(&value, &mut ArrayOverSeqVisitor).unwrap(), "array");
+
+        // The default visit_seq still reaches visit_array
+        let mut visitor = SumVisitor { sum: 0 };
+        let seq = super::SeqAccess::new(value.as_array().unwrap());
+        assert_eq!(visitor.visit_seq(seq).unwrap(), 3);
+    }
+
     struct EnumVisitor;
 
     impl ValueVisitor for EnumVisitor {
@@ -566,4 +836,72 @@ mod tests {
             _ => panic!("Expected ConversionError"),
         }
     }
+
+    struct StringPathRecorder {
+        current: String,
+        paths: Vec<String>,
+    }
+
+    impl ValueVisitor for StringPathRecorder {
+        type Output = ();
+
+        fn enter_path(&mut self, path: &str) {
+            self.current = path.to_string();
+        }
+
+        fn visit_str(&mut self, _v: &str) -> Result<Self::Output> {
+            self.paths.push(self.current.clone());
+            Ok(())
+        }
+
+        fn visit_i64(&mut self, _v: i64) -> Result<Self::Output> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_visit_with_path_records_string_leaves() {
+        use crate::value::test_helpers::{array, int, obj, string};
+
+        let value = obj(vec![
+            ("name", string("app")),
+            (
+                "server",
+                obj(vec![
+                    ("host", string("localhost")),
+                    ("ports", array(vec![int(80), string("https"), int(8080)])),
+                ]),
+            ),
+        ]);
+        let mut visitor = StringPathRecorder {
+            current: String::new(),
+            paths: vec![],
+        };
+
+        visit_with_path(&value, &mut visitor).unwrap();
+        assert_eq!(
+            visitor.paths,
+            vec!["name", "server.host", "server.ports[1]"]
+        );
+    }
+
+    #[test]
+    fn test_visit_with_path_error_reports_path() {
+        use crate::value::test_helpers::{array, int, obj};
+
+        let value = obj(vec![(
+            "server",
+            obj(vec![(
+                "ports",
+                array(vec![int(80), int(443), ConfigValue::Bool(true)]),
+            )]),
+        )]);
+        let mut visitor = SumVisitor { sum: 0 };
+
+        match visit_with_path(&value, &mut visitor).unwrap_err() {
+            Error::ConversionError { key, .. } => assert_eq!(key, "server.ports[2]"),
+            _ => panic!("Expected ConversionError"),
+        }
+        assert_eq!(visitor.sum, 523);
+    }
 }
@@ -2,11 +2,17 @@
 
 use crate::config::Config;
 use crate::discovery;
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::loader::Loader;
+use crate::registry;
+use crate::value::{ConfigValue, FromValue};
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use std::path::PathBuf;
 use std::sync::mpsc as std_mpsc;
+use std::sync::{Arc, RwLock};
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, MissedTickBehavior};
 
 /// Watch a configuration file for changes.
 ///
@@ -42,6 +48,121 @@ pub async fn watch_path(path: PathBuf) -> Result<mpsc::Receiver<Config>> {
     Ok(rx)
 }
 
+/// Watch any registered source by periodically reloading it.
+///
+/// This is a fallback for loaders that cannot push change notifications,
+/// such as databases. The source is loaded once up front, then reloaded
+/// every `interval`; a new `Config` is sent only when the loaded value
+/// differs from the previous one. Failed reloads are skipped. Polling
+/// stops once the receiver is dropped.
+///
+/// # Panics
+///
+/// Panics if `interval` is zero.
+pub async fn poll_loader(identifier: &str, interval: Duration) -> Result<mpsc::Receiver<Config>> {
+    let loader =
+        registry::find_loader(identifier).ok_or(Error::NoLoaderFound(identifier.to_string()))?;
+
+    let formatters = registry::collect_formatters();
+    let initial = loader.load(identifier, &formatters).await?;
+
+    let (tx, rx) = mpsc::channel(32);
+    let identifier = identifier.to_string();
+
+    tokio::spawn(async move {
+        run_poll_loop(loader, identifier, interval, initial.data, tx).await;
+    });
+
+    Ok(rx)
+}
+
+/// A configuration that reloads itself in the background when its file changes.
+///
+/// Reads always see the most recently loaded snapshot. Dropping the
+/// `WatchedConfig` stops the background reload task.
+///
+/// # Examples
+///
+/// ```no_run
+/// use prefer::WatchedConfig;
+///
+/// #[tokio::main]
+/// async fn main() -> prefer::Result<()> {
+///     let config = WatchedConfig::watch("myapp").await?;
+///     let port: u16 = config.get("server.port")?;
+///     Ok(())
+/// }
+/// ```
+pub struct WatchedConfig {
+    current: Arc<RwLock<Config>>,
+    task: JoinHandle<()>,
+}
+
+impl WatchedConfig {
+    /// Load a configuration file by name and keep it up to date.
+    pub async fn watch(name: &str) -> Result<Self> {
+        let path = discovery::find_config_file(name).await?;
+        let initial = Config::load_from_path(&path).await?;
+        Self::watch_path(initial, path).await
+    }
+
+    /// Keep an already-loaded configuration up to date with the file at `path`.
+    pub(crate) async fn watch_path(initial: Config, path: PathBuf) -> Result<Self> {
+        let receiver = watch_path(path).await?;
+        Ok(Self::from_receiver(initial, receiver))
+    }
+
+    /// Wrap an initial configuration and a stream of updates.
+    fn from_receiver(initial: Config, mut receiver: mpsc::Receiver<Config>) -> Self {
+        let current = Arc::new(RwLock::new(initial));
+        let target = current.clone();
+
+        let task = tokio::spawn(async move {
+            while let Some(config) = receiver.recv().await {
+                if let Ok(mut guard) = target.write() {
+                    guard.replace_data(config.into_value());
+                }
+            }
+        });
+
+        Self { current, task }
+    }
+
+    /// Get a value from the current snapshot using dot notation.
+    pub fn get<T: FromValue>(&self, key: &str) -> Result<T> {
+        self.read().get(key)
+    }
+
+    /// Register a handler for changes to one subtree on reload.
+    ///
+    /// See `Config::observe()`. Each reload reports the leaves that differ
+    /// from the previous snapshot. Handlers run while the snapshot is being
+    /// replaced, so they must not read from this `WatchedConfig`; use the
+    /// value they are given instead.
+    pub fn observe(&self, key_prefix: &str, handler: crate::events::EventHandler) {
+        self.current
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .observe(key_prefix, handler);
+    }
+
+    /// Get a clone of the current configuration snapshot.
+    pub fn snapshot(&self) -> Config {
+        self.read().clone()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Config> {
+        // A poisoned lock still holds the last complete snapshot.
+        self.current.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for WatchedConfig {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
 /// Process file system events and send config updates.
 /// Extracted for testability.
 async fn run_event_loop(
@@ -71,6 +192,48 @@ async fn run_event_loop(
     }
 }
 
+/// Reload a source on every tick, sending configs whose data changed.
+async fn run_poll_loop(
+    loader: &'static dyn Loader,
+    identifier: String,
+    interval: Duration,
+    mut previous: ConfigValue,
+    tx: mpsc::Sender<Config>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    // The first tick completes immediately; the initial load already ran.
+    ticker.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = tx.closed() => break,
+            _ = ticker.tick() => {}
+        }
+
+        let formatters = registry::collect_formatters();
+        let Ok(result) = loader.load(&identifier, &formatters).await else {
+            continue;
+        };
+
+        if result.data == previous {
+            continue;
+        }
+
+        previous = result.data.clone();
+        let config = Config::with_metadata(
+            result.data,
+            Some(result.source),
+            Some(loader.name().to_string()),
+            result.format,
+        );
+        if tx.send(config).await.is_err() {
+            break;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,6 +269,83 @@ mod tests {
         let _ = tokio::fs::remove_file(&path).await;
     }
 
+    #[tokio::test]
+    async fn test_watched_config_reloads() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("watched.json");
+        tokio::fs::write(&path, r#"{"value": 1}"#).await.unwrap();
+
+        let initial = Config::load_from_path(&path).await.unwrap();
+        let watched = WatchedConfig::watch_path(initial, path.clone())
+            .await
+            .unwrap();
+        assert_eq!(watched.get::<i32>("value").unwrap(), 1);
+
+        sleep(Duration::from_millis(200)).await;
+        tokio::fs::write(&path, r#"{"value": 2}"#).await.unwrap();
+
+        let mut value = 1;
+        for _ in 0..40 {
+            value = watched.get::<i32>("value").unwrap();
+            if value == 2 {
+                break;
+            }
+            sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(value, 2);
+    }
+
+    #[tokio::test]
+    async fn test_watched_config_routes_reloads_to_observers() {
+        use crate::value::test_helpers::obj;
+
+        let initial = Config::new(obj(vec![
+            ("server", obj(vec![("port", ConfigValue::Integer(8080))])),
+            ("database", obj(vec![("host", ConfigValue::from("db"))])),
+        ]));
+        let (tx, rx) = mpsc::channel(1);
+        let watched = WatchedConfig::from_receiver(initial, rx);
+
+        let server_keys = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let database_keys = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = server_keys.clone();
+        watched.observe(
+            "server",
+            Box::new(move |key, _, _| log.lock().unwrap().push(key.to_string())),
+        );
+        let log = database_keys.clone();
+        watched.observe(
+            "database",
+            Box::new(move |key, _, _| log.lock().unwrap().push(key.to_string())),
+        );
+
+        let mut next = watched.snapshot();
+        next.set("server.port", ConfigValue::Integer(9090));
+        tx.send(next).await.unwrap();
+
+        for _ in 0..40 {
+            if watched.get::<u16>("server.port").unwrap() == 9090 {
+                break;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(*server_keys.lock().unwrap(), vec!["server.port"]);
+        assert!(database_keys.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watched_config_drop_stops_task() {
+        let (tx, rx) = mpsc::channel(1);
+        let watched = WatchedConfig::from_receiver(Config::new(Default::default()), rx);
+        let current = watched.current.clone();
+        drop(watched);
+
+        // Let the runtime process the abort before checking the sender.
+        sleep(Duration::from_millis(50)).await;
+        assert!(tx.is_closed());
+        assert_eq!(Arc::strong_count(&current), 1);
+    }
+
     #[tokio::test]
     async fn test_event_loop_exits_on_channel_disconnect() {
         // Create a channel and immediately drop the sender
@@ -2,7 +2,7 @@
 
 use prefer::value::FromValue;
 use prefer::visitor::{visit, FromValueVisitor, MapAccess, ValueVisitor};
-use prefer::{Config, ConfigBuilder, ConfigValue, Error, MemorySource};
+use prefer::{Change, Config, ConfigBuilder, ConfigValue, Error, MemorySource};
 use serial_test::serial;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -662,6 +662,74 @@ async fn test_config_load_from_path() {
     let loaded: bool = config.get("loaded").unwrap();
     assert!(loaded);
     assert_eq!(config.source_path(), Some(&config_path));
+    assert_eq!(config.source_of("loaded"), None);
+    assert!(config.effective_sources().is_none());
+}
+
+#[tokio::test]
+async fn test_config_diff_with_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("app.json");
+    std::fs::write(
+        &config_path,
+        r#"{"server": {"host": "localhost", "port": 8080}, "debug": false}"#,
+    )
+    .unwrap();
+
+    let mut config = Config::load_from_path(&config_path).await.unwrap();
+    assert!(config
+        .diff_with_path(&config_path)
+        .await
+        .unwrap()
+        .is_empty());
+
+    config.set("server.port", int(9090));
+    config.set("name", str_val("app"));
+    config.data_mut().as_object_mut().unwrap().remove("debug");
+
+    let changes = config.diff_with_path(&config_path).await.unwrap();
+    assert_eq!(
+        changes,
+        vec![
+            Change::Removed {
+                key: "debug".into(),
+                value: ConfigValue::Bool(false),
+            },
+            Change::Added {
+                key: "name".into(),
+                value: str_val("app"),
+            },
+            Change::Modified {
+                key: "server.port".into(),
+                old: int(8080),
+                new: int(9090),
+            },
+        ]
+    );
+
+    let missing = temp_dir.path().join("missing.json");
+    let err = config.diff_with_path(&missing).await.unwrap_err();
+    assert!(matches!(err, Error::FileNotFound(p) if p.ends_with("missing.json")));
+}
+
+#[tokio::test]
+async fn test_config_diff_with_path_stream() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("manifests.yamls");
+    std::fs::write(&config_path, "name: web\n---\nname: db\n").unwrap();
+
+    let mut config = Config::load_from_path(&config_path).await.unwrap();
+    assert!(config
+        .diff_with_path(&config_path)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let docs = config.data_mut().as_array_mut().unwrap();
+    docs.pop();
+    let changes = config.diff_with_path(&config_path).await.unwrap();
+    assert_eq!(changes.len(), 1);
+    assert!(matches!(&changes[0], Change::Modified { key, .. } if key.is_empty()));
 }
 
 #[test]
@@ -1,6 +1,7 @@
 //! Tests for the derive macro.
 
-use prefer::{Config, ConfigValue};
+use prefer::{Config, ConfigBuilder, ConfigValue, Maybe, OneOrMany, WithExtras};
+use std::marker::PhantomData;
 // Import the derive macro for #[derive(FromValue)]
 use prefer_derive::FromValue;
 
@@ -107,6 +108,127 @@ struct ConfigWithRequired {
     endpoint: Option<String>, // Required but nullable
 }
 
+#[derive(Debug, FromValue, PartialEq)]
+struct ConfigWithPath {
+    #[prefer(path = "connection.primary.host")]
+    host: String,
+    #[prefer(path = "connection.primary.port", default = "5432")]
+    port: u16,
+    #[prefer(path = "connection.replica.host")]
+    replica: Option<String>,
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+#[prefer(validate = "validate_port")]
+#[prefer(validate = "validate_range")]
+struct ValidatedConfig {
+    port: u16,
+    min: i64,
+    max: i64,
+}
+
+fn validate_port(config: &ValidatedConfig) -> Result<(), &'static str> {
+    if config.port == 0 {
+        return Err("port must be non-zero");
+    }
+    Ok(())
+}
+
+fn validate_range(config: &ValidatedConfig) -> Result<(), String> {
+    if config.min > config.max {
+        return Err(format!("min {} exceeds max {}", config.min, config.max));
+    }
+    Ok(())
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+struct ConfigWithExtra {
+    host: String,
+    #[prefer(rename = "listen_port")]
+    port: u16,
+    #[prefer(path = "tls.enabled", default)]
+    tls: bool,
+    #[prefer(flatten)]
+    extra: std::collections::HashMap<String, ConfigValue>,
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+struct ConfigWithFlattenedStructAndExtra {
+    name: String,
+    #[prefer(flatten)]
+    server: ServerConfig,
+    #[prefer(flatten)]
+    extra: std::collections::HashMap<String, ConfigValue>,
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+struct ConfigWithFlattenedStruct {
+    name: String,
+    #[prefer(flatten)]
+    server: ServerConfig,
+    #[prefer(skip)]
+    cache: Option<String>,
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+struct ConfigWithCoercion {
+    #[prefer(coerce)]
+    port: u16,
+    #[prefer(coerce)]
+    debug: bool,
+    #[prefer(coerce)]
+    timeout: Option<f64>,
+    #[prefer(coerce, default = "4")]
+    workers: u8,
+    #[prefer(coerce)]
+    name: String,
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+#[prefer(derive_default)]
+struct ConfigWithDerivedDefault {
+    #[prefer(default = "localhost")]
+    host: String,
+    #[prefer(default = "8080")]
+    port: u16,
+    #[prefer(default = "true")]
+    debug: bool,
+    #[prefer(default)]
+    tags: Vec<String>,
+    name: Option<String>,
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+struct ConfigWithWrappers {
+    port: Box<u16>,
+    separator: char,
+    label: std::borrow::Cow<'static, str>,
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+struct ConfigWithMaybe {
+    x: Maybe<i64>,
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+struct ConfigWithMarker {
+    name: String,
+    _marker: PhantomData<String>,
+    unit: (),
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+struct TypedId<T> {
+    id: u64,
+    _kind: PhantomData<T>,
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+struct ConfigWithOneOrMany {
+    hosts: OneOrMany<String>,
+    ports: OneOrMany<u16>,
+}
+
 #[test]
 fn test_simple_struct() {
     let value = obj(vec![("host", str("localhost")), ("port", int(8080))]);
@@ -223,6 +345,42 @@ fn test_nested_struct() {
     assert_eq!(config.database.name, "myapp");
 }
 
+#[derive(Debug, FromValue, PartialEq)]
+struct ClusterConfig {
+    database: ReplicaConfig,
+}
+
+#[derive(Debug, FromValue, PartialEq)]
+struct ReplicaConfig {
+    primary: ServerConfig,
+}
+
+#[test]
+fn test_nested_error_reports_full_path() {
+    let value = obj(vec![(
+        "database",
+        obj(vec![(
+            "primary",
+            obj(vec![("host", int(5)), ("port", int(5432))]),
+        )]),
+    )]);
+
+    let err = <ClusterConfig as FromValueTrait>::from_value(&value).unwrap_err();
+    match err {
+        prefer::Error::ConversionError { key, .. } => assert_eq!(key, "database.primary.host"),
+        other => panic!("expected ConversionError, got {:?}", other),
+    }
+
+    // Keys requested through Config::get are prefixed as well
+    let config = Config::new(obj(vec![("cluster", value)]));
+    match config.get::<ClusterConfig>("cluster").unwrap_err() {
+        prefer::Error::ConversionError { key, .. } => {
+            assert_eq!(key, "cluster.database.primary.host")
+        }
+        other => panic!("expected ConversionError, got {:?}", other),
+    }
+}
+
 #[test]
 fn test_tagged_enum_postgres() {
     let value = obj(vec![
@@ -288,6 +446,61 @@ async fn test_config_extract_with_derive() {
     assert_eq!(server.port, 8080);
 }
 
+#[tokio::test]
+async fn test_builder_build_typed_with_env_override() {
+    std::env::set_var("BUILDTYPED__SERVER__PORT", "9090");
+
+    let result: prefer::Result<NestedConfig> = ConfigBuilder::new()
+        .add_defaults(obj(vec![
+            (
+                "database",
+                obj(vec![
+                    ("host", str("db")),
+                    ("port", int(5432)),
+                    ("name", str("app")),
+                ]),
+            ),
+            (
+                "server",
+                obj(vec![("host", str("localhost")), ("port", int(8080))]),
+            ),
+        ]))
+        .add_env("BUILDTYPED")
+        .build_typed()
+        .await;
+
+    std::env::remove_var("BUILDTYPED__SERVER__PORT");
+
+    let config = result.unwrap();
+    assert_eq!(config.database.name, "app");
+    assert_eq!(config.server.host, "localhost");
+    assert_eq!(config.server.port, 9090);
+}
+
+#[tokio::test]
+async fn test_builder_build_typed_error_has_key() {
+    let err = ConfigBuilder::new()
+        .add_defaults(obj(vec![
+            (
+                "database",
+                obj(vec![
+                    ("host", str("db")),
+                    ("port", int(5432)),
+                    ("name", str("app")),
+                ]),
+            ),
+            (
+                "server",
+                obj(vec![("host", str("localhost")), ("port", str("http"))]),
+            ),
+        ]))
+        .build_typed::<NestedConfig>()
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("server.port"), "{err}");
+}
+
 #[test]
 fn test_required_field_present() {
     let value = obj(vec![
@@ -339,3 +552,419 @@ fn test_required_field_with_null() {
     assert_eq!(config.api_key, "secret123");
     assert_eq!(config.endpoint, None);
 }
+
+#[test]
+fn test_path_reads_nested_value() {
+    let value = obj(vec![(
+        "connection",
+        obj(vec![(
+            "primary",
+            obj(vec![("host", str("db.example.com")), ("port", int(6543))]),
+        )]),
+    )]);
+
+    let config = <ConfigWithPath as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(config.host, "db.example.com");
+    assert_eq!(config.port, 6543);
+    assert_eq!(config.replica, None);
+}
+
+#[test]
+fn test_path_with_default_and_option() {
+    let value = obj(vec![(
+        "connection",
+        obj(vec![
+            ("primary", obj(vec![("host", str("db.example.com"))])),
+            ("replica", obj(vec![("host", str("replica.example.com"))])),
+        ]),
+    )]);
+
+    let config = <ConfigWithPath as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(config.port, 5432);
+    assert_eq!(config.replica, Some("replica.example.com".to_string()));
+}
+
+#[test]
+fn test_as_typed_section() {
+    let config = Config::new(obj(vec![(
+        "services",
+        obj(vec![(
+            "database",
+            obj(vec![
+                ("host", str("db.local")),
+                ("port", int(5432)),
+                ("name", str("app")),
+            ]),
+        )]),
+    )]));
+
+    let db: DatabaseConfig = config.as_typed_section("services.database").unwrap();
+    assert_eq!(
+        db,
+        DatabaseConfig {
+            host: "db.local".to_string(),
+            port: 5432,
+            name: "app".to_string(),
+        }
+    );
+
+    match config.as_typed_section::<DatabaseConfig>("services.cache") {
+        Err(prefer::Error::KeyNotFound(key)) => assert_eq!(key, "services.cache"),
+        other => panic!("expected KeyNotFound, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_as_typed_section_errors_name_full_path() {
+    let config = Config::new(obj(vec![(
+        "server",
+        obj(vec![("host", str("localhost")), ("port", str("high"))]),
+    )]));
+    match config.as_typed_section::<ServerConfig>("server") {
+        Err(prefer::Error::ConversionError { key, .. }) => assert_eq!(key, "server.port"),
+        other => panic!("expected ConversionError, got {:?}", other),
+    }
+
+    let config = Config::new(obj(vec![("server", obj(vec![("port", int(80))]))]));
+    match config.as_typed_section::<ServerConfig>("server") {
+        Err(prefer::Error::KeyNotFound(key)) => assert_eq!(key, "server.host"),
+        other => panic!("expected KeyNotFound, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_path_missing_reports_full_path() {
+    let value = obj(vec![("connection", obj(vec![]))]);
+
+    let result = <ConfigWithPath as FromValueTrait>::from_value(&value);
+    match result.unwrap_err() {
+        prefer::Error::KeyNotFound(key) => assert_eq!(key, "connection.primary.host"),
+        _ => panic!("Expected KeyNotFound error"),
+    }
+}
+
+#[test]
+fn test_validate_accepts_valid_value() {
+    let value = obj(vec![("port", int(8080)), ("min", int(1)), ("max", int(10))]);
+
+    let config = <ValidatedConfig as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(config.port, 8080);
+}
+
+#[test]
+fn test_validate_rejects_invalid_value() {
+    let value = obj(vec![("port", int(8080)), ("min", int(10)), ("max", int(1))]);
+
+    let result = <ValidatedConfig as FromValueTrait>::from_value(&value);
+    match result.unwrap_err() {
+        prefer::Error::ConversionError {
+            type_name, source, ..
+        } => {
+            assert_eq!(type_name, "ValidatedConfig");
+            assert_eq!(source.to_string(), "min 10 exceeds max 1");
+        }
+        _ => panic!("Expected ConversionError"),
+    }
+}
+
+#[test]
+fn test_validate_runs_in_order() {
+    // Both validators fail; the first one declared should win.
+    let value = obj(vec![("port", int(0)), ("min", int(10)), ("max", int(1))]);
+
+    let err = <ValidatedConfig as FromValueTrait>::from_value(&value).unwrap_err();
+    assert!(err.to_string().contains("port must be non-zero"));
+}
+
+#[test]
+fn test_flatten_map_captures_unmapped_keys() {
+    let value = obj(vec![
+        ("host", str("localhost")),
+        ("listen_port", int(8080)),
+        ("tls", obj(vec![("enabled", bool_val(true))])),
+        ("region", str("us-east-1")),
+        ("replicas", int(3)),
+    ]);
+
+    let config = <ConfigWithExtra as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 8080);
+    assert!(config.tls);
+    assert_eq!(config.extra.len(), 2);
+    assert_eq!(config.extra.get("region"), Some(&str("us-east-1")));
+    assert_eq!(config.extra.get("replicas"), Some(&int(3)));
+}
+
+#[test]
+fn test_consumed_keys() {
+    let keys = |keys: Option<Vec<String>>| {
+        let mut keys = keys.unwrap();
+        keys.sort();
+        keys
+    };
+
+    assert_eq!(
+        keys(<ConfigWithRename as FromValueTrait>::consumed_keys()),
+        ["server_host", "server_port"]
+    );
+    assert_eq!(
+        keys(<ConfigWithPath as FromValueTrait>::consumed_keys()),
+        ["connection"]
+    );
+    assert_eq!(
+        keys(<ConfigWithFlattenedStruct as FromValueTrait>::consumed_keys()),
+        ["host", "name", "port"]
+    );
+
+    // Structs that capture leftovers themselves, and non-derived types
+    assert!(<ConfigWithExtra as FromValueTrait>::consumed_keys().is_none());
+    assert!(<String as FromValueTrait>::consumed_keys().is_none());
+}
+
+#[test]
+fn test_with_extras_captures_unknown_keys() {
+    let value = obj(vec![
+        ("host", str("localhost")),
+        ("port", int(8080)),
+        ("feature_flags", arr(vec![str("beta")])),
+        ("added_in_v2", bool_val(true)),
+    ]);
+
+    let config = <WithExtras<SimpleConfig> as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(
+        config.value,
+        SimpleConfig {
+            host: "localhost".to_string(),
+            port: 8080,
+        }
+    );
+    assert_eq!(config.extras.len(), 2);
+    assert_eq!(config.extras["feature_flags"], arr(vec![str("beta")]));
+    assert_eq!(config.extras["added_in_v2"], bool_val(true));
+
+    // Extras and known fields together cover the whole input
+    let mut rebuilt = config.extras.clone();
+    rebuilt.insert("host".to_string(), str(&config.value.host));
+    rebuilt.insert("port".to_string(), int(config.value.port.into()));
+    assert_eq!(ConfigValue::Object(rebuilt), value);
+
+    let config = Config::new(obj(vec![("server", value)]));
+    let server: WithExtras<ServerConfig> = config.get("server").unwrap();
+    assert_eq!(server.value.port, 8080);
+    assert_eq!(server.extras.len(), 2);
+}
+
+#[test]
+fn test_with_extras_errors() {
+    let value = obj(vec![("host", str("localhost"))]);
+    match <WithExtras<SimpleConfig> as FromValueTrait>::from_value(&value) {
+        Err(prefer::Error::KeyNotFound(key)) => assert_eq!(key, "port"),
+        other => panic!("expected KeyNotFound, got {:?}", other),
+    }
+
+    let value = obj(vec![("a", int(1))]);
+    let result =
+        <WithExtras<std::collections::HashMap<String, i64>> as FromValueTrait>::from_value(&value);
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("does not report the keys it reads"));
+}
+
+#[test]
+fn test_flatten_map_skips_keys_of_flattened_structs() {
+    let value = obj(vec![
+        ("name", str("api")),
+        ("host", str("localhost")),
+        ("port", int(8080)),
+        ("region", str("us-east-1")),
+    ]);
+
+    let config = <ConfigWithFlattenedStructAndExtra as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(config.server.host, "localhost");
+    assert_eq!(config.server.port, 8080);
+    assert_eq!(config.extra.len(), 1);
+    assert_eq!(config.extra.get("region"), Some(&str("us-east-1")));
+}
+
+#[test]
+fn test_flatten_map_empty_when_all_keys_mapped() {
+    let value = obj(vec![("host", str("localhost")), ("listen_port", int(8080))]);
+
+    let config = <ConfigWithExtra as FromValueTrait>::from_value(&value).unwrap();
+    assert!(config.extra.is_empty());
+}
+
+#[test]
+fn test_coerce_parses_string_values() {
+    let value = obj(vec![
+        ("port", str("8080")),
+        ("debug", str("TRUE")),
+        ("timeout", str("2.5")),
+        ("name", str("42")),
+    ]);
+
+    let config = <ConfigWithCoercion as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(
+        config,
+        ConfigWithCoercion {
+            port: 8080,
+            debug: true,
+            timeout: Some(2.5),
+            workers: 4,
+            name: "42".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_coerce_parses_leading_zeros() {
+    let value = obj(vec![
+        ("port", str("08080")),
+        ("debug", str("false")),
+        ("timeout", ConfigValue::Null),
+        ("workers", str("007")),
+        ("name", str("api")),
+    ]);
+
+    let config = <ConfigWithCoercion as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.timeout, None);
+    assert_eq!(config.workers, 7);
+}
+
+#[test]
+fn test_coerce_accepts_typed_values() {
+    let value = obj(vec![
+        ("port", int(8080)),
+        ("debug", ConfigValue::Bool(false)),
+        ("workers", int(2)),
+        ("name", str("api")),
+    ]);
+
+    let config = <ConfigWithCoercion as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(config.port, 8080);
+    assert!(!config.debug);
+    assert_eq!(config.timeout, None);
+    assert_eq!(config.workers, 2);
+}
+
+#[test]
+fn test_coerce_reports_unparseable_string() {
+    let value = obj(vec![
+        ("port", str("http")),
+        ("debug", str("true")),
+        ("name", str("api")),
+    ]);
+
+    match <ConfigWithCoercion as FromValueTrait>::from_value(&value) {
+        Err(prefer::Error::ConversionError { key, .. }) => assert_eq!(key, "port"),
+        other => panic!("expected ConversionError, got {:?}", other),
+    }
+
+    // Without `coerce`, strings are not parsed
+    let strict = obj(vec![("host", str("localhost")), ("port", str("8080"))]);
+    assert!(<ServerConfig as FromValueTrait>::from_value(&strict).is_err());
+}
+
+#[test]
+fn test_derive_default_uses_literal_defaults() {
+    let config = ConfigWithDerivedDefault::default();
+    assert_eq!(
+        config,
+        ConfigWithDerivedDefault {
+            host: "localhost".to_string(),
+            port: 8080,
+            debug: true,
+            tags: vec![],
+            name: None,
+        }
+    );
+}
+
+#[test]
+fn test_derive_default_matches_empty_config() {
+    let config = <ConfigWithDerivedDefault as FromValueTrait>::from_value(&obj(vec![])).unwrap();
+    assert_eq!(config, ConfigWithDerivedDefault::default());
+}
+
+#[test]
+fn test_box_char_and_cow_fields() {
+    let value = obj(vec![
+        ("port", int(8080)),
+        ("separator", str(",")),
+        ("label", str("primary")),
+    ]);
+
+    let config = <ConfigWithWrappers as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(*config.port, 8080);
+    assert_eq!(config.separator, ',');
+    assert_eq!(config.label, "primary");
+
+    let value = obj(vec![
+        ("port", int(8080)),
+        ("separator", str(", ")),
+        ("label", str("primary")),
+    ]);
+    assert!(<ConfigWithWrappers as FromValueTrait>::from_value(&value).is_err());
+}
+
+#[test]
+fn test_one_or_many_field() {
+    let value = obj(vec![
+        ("hosts", str("a.example.com")),
+        ("ports", arr(vec![int(80), int(443)])),
+    ]);
+
+    let config = <ConfigWithOneOrMany as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(config.hosts.into_vec(), vec!["a.example.com".to_string()]);
+    assert_eq!(config.ports.into_vec(), vec![80, 443]);
+
+    let value = obj(vec![("hosts", arr(vec![])), ("ports", int(8080))]);
+    let config = <ConfigWithOneOrMany as FromValueTrait>::from_value(&value).unwrap();
+    assert!(config.hosts.is_empty());
+    assert_eq!(*config.ports, [8080]);
+}
+
+#[test]
+fn test_maybe_field_distinguishes_absent_and_null() {
+    let absent = <ConfigWithMaybe as FromValueTrait>::from_value(&obj(vec![])).unwrap();
+    assert_eq!(absent.x, Maybe::Absent);
+
+    let value = obj(vec![("x", ConfigValue::Null)]);
+    let null = <ConfigWithMaybe as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(null.x, Maybe::Null);
+
+    let value = obj(vec![("x", int(5))]);
+    let present = <ConfigWithMaybe as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(present.x, Maybe::Value(5));
+
+    let value = obj(vec![("x", str("five"))]);
+    assert!(<ConfigWithMaybe as FromValueTrait>::from_value(&value).is_err());
+}
+
+#[test]
+fn test_phantom_data_and_unit_fields() {
+    let value = obj(vec![("name", str("app"))]);
+    let config = <ConfigWithMarker as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(
+        config,
+        ConfigWithMarker {
+            name: "app".to_string(),
+            _marker: PhantomData,
+            unit: (),
+        }
+    );
+
+    // Present values are ignored
+    let value = obj(vec![
+        ("name", str("app")),
+        ("_marker", int(1)),
+        ("unit", ConfigValue::Null),
+    ]);
+    assert!(<ConfigWithMarker as FromValueTrait>::from_value(&value).is_ok());
+
+    let value = obj(vec![("id", int(7))]);
+    let id = <TypedId<SimpleConfig> as FromValueTrait>::from_value(&value).unwrap();
+    assert_eq!(id.id, 7);
+}
@@ -0,0 +1,126 @@
+//! Tests for overriding built-in formatters.
+//!
+//! These live in their own test binary because the runtime registration
+//! below replaces the JSON formatter for the whole process.
+
+use prefer::formatter::json::JsonFormatter;
+use prefer::formatter::Formatter;
+use prefer::registry::{self, RegisteredFormatter};
+use prefer::ConfigValue;
+
+/// A JSON formatter that tags everything it parses.
+struct TaggingJsonFormatter;
+
+impl Formatter for TaggingJsonFormatter {
+    fn provides(&self, identifier: &str) -> bool {
+        prefer::formatter::extension_matches(identifier, self.extensions())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+
+    fn deserialize(&self, content: &str) -> prefer::Result<ConfigValue> {
+        let mut value = JsonFormatter.deserialize(content)?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert("tagged".to_string(), ConfigValue::Bool(true));
+        }
+        Ok(value)
+    }
+
+    fn serialize(&self, value: &ConfigValue) -> prefer::Result<String> {
+        JsonFormatter.serialize(value)
+    }
+
+    fn name(&self) -> &str {
+        "tagging-json"
+    }
+}
+
+/// Two formatters for the same extension, told apart by priority.
+struct PriorityFormatter {
+    name: &'static str,
+    priority: i32,
+}
+
+impl Formatter for PriorityFormatter {
+    fn provides(&self, identifier: &str) -> bool {
+        prefer::formatter::extension_matches(identifier, self.extensions())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["priotest"]
+    }
+
+    fn deserialize(&self, _content: &str) -> prefer::Result<ConfigValue> {
+        Ok(ConfigValue::Null)
+    }
+
+    fn serialize(&self, _value: &ConfigValue) -> prefer::Result<String> {
+        Ok(String::new())
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+}
+
+static HIGH_PRIORITY: PriorityFormatter = PriorityFormatter {
+    name: "prio-high",
+    priority: 10,
+};
+
+inventory::submit! { RegisteredFormatter(&HIGH_PRIORITY) }
+
+#[tokio::test]
+async fn test_runtime_json_formatter_overrides_builtin() {
+    registry::register_formatter(Box::new(TaggingJsonFormatter));
+
+    assert_eq!(
+        registry::find_formatter("config.json").unwrap().name(),
+        "tagging-json"
+    );
+
+    let names: Vec<&str> = registry::formatters_for_extension("json")
+        .iter()
+        .map(|f| f.name())
+        .collect();
+    assert_eq!(names, vec!["tagging-json", "json"]);
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("app.json");
+    std::fs::write(&path, r#"{"port": 8080}"#).unwrap();
+
+    let config = prefer::Config::load_from_path(&path).await.unwrap();
+    assert_eq!(config.get::<u16>("port").unwrap(), 8080);
+    assert!(config.get::<bool>("tagged").unwrap());
+}
+
+#[test]
+fn test_higher_priority_formatter_wins_over_runtime() {
+    registry::register_formatter(Box::new(PriorityFormatter {
+        name: "prio-low",
+        priority: 0,
+    }));
+
+    assert_eq!(
+        registry::find_formatter("settings.priotest")
+            .unwrap()
+            .name(),
+        "prio-high"
+    );
+    let names: Vec<&str> = registry::formatters_for_extension("priotest")
+        .iter()
+        .map(|f| f.name())
+        .collect();
+    assert_eq!(names, vec!["prio-high", "prio-low"]);
+}
+
+#[test]
+fn test_formatters_for_extension_unknown() {
+    assert!(registry::formatters_for_extension("bson").is_empty());
+}
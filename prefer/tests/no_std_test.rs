@@ -83,3 +83,48 @@ fn test_nested_values_no_std() {
     let values: Vec<i64> = FromValueTrait::from_value(&array).unwrap();
     assert_eq!(values, vec![1, 2, 3]);
 }
+
+#[test]
+fn test_to_json_string_no_std() {
+    #[cfg(not(feature = "std"))]
+    use alloc::collections::BTreeMap as HashMap;
+    #[cfg(feature = "std")]
+    use std::collections::HashMap;
+
+    let mut server = HashMap::new();
+    server.insert("port".to_string(), ConfigValue::Integer(8080));
+    server.insert("ratio".to_string(), ConfigValue::Float(1.0));
+    server.insert("nan".to_string(), ConfigValue::Float(f64::NAN));
+
+    let mut root = HashMap::new();
+    root.insert("server".to_string(), ConfigValue::Object(server));
+    root.insert(
+        "name".to_string(),
+        ConfigValue::String("say \"hi\"\\\n\t\u{1}".to_string()),
+    );
+    root.insert(
+        "tags".to_string(),
+        ConfigValue::Array(vec![ConfigValue::Bool(true), ConfigValue::Null]),
+    );
+    root.insert("raw".to_string(), ConfigValue::Bytes(vec![0, 255]));
+    root.insert("empty".to_string(), ConfigValue::Object(HashMap::new()));
+
+    assert_eq!(
+        ConfigValue::Object(root).to_json_string(),
+        r#"{"empty":{},"name":"say \"hi\"\\\n\t\u0001","raw":"AP8=","server":{"nan":null,"port":8080,"ratio":1.0},"tags":[true,null]}"#
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_to_json_string_parses_back() {
+    use prefer::formatter::{json::JsonFormatter, Formatter};
+
+    let value = ConfigValue::Array(vec![
+        ConfigValue::String("line\nbreak \u{7f} é".to_string()),
+        ConfigValue::Float(-2.5e-8),
+        ConfigValue::Integer(-42),
+    ]);
+    let parsed = JsonFormatter.deserialize(&value.to_json_string()).unwrap();
+    assert_eq!(parsed, value);
+}
@@ -1,14 +1,18 @@
 //! Tests for the registry-based load/watch pipeline.
 
 use async_trait::async_trait;
+use prefer::formatter::Formatter;
 use prefer::loader::db::{ColumnValue, ConfigEntry, ConfigLoader, DbLoader};
 use prefer::loader::file::FileLoader;
-use prefer::loader::Loader;
+use prefer::loader::{LoadResult, Loader};
 use prefer::registry;
 use prefer::registry::RegisteredLoader;
 use serial_test::serial;
 use std::collections::BTreeMap;
 use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tempfile::TempDir;
 
 struct RawJsonDbLoader;
@@ -85,6 +89,290 @@ inventory::submit! { RegisteredLoader(&RAW_JSON_LOADER) }
 inventory::submit! { RegisteredLoader(&RAW_TOML_LOADER) }
 inventory::submit! { RegisteredLoader(&COLUMNAR_LOADER) }
 
+/// A loader that intercepts a bare name the file loader would also accept.
+struct PriorityLoader;
+
+#[async_trait]
+impl Loader for PriorityLoader {
+    fn provides(&self, identifier: &str) -> bool {
+        identifier == "intercepted-app"
+    }
+
+    async fn load(
+        &self,
+        identifier: &str,
+        _formatters: &[&dyn Formatter],
+    ) -> prefer::Result<LoadResult> {
+        Ok(LoadResult::new(
+            identifier,
+            prefer::ConfigValue::Object(Default::default()),
+        ))
+    }
+
+    fn name(&self) -> &str {
+        // Sorts after "file", so only priority can make this win
+        "zz-priority"
+    }
+
+    fn priority(&self) -> i32 {
+        10
+    }
+}
+
+static PRIORITY_LOADER: PriorityLoader = PriorityLoader;
+
+inventory::submit! { RegisteredLoader(&PRIORITY_LOADER) }
+
+/// A loader configured at runtime, as if from command-line arguments.
+struct RuntimeLoader {
+    suffix: String,
+    host: String,
+}
+
+#[async_trait]
+impl Loader for RuntimeLoader {
+    fn provides(&self, identifier: &str) -> bool {
+        identifier.ends_with(&self.suffix)
+    }
+
+    async fn load(
+        &self,
+        identifier: &str,
+        _formatters: &[&dyn Formatter],
+    ) -> prefer::Result<LoadResult> {
+        let mut data = std::collections::HashMap::new();
+        data.insert(
+            "host".to_string(),
+            prefer::ConfigValue::String(self.host.clone()),
+        );
+        Ok(LoadResult::new(
+            identifier,
+            prefer::ConfigValue::Object(data),
+        ))
+    }
+
+    fn name(&self) -> &str {
+        "runtime"
+    }
+}
+
+/// A line-based `key=value` formatter registered at runtime.
+struct KeyValueFormatter;
+
+impl Formatter for KeyValueFormatter {
+    fn provides(&self, identifier: &str) -> bool {
+        prefer::formatter::extension_matches(identifier, self.extensions())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["kvtest"]
+    }
+
+    fn deserialize(&self, content: &str) -> prefer::Result<prefer::ConfigValue> {
+        Ok(prefer::ConfigValue::Object(
+            content
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .map(|(k, v)| (k.to_string(), prefer::ConfigValue::String(v.to_string())))
+                .collect(),
+        ))
+    }
+
+    fn serialize(&self, _value: &prefer::ConfigValue) -> prefer::Result<String> {
+        Ok(String::new())
+    }
+
+    fn name(&self) -> &str {
+        "kvtest"
+    }
+}
+
+/// A loader without native watching, backed by a swappable `MemorySource`.
+struct PollingLoader {
+    source: Arc<tokio::sync::Mutex<prefer::MemorySource>>,
+    loads: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Loader for PollingLoader {
+    fn provides(&self, identifier: &str) -> bool {
+        identifier.starts_with("polltest://")
+    }
+
+    #[allow(deprecated)]
+    async fn load(
+        &self,
+        identifier: &str,
+        _formatters: &[&dyn Formatter],
+    ) -> prefer::Result<LoadResult> {
+        use prefer::source::Source;
+
+        self.loads.fetch_add(1, Ordering::SeqCst);
+        Ok(LoadResult::new(
+            identifier,
+            self.source.lock().await.load().await?,
+        ))
+    }
+
+    fn name(&self) -> &str {
+        "polltest"
+    }
+}
+
+fn port_config(port: i64) -> prefer::ConfigValue {
+    prefer::ConfigValue::Object(
+        [("port".to_string(), prefer::ConfigValue::Integer(port))]
+            .into_iter()
+            .collect(),
+    )
+}
+
+#[tokio::test]
+async fn test_watch_with_interval_polls_loader_without_watch() {
+    let source = Arc::new(tokio::sync::Mutex::new(prefer::MemorySource::new(
+        port_config(1),
+    )));
+    let loads = Arc::new(AtomicUsize::new(0));
+    registry::register_loader(Box::new(PollingLoader {
+        source: source.clone(),
+        loads: loads.clone(),
+    }));
+
+    assert!(!registry::supports_watch("polltest://app"));
+    assert!(matches!(
+        prefer::watch("polltest://app").await,
+        Err(prefer::Error::WatchNotSupported(_))
+    ));
+
+    let mut receiver = prefer::watch_with_interval("polltest://app", Duration::from_millis(10))
+        .await
+        .unwrap();
+
+    // Unchanged data is not re-sent
+    let unchanged = tokio::time::timeout(Duration::from_millis(100), receiver.recv()).await;
+    assert!(unchanged.is_err());
+
+    *source.lock().await = prefer::MemorySource::new(port_config(2));
+
+    let config = tokio::time::timeout(Duration::from_secs(2), receiver.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(config.get::<i64>("port").unwrap(), 2);
+    assert_eq!(config.loader_name(), Some("polltest"));
+
+    // Dropping the receiver stops the polling task
+    drop(receiver);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let stopped_at = loads.load(Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(loads.load(Ordering::SeqCst), stopped_at);
+}
+
+#[tokio::test]
+async fn test_register_loader_at_runtime() {
+    assert!(registry::find_loader("runtime://service").is_none());
+
+    registry::register_loader(Box::new(RuntimeLoader {
+        suffix: "runtime://service".to_string(),
+        host: "cli.example.com".to_string(),
+    }));
+
+    let config = prefer::load("runtime://service").await.unwrap();
+    let host: String = config.get("host").unwrap();
+    assert_eq!(host, "cli.example.com");
+    assert_eq!(config.loader_name(), Some("runtime"));
+}
+
+#[test]
+fn test_runtime_loader_takes_precedence() {
+    // Bare names are normally handled by the file loader
+    assert_eq!(
+        registry::find_loader("app.precedencetest").unwrap().name(),
+        "file"
+    );
+
+    registry::register_loader(Box::new(RuntimeLoader {
+        suffix: ".precedencetest".to_string(),
+        host: String::new(),
+    }));
+
+    assert_eq!(
+        registry::find_loader("app.precedencetest").unwrap().name(),
+        "runtime"
+    );
+}
+
+#[test]
+fn test_register_formatter_at_runtime() {
+    registry::register_formatter(Box::new(KeyValueFormatter));
+
+    let fmt = registry::find_formatter("settings.kvtest").unwrap();
+    assert_eq!(fmt.name(), "kvtest");
+    assert_eq!(
+        registry::find_formatter_by_hint("kvtest").unwrap().name(),
+        "kvtest"
+    );
+
+    let data = fmt.deserialize("host=localhost\nport=80").unwrap();
+    assert_eq!(
+        data.get("host"),
+        Some(&prefer::ConfigValue::String("localhost".to_string()))
+    );
+    assert!(registry::collect_formatters()
+        .iter()
+        .any(|f| f.name() == "kvtest"));
+}
+
+#[test]
+fn test_higher_priority_loader_wins() {
+    assert!(FileLoader::new().provides("intercepted-app"));
+
+    let loader = registry::find_loader("intercepted-app").unwrap();
+    assert_eq!(loader.name(), "zz-priority");
+
+    // Other bare names still go to the file loader
+    assert_eq!(registry::find_loader("myapp").unwrap().name(), "file");
+}
+
+#[test]
+fn test_list_formatters_includes_builtins() {
+    let formatters = registry::list_formatters();
+    for name in ["json", "yaml", "toml", "ini", "xml"] {
+        assert!(formatters.contains(&name), "missing formatter {name}");
+    }
+
+    let extensions = registry::list_extensions();
+    for ext in ["json", "json5", "yaml", "yml", "toml", "ini", "xml"] {
+        assert!(extensions.contains(&ext), "missing extension {ext}");
+    }
+}
+
+#[test]
+fn test_list_loaders_includes_registered() {
+    let loaders = registry::list_loaders();
+    assert!(loaders.contains(&"file"));
+    assert!(loaders.contains(&"raw-json-integration"));
+    assert!(loaders.contains(&"columnar-integration"));
+}
+
+#[test]
+fn test_supports_watch_for_file_identifiers() {
+    assert!(registry::supports_watch("myapp"));
+    assert!(registry::supports_watch("file:///etc/myapp.toml"));
+}
+
+#[test]
+fn test_supports_watch_false_for_db_identifiers() {
+    assert!(!registry::supports_watch("rawjson://settings"));
+    assert!(!registry::supports_watch("colint://mydb/config"));
+}
+
+#[test]
+fn test_supports_watch_false_without_loader() {
+    assert!(!registry::supports_watch("unknowndb://settings"));
+}
+
 #[test]
 fn test_file_loader_provides_bare_names() {
     let loader = FileLoader::new();
@@ -166,6 +454,20 @@ fn test_find_formatter_by_hint() {
     assert_eq!(fmt.unwrap().name(), "yaml");
 }
 
+#[test]
+fn test_find_formatter_by_content() {
+    let fmt = registry::find_formatter_by_content(r#"{"name": "app"}"#);
+    assert_eq!(fmt.unwrap().name(), "json");
+
+    let fmt = registry::find_formatter_by_content("name = \"app\"\nport = 8080\n");
+    assert_eq!(fmt.unwrap().name(), "toml");
+
+    let fmt = registry::find_formatter_by_content("name: app\nport: 8080\n");
+    assert_eq!(fmt.unwrap().name(), "yaml");
+
+    assert!(registry::find_formatter_by_content("just some words").is_none());
+}
+
 #[test]
 fn test_find_formatter_by_hint_returns_none_for_unknown() {
     let fmt = registry::find_formatter_by_hint("bson");
@@ -193,6 +495,7 @@ async fn test_load_routes_to_file_loader() {
 
     // Verify metadata was populated
     assert_eq!(config.loader_name(), Some("file"));
+    assert_eq!(config.format(), Some("json"));
     assert!(config.source().is_some());
 
     std::env::set_current_dir(original_dir).unwrap();
@@ -295,6 +598,7 @@ async fn test_load_yaml_routes_correctly() {
     let host: String = config.get("host").unwrap();
     assert_eq!(host, "localhost");
     assert_eq!(config.loader_name(), Some("file"));
+    assert_eq!(config.format(), Some("yaml"));
 
     std::env::set_current_dir(original_dir).unwrap();
 }
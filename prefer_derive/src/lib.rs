@@ -20,7 +20,8 @@
 
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Ident, Type};
 
 /// Derive the `FromValue` trait for a struct or enum.
@@ -30,10 +31,34 @@ use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Ident,
 /// ## Field Attributes
 ///
 /// - `#[prefer(rename = "name")]` - Use a different key name in the config
+/// - `#[prefer(path = "a.b")]` - Read the field from a nested path within the object
 /// - `#[prefer(default)]` - Use `Default::default()` if the field is missing
 /// - `#[prefer(default = "value")]` - Use a literal value if the field is missing
 /// - `#[prefer(skip)]` - Skip this field during deserialization (requires Default)
-/// - `#[prefer(flatten)]` - Flatten a nested struct into the parent
+/// - `#[prefer(flatten)]` - Flatten a nested struct into the parent. On a
+///   `HashMap`/`BTreeMap` field, captures every key not consumed by another
+///   field instead. Only one such capture field is allowed per struct.
+/// - `#[prefer(coerce)]` - If the value is a string that doesn't convert
+///   directly, parse it with the field type's `FromStr` (see
+///   `prefer::value::from_value_coerced`). The field type, or the `T` in
+///   `Option<T>`, must implement `FromStr`. Useful for string-only sources
+///   like env vars and INI files.
+///
+/// `Option<T>` fields are `None` when the key is missing or `null`. Use
+/// `prefer::value::Maybe<T>` instead to tell those two cases apart.
+/// `PhantomData<T>` and `()` fields may be missing too.
+///
+/// Derived structs also report the keys they read through
+/// `FromValue::consumed_keys()`, so they can be wrapped in
+/// `prefer::WithExtras` to collect unknown keys.
+///
+/// ## Container Attributes (for structs)
+///
+/// - `#[prefer(validate = "path::to::fn")]` - Call `fn(&Self) -> Result<(), E>` after
+///   construction; an `Err` becomes a `ConversionError`. May be repeated, and
+///   validators run in the order given.
+/// - `#[prefer(derive_default)]` - Also implement `Default`, using each field's
+///   `default = "value"` literal, or `Default::default()` for other fields.
 ///
 /// ## Container Attributes (for enums)
 ///
@@ -78,10 +103,30 @@ fn derive_from_value_impl(input: DeriveInput) -> Result<TokenStream2, Error> {
     let generics = &input.generics;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let container_attrs = parse_container_attrs(&input.attrs)?;
+
     match &input.data {
-        Data::Struct(data) => derive_struct(name, impl_generics, ty_generics, where_clause, data),
+        Data::Struct(data) => derive_struct(
+            name,
+            impl_generics,
+            ty_generics,
+            where_clause,
+            data,
+            container_attrs,
+        ),
         Data::Enum(data) => {
-            let container_attrs = parse_container_attrs(&input.attrs)?;
+            if !container_attrs.validate.is_empty() {
+                return Err(Error::new_spanned(
+                    name,
+                    "`validate` is only supported on structs",
+                ));
+            }
+            if container_attrs.derive_default {
+                return Err(Error::new_spanned(
+                    name,
+                    "`derive_default` is only supported on structs",
+                ));
+            }
             derive_enum(
                 name,
                 impl_generics,
@@ -101,15 +146,19 @@ fn derive_from_value_impl(input: DeriveInput) -> Result<TokenStream2, Error> {
 #[derive(Default)]
 struct ContainerAttrs {
     tag: Option<String>,
+    validate: Vec<syn::Path>,
+    derive_default: bool,
 }
 
 #[derive(Default)]
 struct FieldAttrs {
     rename: Option<String>,
+    path: Option<String>,
     default: Option<DefaultValue>,
     skip: bool,
     flatten: bool,
     required: bool,
+    coerce: bool,
 }
 
 enum DefaultValue {
@@ -129,6 +178,11 @@ fn parse_container_attrs(attrs: &[Attribute]) -> Result<ContainerAttrs, Error> {
             if meta.path.is_ident("tag") {
                 let value: syn::LitStr = meta.value()?.parse()?;
                 container.tag = Some(value.value());
+            } else if meta.path.is_ident("validate") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                container.validate.push(value.parse()?);
+            } else if meta.path.is_ident("derive_default") {
+                container.derive_default = true;
             }
             Ok(())
         })?;
@@ -149,6 +203,9 @@ fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs, Error> {
             if meta.path.is_ident("rename") {
                 let value: syn::LitStr = meta.value()?.parse()?;
                 field_attrs.rename = Some(value.value());
+            } else if meta.path.is_ident("path") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                field_attrs.path = Some(value.value());
             } else if meta.path.is_ident("default") {
                 if meta.input.peek(syn::Token![=]) {
                     let value: syn::LitStr = meta.value()?.parse()?;
@@ -162,6 +219,8 @@ fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs, Error> {
                 field_attrs.flatten = true;
             } else if meta.path.is_ident("required") {
                 field_attrs.required = true;
+            } else if meta.path.is_ident("coerce") {
+                field_attrs.coerce = true;
             }
             Ok(())
         })?;
@@ -176,6 +235,7 @@ fn derive_struct(
     ty_generics: syn::TypeGenerics,
     where_clause: Option<&syn::WhereClause>,
     data: &syn::DataStruct,
+    container_attrs: ContainerAttrs,
 ) -> Result<TokenStream2, Error> {
     let fields = match &data.fields {
         Fields::Named(fields) => &fields.named,
@@ -186,32 +246,115 @@ fn derive_struct(
             ))
         }
         Fields::Unit => {
+            let default_impl = container_attrs.derive_default.then(|| {
+                quote! {
+                    impl #impl_generics ::core::default::Default for #name #ty_generics #where_clause {
+                        fn default() -> Self {
+                            Self
+                        }
+                    }
+                }
+            });
             return Ok(quote! {
                 impl #impl_generics prefer::FromValue for #name #ty_generics #where_clause {
                     fn from_value(_value: &prefer::ConfigValue) -> prefer::Result<Self> {
                         Ok(Self)
                     }
                 }
+
+                #default_impl
             });
         }
     };
 
     let mut field_extractions = Vec::new();
+    let mut field_defaults = Vec::new();
+    let mut consumed_keys = Vec::new();
+    let mut flattened_types = Vec::new();
+    let mut capture_field = None;
+
+    for field in fields {
+        let attrs = parse_field_attrs(&field.attrs)?;
+        if attrs.flatten && !is_map_type(&field.ty) {
+            flattened_types.push(&field.ty);
+        }
+        if attrs.skip || attrs.flatten {
+            continue;
+        }
+        let key = match (&attrs.path, &attrs.rename) {
+            (Some(path), _) => path.split('.').next().unwrap_or_default().to_string(),
+            (None, Some(rename)) => rename.clone(),
+            (None, None) => field.ident.as_ref().unwrap().to_string(),
+        };
+        // Several `path` fields may share a top-level key
+        if !consumed_keys.contains(&key) {
+            consumed_keys.push(key);
+        }
+    }
 
     for field in fields {
         let field_name = field.ident.as_ref().unwrap();
         let field_type = &field.ty;
         let attrs = parse_field_attrs(&field.attrs)?;
 
+        if attrs.rename.is_some() && attrs.path.is_some() {
+            return Err(Error::new_spanned(
+                field,
+                "`rename` and `path` cannot be used together",
+            ));
+        }
+
         let key_name = attrs
-            .rename
+            .path
             .clone()
+            .or_else(|| attrs.rename.clone())
             .unwrap_or_else(|| field_name.to_string());
 
+        let convert = from_value_fn(field_type, attrs.coerce);
+
+        let lookup = match &attrs.path {
+            Some(path) => {
+                let segments = path.split('.');
+                quote! {
+                    Some(value)#(.and_then(|v| v.get(#segments)))*
+                }
+            }
+            None => quote! { obj.get(#key_name) },
+        };
+
         let extraction = if attrs.skip {
             quote! {
                 #field_name: ::core::default::Default::default()
             }
+        } else if attrs.flatten && is_map_type(field_type) {
+            if capture_field.replace(field_name).is_some() {
+                return Err(Error::new_spanned(
+                    field,
+                    "only one flattened map field is allowed",
+                ));
+            }
+            // Keys read by flattened structs are only known at runtime
+            quote! {
+                #field_name: {
+                    let mut flattened: Vec<String> = Vec::new();
+                    #(
+                        if let Some(keys) = <#flattened_types as prefer::FromValue>::consumed_keys() {
+                            flattened.extend(keys);
+                        }
+                    )*
+                    <#field_type as prefer::FromValue>::from_value(
+                        &prefer::ConfigValue::Object(
+                            obj.iter()
+                                .filter(|(k, _)| {
+                                    ![#(#consumed_keys),*].contains(&k.as_str())
+                                        && !flattened.contains(k)
+                                })
+                                .map(|(k, v)| (k.clone(), v.clone()))
+                                .collect()
+                        )
+                    )?
+                }
+            }
         } else if attrs.flatten {
             quote! {
                 #field_name: <#field_type as prefer::FromValue>::from_value(value)?
@@ -219,16 +362,16 @@ fn derive_struct(
         } else if attrs.required {
             // Required fields must always be present, even if Option type
             quote! {
-                #field_name: <#field_type as prefer::FromValue>::from_value(
-                    obj.get(#key_name).ok_or_else(|| prefer::Error::KeyNotFound(#key_name.to_string()))?
+                #field_name: #convert(
+                    #lookup.ok_or_else(|| prefer::Error::KeyNotFound(#key_name.to_string()))?
                 ).map_err(|e| e.with_key(#key_name))?
             }
         } else {
             match &attrs.default {
                 Some(DefaultValue::Default) => {
                     quote! {
-                        #field_name: obj.get(#key_name)
-                            .map(|v| <#field_type as prefer::FromValue>::from_value(v))
+                        #field_name: #lookup
+                            .map(|v| #convert(v))
                             .transpose()
                             .map_err(|e| e.with_key(#key_name))?
                             .unwrap_or_default()
@@ -237,8 +380,8 @@ fn derive_struct(
                 Some(DefaultValue::Literal(lit)) => {
                     let default_expr = generate_default_expr(field_type, lit)?;
                     quote! {
-                        #field_name: obj.get(#key_name)
-                            .map(|v| <#field_type as prefer::FromValue>::from_value(v))
+                        #field_name: #lookup
+                            .map(|v| #convert(v))
                             .transpose()
                             .map_err(|e| e.with_key(#key_name))?
                             .unwrap_or_else(|| #default_expr)
@@ -247,16 +390,24 @@ fn derive_struct(
                 None => {
                     if is_option_type(field_type) {
                         quote! {
-                            #field_name: obj.get(#key_name)
-                                .map(|v| <#field_type as prefer::FromValue>::from_value(v))
+                            #field_name: #lookup
+                                .map(|v| #convert(v))
                                 .transpose()
                                 .map_err(|e| e.with_key(#key_name))?
                                 .flatten()
                         }
+                    } else if defaults_when_missing(field_type) {
+                        quote! {
+                            #field_name: #lookup
+                                .map(|v| #convert(v))
+                                .transpose()
+                                .map_err(|e| e.with_key(#key_name))?
+                                .unwrap_or_default()
+                        }
                     } else {
                         quote! {
-                            #field_name: <#field_type as prefer::FromValue>::from_value(
-                                obj.get(#key_name).ok_or_else(|| prefer::Error::KeyNotFound(#key_name.to_string()))?
+                            #field_name: #convert(
+                                #lookup.ok_or_else(|| prefer::Error::KeyNotFound(#key_name.to_string()))?
                             ).map_err(|e| e.with_key(#key_name))?
                         }
                     }
@@ -265,9 +416,47 @@ fn derive_struct(
         };
 
         field_extractions.push(extraction);
+
+        if container_attrs.derive_default {
+            let default_expr = match &attrs.default {
+                Some(DefaultValue::Literal(lit)) => generate_default_expr(field_type, lit)?,
+                // Spanned so a missing `Default` impl is reported at the field
+                _ => quote_spanned! {field_type.span()=>
+                    <#field_type as ::core::default::Default>::default()
+                },
+            };
+            field_defaults.push(quote! { #field_name: #default_expr });
+        }
     }
 
     let type_name = name.to_string();
+    let validators = container_attrs.validate;
+
+    // A flattened map reads every key, so there are no extras to report
+    let consumed_keys_fn = if capture_field.is_some() {
+        quote! {}
+    } else {
+        quote! {
+            fn consumed_keys() -> Option<Vec<String>> {
+                let mut keys = Vec::new();
+                #( keys.push(#consumed_keys.to_string()); )*
+                #( keys.extend(<#flattened_types as prefer::FromValue>::consumed_keys()?); )*
+                Some(keys)
+            }
+        }
+    };
+
+    let default_impl = container_attrs.derive_default.then(|| {
+        quote! {
+            impl #impl_generics ::core::default::Default for #name #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self {
+                        #(#field_defaults),*
+                    }
+                }
+            }
+        }
+    });
 
     Ok(quote! {
         impl #impl_generics prefer::FromValue for #name #ty_generics #where_clause {
@@ -278,11 +467,25 @@ fn derive_struct(
                     source: "expected object".into(),
                 })?;
 
-                Ok(Self {
+                let result = Self {
                     #(#field_extractions),*
-                })
+                };
+
+                #(
+                    #validators(&result).map_err(|e| prefer::Error::ConversionError {
+                        key: String::new(),
+                        type_name: #type_name.to_string(),
+                        source: format!("{}", e).into(),
+                    })?;
+                )*
+
+                Ok(result)
             }
+
+            #consumed_keys_fn
         }
+
+        #default_impl
     })
 }
 
@@ -319,13 +522,14 @@ fn derive_enum(
                             .rename
                             .clone()
                             .unwrap_or_else(|| field_name.to_string());
+                        let convert = from_value_fn(field_type, field_attrs.coerce);
 
                         let extraction = if field_attrs.skip {
                             quote! { #field_name: ::core::default::Default::default() }
                         } else if let Some(DefaultValue::Default) = field_attrs.default {
                             quote! {
                                 #field_name: obj.get(#key_name)
-                                    .map(|v| <#field_type as prefer::FromValue>::from_value(v))
+                                    .map(|v| #convert(v))
                                     .transpose()?
                                     .unwrap_or_default()
                             }
@@ -333,20 +537,27 @@ fn derive_enum(
                             let default_expr = generate_default_expr(field_type, lit)?;
                             quote! {
                                 #field_name: obj.get(#key_name)
-                                    .map(|v| <#field_type as prefer::FromValue>::from_value(v))
+                                    .map(|v| #convert(v))
                                     .transpose()?
                                     .unwrap_or_else(|| #default_expr)
                             }
                         } else if is_option_type(field_type) {
                             quote! {
                                 #field_name: obj.get(#key_name)
-                                    .map(|v| <#field_type as prefer::FromValue>::from_value(v))
+                                    .map(|v| #convert(v))
                                     .transpose()?
                                     .flatten()
                             }
+                        } else if defaults_when_missing(field_type) {
+                            quote! {
+                                #field_name: obj.get(#key_name)
+                                    .map(|v| #convert(v))
+                                    .transpose()?
+                                    .unwrap_or_default()
+                            }
                         } else {
                             quote! {
-                                #field_name: <#field_type as prefer::FromValue>::from_value(
+                                #field_name: #convert(
                                     obj.get(#key_name).ok_or_else(|| prefer::Error::KeyNotFound(#key_name.to_string()))?
                                 )?
                             }
@@ -498,6 +709,34 @@ fn derive_enum(
     }
 }
 
+/// The function used to convert a field's value, honoring `#[prefer(coerce)]`.
+fn from_value_fn(ty: &Type, coerce: bool) -> TokenStream2 {
+    if !coerce {
+        return quote! { <#ty as prefer::FromValue>::from_value };
+    }
+    match option_inner_type(ty) {
+        Some(inner) => quote! { prefer::value::from_value_coerced_option::<#inner> },
+        None => quote! { prefer::value::from_value_coerced::<#ty> },
+    }
+}
+
+/// The `T` in `Option<T>`, if the type is an `Option`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    if !is_option_type(ty) {
+        return None;
+    }
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    match &type_path.path.segments.last()?.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn is_option_type(ty: &Type) -> bool {
     if let Type::Path(type_path) = ty {
         if let Some(segment) = type_path.path.segments.last() {
@@ -507,6 +746,30 @@ fn is_option_type(ty: &Type) -> bool {
     false
 }
 
+/// Whether a missing key should produce the field type's default value.
+///
+/// True for `Maybe<T>`, `PhantomData<T>`, and `()`.
+fn defaults_when_missing(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Maybe" || segment.ident == "PhantomData"),
+        Type::Tuple(tuple) => tuple.elems.is_empty(),
+        _ => false,
+    }
+}
+
+fn is_map_type(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "HashMap" || segment.ident == "BTreeMap";
+        }
+    }
+    false
+}
+
 fn generate_default_expr(_ty: &Type, literal: &str) -> Result<TokenStream2, Error> {
     // Try to parse as different literal types
     if let Ok(n) = literal.parse::<i64>() {